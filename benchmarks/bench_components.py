@@ -0,0 +1,92 @@
+"""Connected components: union-find in Mesh.connected_components against BFS labeling.
+
+Builds a mesh of many disjoint icospheres and checks that Mesh.connected_components and
+Mesh.component_count agree with a breadth-first labeling over shared vertices, then times
+them. The BFS reference is plain Python, so the timings compare the whole call as used from
+Python rather than the two algorithms in isolation.
+
+    python benchmarks/bench_components.py --shells 2000 --subdivisions 3
+"""
+
+import argparse
+import time
+from collections import deque
+
+import meshalyzer
+
+
+def many_shells(count, subdivisions):
+    positions, indices = meshalyzer.Mesh.sphere(0.4, subdivisions).to_buffers()
+    vertex_count = len(positions) // 3
+
+    all_positions, all_indices = [], []
+    for shell in range(count):
+        offset = [float(shell % 100), float(shell // 100), 0.0]
+        all_positions.extend(p + offset[i % 3] for i, p in enumerate(positions))
+        all_indices.extend(i + shell * vertex_count for i in indices)
+    # shuffle the face order across shells so components aren't contiguous runs of faces
+    faces = [all_indices[i:i + 3] for i in range(0, len(all_indices), 3)]
+    faces = faces[::2] + faces[1::2]
+    return meshalyzer.Mesh.from_buffers(all_positions, [i for face in faces for i in face])
+
+
+def bfs_components(faces, vertex_count):
+    vertex_faces = [[] for _ in range(vertex_count)]
+    for f, face in enumerate(faces):
+        for v in face:
+            vertex_faces[v].append(f)
+
+    seen = [False] * len(faces)
+    components = []
+    for start in range(len(faces)):
+        if seen[start]:
+            continue
+        seen[start] = True
+        component, queue = [], deque([start])
+        while queue:
+            f = queue.popleft()
+            component.append(f)
+            for v in faces[f]:
+                for g in vertex_faces[v]:
+                    if not seen[g]:
+                        seen[g] = True
+                        queue.append(g)
+        components.append(sorted(component))
+    return components
+
+
+def timed(function, repeats):
+    best = float("inf")
+    for _ in range(repeats):
+        start = time.perf_counter()
+        result = function()
+        best = min(best, time.perf_counter() - start)
+    return result, best
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument("--shells", type=int, default=2000)
+    parser.add_argument("--subdivisions", type=int, default=3)
+    parser.add_argument("--repeats", type=int, default=3)
+    args = parser.parse_args()
+
+    mesh = many_shells(args.shells, args.subdivisions)
+    faces = mesh.faces
+    print(f"{mesh.face_count()} faces, {mesh.vertex_count()} vertices, {args.shells} shells")
+
+    expected, bfs_time = timed(lambda: bfs_components(faces, mesh.vertex_count()), args.repeats)
+    components, union_find_time = timed(mesh.connected_components, args.repeats)
+    count, count_time = timed(mesh.component_count, args.repeats)
+
+    # both number components by their first face, so the groupings must match exactly
+    assert components == expected, "union-find and BFS disagree"
+    assert count == len(expected) == args.shells
+
+    print(f"BFS (Python)          {bfs_time * 1e3:10.1f} ms")
+    print(f"connected_components  {union_find_time * 1e3:10.1f} ms")
+    print(f"component_count       {count_time * 1e3:10.1f} ms")
+
+
+if __name__ == "__main__":
+    main()
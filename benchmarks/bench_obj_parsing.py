@@ -0,0 +1,81 @@
+"""OBJ loading: chunked parallel parsing in Mesh.from_obj against a single thread.
+
+Writes a large OBJ with normals and mixed absolute and relative face indices, then loads it
+in fresh processes with RAYON_NUM_THREADS set to 1, 2, 4, ... up to the core count. Every run
+has to load the same mesh as the single threaded one. Needs a build with the `parallel`
+feature; without it every thread count parses serially.
+
+    python benchmarks/bench_obj_parsing.py --lines 20000000
+"""
+
+import argparse
+import os
+import subprocess
+import sys
+import tempfile
+
+LOAD = """
+import hashlib, sys, time
+import meshalyzer
+
+start = time.perf_counter()
+mesh = meshalyzer.Mesh.from_obj(sys.argv[1])
+elapsed = time.perf_counter() - start
+positions, indices = mesh.to_buffers()
+digest = hashlib.sha256(repr((positions, indices)).encode()).hexdigest()
+print(elapsed, digest)
+"""
+
+
+def write_obj(path, lines):
+    # a strip of quads, each one a vertex pair and a normal pair followed by two triangles
+    strips = max(1, lines // 6)
+    with open(path, "w") as out:
+        for i in range(strips):
+            out.write(f"v {i * 0.001:.6f} 0 {i % 7 * 0.125}\nv {i * 0.001:.6f} 1 0\n")
+            out.write("vn 0 0 1\nvn 0 0 1\n")
+            if i > 0:
+                a, b = 2 * i - 1, 2 * i
+                out.write(f"f {a}//{a} {a + 2}//{a + 2} {b}//{b}\n")
+                out.write("f -1//-1 -2//-2 -3//-3\n")
+
+
+def load(path, threads):
+    env = dict(os.environ, RAYON_NUM_THREADS=str(threads))
+    output = subprocess.run(
+        [sys.executable, "-c", LOAD, path], env=env, check=True, capture_output=True, text=True
+    ).stdout.split()
+    return float(output[0]), output[1]
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument("--lines", type=int, default=2000000)
+    parser.add_argument("--repeats", type=int, default=3)
+    args = parser.parse_args()
+
+    thread_counts = [1]
+    while thread_counts[-1] * 2 <= os.cpu_count():
+        thread_counts.append(thread_counts[-1] * 2)
+    if thread_counts[-1] != os.cpu_count():
+        thread_counts.append(os.cpu_count())
+
+    with tempfile.TemporaryDirectory() as directory:
+        path = os.path.join(directory, "bench.obj")
+        write_obj(path, args.lines)
+        size = os.path.getsize(path)
+        print(f"{args.lines} lines, {size / 1e6:.0f} MB")
+
+        serial_time, serial_digest = None, None
+        for threads in thread_counts:
+            runs = [load(path, threads) for _ in range(args.repeats)]
+            best = min(elapsed for elapsed, _ in runs)
+            digests = {digest for _, digest in runs}
+            if serial_digest is None:
+                serial_time, serial_digest = best, next(iter(digests))
+            assert digests == {serial_digest}, f"{threads} threads loaded a different mesh"
+            print(f"{threads:3d} threads  {best:8.3f} s  speedup {serial_time / best:5.2f}")
+
+
+if __name__ == "__main__":
+    main()
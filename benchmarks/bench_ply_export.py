@@ -0,0 +1,107 @@
+"""PLY export: peak memory of the streaming binary writer in Mesh.save_ply against a buffered one.
+
+Builds grids of increasing size and exports each of them twice, in fresh processes: once with
+Mesh.save_ply, which encodes vertices and faces straight into a buffered file writer, and once
+the way the writer used to work, assembling the whole file in memory from to_buffers and
+writing it at once. Each process reports how far its peak RSS (ru_maxrss) rose during the
+export alone, and both files must be byte for byte the same. Streaming should stay flat while
+buffering grows with the file. Needs Linux, which can reset the peak after the setup.
+
+    python benchmarks/bench_ply_export.py --divisions 500 1000 2000
+"""
+
+import argparse
+import hashlib
+import os
+import subprocess
+import sys
+import tempfile
+
+EXPORT = """
+import gc, resource, sys
+from array import array
+import meshalyzer
+
+def peak_mb():
+    return resource.getrusage(resource.RUSAGE_SELF).ru_maxrss / 1024
+
+mode, path, divisions = sys.argv[1], sys.argv[2], int(sys.argv[3])
+mesh = meshalyzer.Mesh.plane(1.0, 1.0, divisions)
+if mode == "buffered":
+    positions, indices = mesh.to_buffers()
+    positions, indices = array("f", positions), array("I", indices)
+    if sys.byteorder != "little":
+        positions.byteswap()
+        indices.byteswap()
+
+# lower the high water mark to the current RSS, so building the inputs doesn't count
+gc.collect()
+with open("/proc/self/clear_refs", "w") as clear_refs:
+    clear_refs.write("5")
+before = peak_mb()
+
+if mode == "streaming":
+    mesh.save_ply(path)
+else:
+    # the whole file in one buffer: header, xyz floats, then a count byte and three ints per face
+    vertex_count, face_count = len(positions) // 3, len(indices) // 3
+    header = (
+        "ply\\nformat binary_little_endian 1.0\\n"
+        f"element vertex {vertex_count}\\n"
+        "property float x\\nproperty float y\\nproperty float z\\n"
+        f"element face {face_count}\\n"
+        "property list uchar int vertex_indices\\nend_header\\n"
+    ).encode()
+    vertex_bytes = memoryview(positions).cast("B")
+    face_start = len(header) + len(vertex_bytes)
+    buffer = bytearray(face_start + 13 * face_count)
+    buffer[:len(header)] = header
+    buffer[len(header):face_start] = vertex_bytes
+    buffer[face_start::13] = b"\\x03" * face_count
+    index_bytes = memoryview(indices).cast("B")
+    for k in range(12):
+        buffer[face_start + 1 + k::13] = bytes(index_bytes[k::12])
+    with open(path, "wb") as out:
+        out.write(buffer)
+
+print(before, peak_mb(), mesh.vertex_count(), mesh.face_count())
+"""
+
+
+def export(mode, path, divisions):
+    output = subprocess.run(
+        [sys.executable, "-c", EXPORT, mode, path, str(divisions)],
+        check=True, capture_output=True, text=True,
+    ).stdout.split()
+    before, peak, vertices, faces = float(output[0]), float(output[1]), int(output[2]), int(output[3])
+    return peak - before, vertices, faces
+
+
+def digest(path):
+    sha = hashlib.sha256()
+    with open(path, "rb") as file:
+        for block in iter(lambda: file.read(1 << 20), b""):
+            sha.update(block)
+    return sha.hexdigest()
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument("--divisions", type=int, nargs="+", default=[250, 500, 1000])
+    args = parser.parse_args()
+
+    print(f"{'vertices':>10} {'faces':>10} {'file MB':>8} {'streaming MB':>13} {'buffered MB':>12}")
+    with tempfile.TemporaryDirectory() as directory:
+        streamed_path = os.path.join(directory, "streamed.ply")
+        buffered_path = os.path.join(directory, "buffered.ply")
+        for divisions in args.divisions:
+            streamed, vertices, faces = export("streaming", streamed_path, divisions)
+            buffered, _, _ = export("buffered", buffered_path, divisions)
+            assert digest(streamed_path) == digest(buffered_path), f"{divisions} divisions: files differ"
+
+            size = os.path.getsize(streamed_path) / 1e6
+            print(f"{vertices:10d} {faces:10d} {size:8.1f} {streamed:13.1f} {buffered:12.1f}")
+
+
+if __name__ == "__main__":
+    main()
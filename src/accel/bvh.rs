@@ -0,0 +1,415 @@
+use nalgebra::{Point3, Vector3};
+
+//faces per leaf before a node is split further
+const LEAF_SIZE: usize = 4;
+
+//Moller-Trumbore tolerances: rays closer to parallel with the triangle plane than this
+//(relative to the edge lengths) miss, and barycentric coordinates may overshoot by this
+//much so rays through shared edges and vertices can't slip between neighbouring faces
+const PARALLEL_EPSILON: f32 = 1e-7;
+const BARYCENTRIC_EPSILON: f32 = 1e-6;
+
+#[derive(Clone, Debug)]
+struct Aabb {
+    min: Vector3<f32>,
+    max: Vector3<f32>,
+}
+
+impl Aabb {
+    fn empty() -> Self {
+        Aabb {
+            min: Vector3::repeat(f32::INFINITY),
+            max: Vector3::repeat(f32::NEG_INFINITY),
+        }
+    }
+
+    fn grow(&mut self, point: &Vector3<f32>) {
+        self.min = self.min.inf(point);
+        self.max = self.max.sup(point);
+    }
+
+    //distance at which the ray enters the box, if that happens before max_t
+    fn ray_entry(&self, origin: &Vector3<f32>, inv_direction: &Vector3<f32>, max_t: f32) -> Option<f32> {
+        let mut t_min = 0.0f32;
+        let mut t_max = max_t;
+
+        for axis in 0..3 {
+            //parallel to this slab: inside it for the whole ray or never
+            if inv_direction[axis].is_infinite() {
+                if origin[axis] < self.min[axis] || origin[axis] > self.max[axis] {
+                    return None;
+                }
+                continue;
+            }
+
+            let t0 = (self.min[axis] - origin[axis]) * inv_direction[axis];
+            let t1 = (self.max[axis] - origin[axis]) * inv_direction[axis];
+
+            t_min = t_min.max(t0.min(t1));
+            t_max = t_max.min(t0.max(t1));
+
+            if t_min > t_max {
+                return None;
+            }
+        }
+
+        Some(t_min)
+    }
+
+    //whether the boxes share at least a point, touching counts
+    fn overlaps(&self, min: &Vector3<f32>, max: &Vector3<f32>) -> bool {
+        (0..3).all(|axis| self.min[axis] <= max[axis] && min[axis] <= self.max[axis])
+    }
+
+    //squared distance from a point to the box, zero inside
+    fn distance_squared(&self, point: &Vector3<f32>) -> f32 {
+        let below = self.min - point;
+        let above = point - self.max;
+        below.sup(&above).sup(&Vector3::zeros()).norm_squared()
+    }
+}
+
+//leaves cover face_indices[start..start + count]; inner nodes have count 0, their
+//left child right after them and their right child at `start`
+#[derive(Clone, Debug)]
+struct Node {
+    bounds: Aabb,
+    start: usize,
+    count: usize,
+}
+
+//bounding volume hierarchy over the faces of a triangle mesh
+#[derive(Clone, Debug)]
+pub struct Bvh {
+    nodes: Vec<Node>,
+    triangles: Vec<[Vector3<f32>; 3]>,
+    face_indices: Vec<usize>,
+}
+
+impl Bvh {
+    pub fn new(vertices: &[Point3<f32>], faces: &[[usize; 3]]) -> Self {
+        let triangles: Vec<[Vector3<f32>; 3]> = faces
+            .iter()
+            .map(|face| [
+                vertices[face[0]].coords,
+                vertices[face[1]].coords,
+                vertices[face[2]].coords,
+            ])
+            .collect();
+        let centroids: Vec<Vector3<f32>> = triangles
+            .iter()
+            .map(|t| (t[0] + t[1] + t[2]) / 3.0)
+            .collect();
+
+        let mut bvh = Bvh {
+            nodes: Vec::with_capacity(2 * faces.len() / LEAF_SIZE + 1),
+            triangles,
+            face_indices: (0..faces.len()).collect(),
+        };
+
+        if !faces.is_empty() {
+            bvh.build(&centroids, 0, faces.len());
+        }
+        bvh
+    }
+
+    //splits each range at the median centroid along the widest centroid axis
+    fn build(&mut self, centroids: &[Vector3<f32>], lo: usize, hi: usize) -> usize {
+        let mut bounds = Aabb::empty();
+        let mut centroid_bounds = Aabb::empty();
+        for &f in &self.face_indices[lo..hi] {
+            for corner in &self.triangles[f] {
+                bounds.grow(corner);
+            }
+            centroid_bounds.grow(&centroids[f]);
+        }
+
+        let node_idx = self.nodes.len();
+        self.nodes.push(Node { bounds, start: lo, count: hi - lo });
+
+        if hi - lo <= LEAF_SIZE {
+            return node_idx;
+        }
+
+        let axis = (centroid_bounds.max - centroid_bounds.min).imax();
+        let mid = lo + (hi - lo) / 2;
+        self.face_indices[lo..hi].select_nth_unstable_by(mid - lo, |&a, &b| {
+            centroids[a][axis].total_cmp(&centroids[b][axis])
+        });
+
+        self.build(centroids, lo, mid);
+        let right = self.build(centroids, mid, hi);
+
+        self.nodes[node_idx].start = right;
+        self.nodes[node_idx].count = 0;
+        node_idx
+    }
+
+    //nearest hit along a unit direction as (distance, face index)
+    pub fn intersect_ray(&self, origin: &Point3<f32>, direction: &Vector3<f32>) -> Option<(f32, usize)> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+
+        let origin = origin.coords;
+        let inv_direction = direction.map(|d| 1.0 / d);
+        let mut best: Option<(f32, usize)> = None;
+        let mut stack = vec![0];
+
+        while let Some(node_idx) = stack.pop() {
+            let node = &self.nodes[node_idx];
+            let max_t = best.map_or(f32::INFINITY, |(t, _)| t);
+
+            if node.bounds.ray_entry(&origin, &inv_direction, max_t).is_none() {
+                continue;
+            }
+
+            if node.count > 0 {
+                for &face in &self.face_indices[node.start..node.start + node.count] {
+                    if let Some(t) = intersect_triangle(&origin, direction, &self.triangles[face]) {
+                        //ties go to the lower face index so results don't depend on tree layout
+                        let closer = match best {
+                            Some((best_t, best_face)) => t < best_t || (t == best_t && face < best_face),
+                            None => true,
+                        };
+                        if closer {
+                            best = Some((t, face));
+                        }
+                    }
+                }
+                continue;
+            }
+
+            //visit the nearer child first so its hits prune the farther one
+            let (left, right) = (node_idx + 1, node.start);
+            let left_entry = self.nodes[left].bounds.ray_entry(&origin, &inv_direction, max_t);
+            let right_entry = self.nodes[right].bounds.ray_entry(&origin, &inv_direction, max_t);
+
+            match (left_entry, right_entry) {
+                (Some(l), Some(r)) if l <= r => {
+                    stack.push(right);
+                    stack.push(left);
+                }
+                (Some(_), Some(_)) => {
+                    stack.push(left);
+                    stack.push(right);
+                }
+                (Some(_), None) => stack.push(left),
+                (None, Some(_)) => stack.push(right),
+                (None, None) => {}
+            }
+        }
+
+        best
+    }
+
+    //every hit along a unit direction as (distance, face index), sorted by distance then face
+    pub fn intersect_ray_all(&self, origin: &Point3<f32>, direction: &Vector3<f32>) -> Vec<(f32, usize)> {
+        let mut hits = Vec::new();
+        if self.nodes.is_empty() {
+            return hits;
+        }
+
+        let origin = origin.coords;
+        let inv_direction = direction.map(|d| 1.0 / d);
+        let mut stack = vec![0];
+
+        while let Some(node_idx) = stack.pop() {
+            let node = &self.nodes[node_idx];
+            if node.bounds.ray_entry(&origin, &inv_direction, f32::INFINITY).is_none() {
+                continue;
+            }
+
+            if node.count > 0 {
+                for &face in &self.face_indices[node.start..node.start + node.count] {
+                    if let Some(t) = intersect_triangle(&origin, direction, &self.triangles[face]) {
+                        hits.push((t, face));
+                    }
+                }
+            } else {
+                stack.push(node.start);
+                stack.push(node_idx + 1);
+            }
+        }
+
+        hits.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        hits
+    }
+
+    //faces whose bounding box overlaps the box from min to max, sorted by index
+    pub fn faces_in_box(&self, min: &Vector3<f32>, max: &Vector3<f32>) -> Vec<usize> {
+        let mut faces = Vec::new();
+        if self.nodes.is_empty() {
+            return faces;
+        }
+
+        let mut stack = vec![0];
+        while let Some(node_idx) = stack.pop() {
+            let node = &self.nodes[node_idx];
+            if !node.bounds.overlaps(min, max) {
+                continue;
+            }
+
+            if node.count > 0 {
+                for &face in &self.face_indices[node.start..node.start + node.count] {
+                    let mut bounds = Aabb::empty();
+                    for corner in &self.triangles[face] {
+                        bounds.grow(corner);
+                    }
+                    if bounds.overlaps(min, max) {
+                        faces.push(face);
+                    }
+                }
+            } else {
+                stack.push(node.start);
+                stack.push(node_idx + 1);
+            }
+        }
+
+        faces.sort_unstable();
+        faces
+    }
+
+    //closest point on the surface as (face index, point, barycentric coordinates, distance)
+    pub fn closest_point(&self, query: &Point3<f32>) -> Option<(usize, Vector3<f32>, [f32; 3], f32)> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+
+        let query = query.coords;
+        let mut best: Option<(f32, usize, Vector3<f32>, [f32; 3])> = None;
+        let mut stack = vec![0];
+
+        while let Some(node_idx) = stack.pop() {
+            let node = &self.nodes[node_idx];
+            let max_distance_sq = best.map_or(f32::INFINITY, |(d, ..)| d);
+
+            if node.bounds.distance_squared(&query) > max_distance_sq {
+                continue;
+            }
+
+            if node.count > 0 {
+                for &face in &self.face_indices[node.start..node.start + node.count] {
+                    let (point, bary) = closest_on_triangle(&query, &self.triangles[face]);
+                    let distance_sq = (point - query).norm_squared();
+
+                    //ties go to the lower face index so results don't depend on tree layout
+                    let closer = match best {
+                        Some((best_d, best_face, ..)) => {
+                            distance_sq < best_d || (distance_sq == best_d && face < best_face)
+                        }
+                        None => true,
+                    };
+                    if closer {
+                        best = Some((distance_sq, face, point, bary));
+                    }
+                }
+                continue;
+            }
+
+            //push the farther child first so the nearer one is searched first
+            let (left, right) = (node_idx + 1, node.start);
+            let left_distance = self.nodes[left].bounds.distance_squared(&query);
+            let right_distance = self.nodes[right].bounds.distance_squared(&query);
+
+            if left_distance <= right_distance {
+                stack.push(right);
+                stack.push(left);
+            } else {
+                stack.push(left);
+                stack.push(right);
+            }
+        }
+
+        best.map(|(distance_sq, face, point, bary)| (face, point, bary, distance_sq.sqrt()))
+    }
+}
+
+//closest point of a triangle and its barycentric coordinates (Ericson, Real-Time Collision
+//Detection 5.1.5); points outside the triangle are clamped to the nearest edge or corner
+fn closest_on_triangle(p: &Vector3<f32>, triangle: &[Vector3<f32>; 3]) -> (Vector3<f32>, [f32; 3]) {
+    let [a, b, c] = *triangle;
+    let ab = b - a;
+    let ac = c - a;
+
+    let ap = p - a;
+    let d1 = ab.dot(&ap);
+    let d2 = ac.dot(&ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return (a, [1.0, 0.0, 0.0]);
+    }
+
+    let bp = p - b;
+    let d3 = ab.dot(&bp);
+    let d4 = ac.dot(&bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return (b, [0.0, 1.0, 0.0]);
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        let v = d1 / (d1 - d3);
+        return (a + ab * v, [1.0 - v, v, 0.0]);
+    }
+
+    let cp = p - c;
+    let d5 = ab.dot(&cp);
+    let d6 = ac.dot(&cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return (c, [0.0, 0.0, 1.0]);
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        let w = d2 / (d2 - d6);
+        return (a + ac * w, [1.0 - w, 0.0, w]);
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return (b + (c - b) * w, [0.0, 1.0 - w, w]);
+    }
+
+    //degenerate triangles end up here with a zero denominator, fall back to the first corner
+    let denom = va + vb + vc;
+    if denom == 0.0 {
+        return (a, [1.0, 0.0, 0.0]);
+    }
+
+    let v = vb / denom;
+    let w = vc / denom;
+    (a + ab * v + ac * w, [1.0 - v - w, v, w])
+}
+
+//Moller-Trumbore ray/triangle test, distance along the ray for hits in front of the origin
+fn intersect_triangle(origin: &Vector3<f32>, direction: &Vector3<f32>, triangle: &[Vector3<f32>; 3]) -> Option<f32> {
+    let edge1 = triangle[1] - triangle[0];
+    let edge2 = triangle[2] - triangle[0];
+
+    let p = direction.cross(&edge2);
+    let det = edge1.dot(&p);
+    if det.abs() <= PARALLEL_EPSILON * edge1.norm() * edge2.norm() {
+        return None;
+    }
+
+    let inv_det = 1.0 / det;
+    let s = origin - triangle[0];
+    let u = s.dot(&p) * inv_det;
+    if !(-BARYCENTRIC_EPSILON..=1.0 + BARYCENTRIC_EPSILON).contains(&u) {
+        return None;
+    }
+
+    let q = s.cross(&edge1);
+    let v = direction.dot(&q) * inv_det;
+    if v < -BARYCENTRIC_EPSILON || u + v > 1.0 + BARYCENTRIC_EPSILON {
+        return None;
+    }
+
+    let t = edge2.dot(&q) * inv_det;
+    if t > 0.0 {
+        Some(t)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,131 @@
+use nalgebra::Point3;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+//balanced kd-tree over a point set, stored implicitly: the node of a range is its middle
+//element and the two halves are its subtrees
+#[derive(Clone, Debug)]
+pub struct KdTree {
+    points: Vec<Point3<f32>>,
+    order: Vec<usize>,
+    axes: Vec<u8>,
+}
+
+//heap entry ordered by distance, then by index so ties resolve deterministically
+#[derive(PartialEq)]
+struct Candidate {
+    distance_sq: f32,
+    index: usize,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance_sq
+            .total_cmp(&other.distance_sq)
+            .then(self.index.cmp(&other.index))
+    }
+}
+
+impl KdTree {
+    pub fn new(points: &[Point3<f32>]) -> Self {
+        let mut tree = KdTree {
+            points: points.to_vec(),
+            order: (0..points.len()).collect(),
+            axes: vec![0; points.len()],
+        };
+
+        tree.build(0, points.len());
+        tree
+    }
+
+    //splits each range at the median along its widest axis
+    fn build(&mut self, lo: usize, hi: usize) {
+        if hi - lo <= 1 {
+            return;
+        }
+
+        let mut min = [f32::INFINITY; 3];
+        let mut max = [f32::NEG_INFINITY; 3];
+        for &i in &self.order[lo..hi] {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(self.points[i][axis]);
+                max[axis] = max[axis].max(self.points[i][axis]);
+            }
+        }
+
+        let axis = (0..3)
+            .max_by(|&a, &b| (max[a] - min[a]).total_cmp(&(max[b] - min[b])))
+            .unwrap_or(0);
+
+        let mid = lo + (hi - lo) / 2;
+        let points = &self.points;
+        self.order[lo..hi].select_nth_unstable_by(mid - lo, |&a, &b| {
+            points[a][axis].total_cmp(&points[b][axis])
+        });
+        self.axes[mid] = axis as u8;
+
+        self.build(lo, mid);
+        self.build(mid + 1, hi);
+    }
+
+    //closest point as (index, distance)
+    pub fn nearest(&self, query: &Point3<f32>) -> Option<(usize, f32)> {
+        self.k_nearest(query, 1).into_iter().next()
+    }
+
+    //k closest points as (index, distance), sorted by increasing distance
+    pub fn k_nearest(&self, query: &Point3<f32>, k: usize) -> Vec<(usize, f32)> {
+        if k == 0 {
+            return Vec::new();
+        }
+
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        self.search(0, self.points.len(), query, k, &mut heap);
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|c| (c.index, c.distance_sq.sqrt()))
+            .collect()
+    }
+
+    fn search(&self, lo: usize, hi: usize, query: &Point3<f32>, k: usize, heap: &mut BinaryHeap<Candidate>) {
+        if lo >= hi {
+            return;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        let index = self.order[mid];
+        let point = &self.points[index];
+
+        heap.push(Candidate {
+            distance_sq: (point - query).norm_squared(),
+            index,
+        });
+        if heap.len() > k {
+            heap.pop();
+        }
+
+        let axis = self.axes[mid] as usize;
+        let diff = query[axis] - point[axis];
+        let (near, far) = if diff < 0.0 {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+
+        self.search(near.0, near.1, query, k, heap);
+
+        let worst = heap.peek().map_or(f32::INFINITY, |c| c.distance_sq);
+        if heap.len() < k || diff * diff <= worst {
+            self.search(far.0, far.1, query, k, heap);
+        }
+    }
+}
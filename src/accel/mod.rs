@@ -0,0 +1,3 @@
+pub mod bvh;
+pub mod kdtree;
+pub mod union_find;
@@ -0,0 +1,156 @@
+use crate::mesh::types::Mesh;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+//Shortest paths along mesh edges (Dijkstra). Edge-graph distances overestimate true
+//geodesics somewhat, since paths can't cut across faces, but they are exact for the polylines
+//they describe and cheap enough to run on every vertex.
+
+//heap entry ordered so the max-heap pops the closest vertex, then the lowest index
+#[derive(PartialEq)]
+struct Visit {
+    distance: f64,
+    vertex: usize,
+}
+
+impl Eq for Visit {}
+
+impl PartialOrd for Visit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Visit {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance
+            .total_cmp(&self.distance)
+            .then(other.vertex.cmp(&self.vertex))
+    }
+}
+
+pub struct ShortestPaths {
+    //infinite for vertices not connected to any source
+    pub distances: Vec<f32>,
+    //previous vertex on the shortest path, None for sources and unreached vertices
+    pub predecessors: Vec<Option<usize>>,
+}
+
+impl ShortestPaths {
+    //vertices from the nearest source to target, empty if target was not reached
+    pub fn path_to(&self, target: usize) -> Vec<usize> {
+        if !self.distances[target].is_finite() {
+            return Vec::new();
+        }
+
+        let mut path = vec![target];
+        let mut current = target;
+        while let Some(previous) = self.predecessors[current] {
+            path.push(previous);
+            current = previous;
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+//edge-connected neighbours of every vertex with the edge lengths, sorted by neighbour
+pub fn edge_graph(mesh: &Mesh) -> Vec<Vec<(usize, f64)>> {
+    let mut graph: Vec<Vec<(usize, f64)>> = vec![Vec::new(); mesh.vertices.len()];
+
+    for face in &mesh.faces {
+        for i in 0..3 {
+            let (a, b) = (face[i], face[(i + 1) % 3]);
+            if a == b {
+                continue;
+            }
+
+            let length = (mesh.vertices[a] - mesh.vertices[b]).norm() as f64;
+            graph[a].push((b, length));
+            graph[b].push((a, length));
+        }
+    }
+
+    for neighbors in &mut graph {
+        neighbors.sort_by_key(|n| n.0);
+        neighbors.dedup_by_key(|n| n.0);
+    }
+
+    graph
+}
+
+//distances from the closest of the sources, stops early once `stop_at` is settled
+pub fn dijkstra(mesh: &Mesh, sources: &[usize], stop_at: Option<usize>) -> ShortestPaths {
+    let graph = edge_graph(mesh);
+    let mut distances = vec![f64::INFINITY; mesh.vertices.len()];
+    let mut predecessors = vec![None; mesh.vertices.len()];
+    relax(&graph, sources, &mut distances, Some(&mut predecessors), stop_at);
+
+    ShortestPaths {
+        distances: distances.into_iter().map(|d| d as f32).collect(),
+        predecessors,
+    }
+}
+
+//distances to the nearest of a growing set of sources, e.g. for farthest point sampling
+//every added source only visits the vertices it is the nearest source of, and the edge graph
+//is built once for all of them
+pub struct NearestSource {
+    graph: Vec<Vec<(usize, f64)>>,
+    //infinite until a source is reachable
+    pub distances: Vec<f64>,
+}
+
+impl NearestSource {
+    pub fn new(mesh: &Mesh) -> Self {
+        NearestSource {
+            graph: edge_graph(mesh),
+            distances: vec![f64::INFINITY; mesh.vertices.len()],
+        }
+    }
+
+    pub fn add(&mut self, source: usize) {
+        relax(&self.graph, &[source], &mut self.distances, None, None);
+    }
+}
+
+//Dijkstra from sources that only pushes vertices it brings closer than the given distances
+//from infinite distances that is a plain search, from those of earlier sources it lowers them
+//to the nearest of all sources and stops wherever an earlier one is nearer
+fn relax(
+    graph: &[Vec<(usize, f64)>],
+    sources: &[usize],
+    distances: &mut [f64],
+    mut predecessors: Option<&mut [Option<usize>]>,
+    stop_at: Option<usize>,
+) {
+    let mut heap = BinaryHeap::new();
+
+    for &source in sources {
+        if distances[source] > 0.0 {
+            distances[source] = 0.0;
+            heap.push(Visit { distance: 0.0, vertex: source });
+        }
+    }
+
+    while let Some(Visit { distance, vertex }) = heap.pop() {
+        if distance > distances[vertex] {
+            continue;
+        }
+        if Some(vertex) == stop_at {
+            break;
+        }
+
+        for &(neighbor, length) in &graph[vertex] {
+            let candidate = distance + length;
+            if candidate < distances[neighbor] {
+                distances[neighbor] = candidate;
+                if let Some(predecessors) = &mut predecessors {
+                    predecessors[neighbor] = Some(vertex);
+                }
+                heap.push(Visit { distance: candidate, vertex: neighbor });
+            }
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use crate::mesh::types::Mesh;
+use nalgebra::{Point3, Vector3};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+//Discrete Laplace-Beltrami building blocks shared by smoothing, curvature and the
+//matrix export: cotangent edge weights and mixed Voronoi vertex areas (Meyer et al. 2003).
+
+pub struct CotangentWeights {
+    //(cot alpha + cot beta) / 2 per undirected edge, keyed by (min vertex, max vertex)
+    pub weights: HashMap<(usize, usize), f32>,
+    //edges shared by more than two faces, which have no well defined weight
+    pub non_manifold_edges: usize,
+    //vertices on an edge with a single face, whose 1-ring is incomplete
+    pub boundary_vertices: Vec<bool>,
+}
+
+//cotangent of the angle at `corner` between the edges to `a` and `b`
+pub fn cotangent(corner: &Point3<f32>, a: &Point3<f32>, b: &Point3<f32>) -> f32 {
+    let u: Vector3<f32> = a - corner;
+    let v: Vector3<f32> = b - corner;
+    let sin = u.cross(&v).norm();
+
+    if sin <= f32::EPSILON * u.norm() * v.norm() {
+        return 0.0;
+    }
+
+    u.dot(&v) / sin
+}
+
+pub fn cotangent_weights(mesh: &Mesh) -> CotangentWeights {
+    let mut face_counts: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut weights: HashMap<(usize, usize), f32> = HashMap::new();
+
+    for face in &mesh.faces {
+        for i in 0..3 {
+            let corner = face[i];
+            let a = face[(i + 1) % 3];
+            let b = face[(i + 2) % 3];
+            let edge = (a.min(b), a.max(b));
+
+            *face_counts.entry(edge).or_insert(0) += 1;
+            *weights.entry(edge).or_insert(0.0) += 0.5
+                * cotangent(&mesh.vertices[corner], &mesh.vertices[a], &mesh.vertices[b]);
+        }
+    }
+
+    let mut non_manifold_edges = 0;
+    let mut boundary_vertices = vec![false; mesh.vertices.len()];
+    for ((a, b), count) in face_counts {
+        if count > 2 {
+            weights.remove(&(a, b));
+            non_manifold_edges += 1;
+        } else if count == 1 {
+            boundary_vertices[a] = true;
+            boundary_vertices[b] = true;
+        }
+    }
+
+    CotangentWeights {
+        weights,
+        non_manifold_edges,
+        boundary_vertices,
+    }
+}
+
+//neighbors of every vertex with their cotangent weights, sorted by neighbor so sums don't
+//depend on hash order; weights from obtuse triangles can be negative and are clamped to zero,
+//which keeps smoothing a convex combination of the ring
+pub fn cotangent_rings(mesh: &Mesh) -> Vec<Vec<(usize, f32)>> {
+    let mut rings = vec![Vec::new(); mesh.vertices.len()];
+
+    for (&(a, b), &w) in &cotangent_weights(mesh).weights {
+        rings[a].push((b, w.max(0.0)));
+        rings[b].push((a, w.max(0.0)));
+    }
+    for ring in &mut rings {
+        ring.sort_by_key(|&(neighbor, _)| neighbor);
+    }
+
+    rings
+}
+
+//mixed Voronoi area of every vertex, falling back to triangle area fractions on obtuse faces
+pub fn mixed_areas(mesh: &Mesh) -> Vec<f32> {
+    let mut areas = vec![0.0; mesh.vertices.len()];
+
+    for face in &mesh.faces {
+        let p = [
+            mesh.vertices[face[0]],
+            mesh.vertices[face[1]],
+            mesh.vertices[face[2]],
+        ];
+        let area = (p[1] - p[0]).cross(&(p[2] - p[0])).norm() * 0.5;
+        if area <= 0.0 {
+            continue;
+        }
+
+        let obtuse = (0..3).find(|&i| (p[(i + 1) % 3] - p[i]).dot(&(p[(i + 2) % 3] - p[i])) < 0.0);
+
+        for i in 0..3 {
+            let j = (i + 1) % 3;
+            let k = (i + 2) % 3;
+
+            areas[face[i]] += match obtuse {
+                Some(o) if o == i => area * 0.5,
+                Some(_) => area * 0.25,
+                None => {
+                    (cotangent(&p[k], &p[i], &p[j]) * (p[j] - p[i]).norm_squared()
+                        + cotangent(&p[j], &p[i], &p[k]) * (p[k] - p[i]).norm_squared())
+                        / 8.0
+                }
+            };
+        }
+    }
+
+    areas
+}
+
+//unnormalized cotangent Laplacian in COO form, entries sorted by (row, col)
+//off-diagonal entries are the edge weights, diagonals the negated row sums
+pub fn cotangent_matrix(weights: &CotangentWeights, vertex_count: usize) -> (Vec<(usize, usize)>, Vec<f32>) {
+    let mut diagonal = vec![0.0; vertex_count];
+    let mut entries: Vec<((usize, usize), f32)> = Vec::with_capacity(weights.weights.len() * 2 + vertex_count);
+
+    for (&(a, b), &w) in &weights.weights {
+        entries.push(((a, b), w));
+        entries.push(((b, a), w));
+        diagonal[a] -= w;
+        diagonal[b] -= w;
+    }
+
+    entries.extend(diagonal.into_iter().enumerate().map(|(i, d)| ((i, i), d)));
+    entries.sort_by_key(|&(index, _)| index);
+
+    entries.into_iter().unzip()
+}
+
+//mean curvature normal of every vertex, the cotangent Laplacian of the positions over twice
+//the mixed area: its length is the mean curvature and it points inward on convex regions
+//boundary vertices and vertices without area get the zero vector
+pub fn mean_curvature_normals(mesh: &Mesh) -> Vec<Vector3<f32>> {
+    let weights = cotangent_weights(mesh);
+    let areas = mixed_areas(mesh);
+    let mut laplacian = vec![Vector3::zeros(); mesh.vertices.len()];
+
+    for (&(a, b), &w) in &weights.weights {
+        let edge = mesh.vertices[b] - mesh.vertices[a];
+        laplacian[a] += edge * w;
+        laplacian[b] -= edge * w;
+    }
+
+    laplacian
+        .into_iter()
+        .enumerate()
+        .map(|(v, sum)| {
+            if weights.boundary_vertices[v] || areas[v] <= 0.0 {
+                Vector3::zeros()
+            } else {
+                sum / (2.0 * areas[v])
+            }
+        })
+        .collect()
+}
+
+//angle deficit of every vertex: 2 pi minus the angles of its faces at it, or pi minus them on
+//the boundary; this is the Gaussian curvature integrated over the vertex area, so it sums to
+//2 pi times the Euler characteristic (Gauss-Bonnet); vertices without faces get zero
+pub fn angle_deficits(mesh: &Mesh) -> Vec<f64> {
+    let mut angle_sums = vec![0.0f64; mesh.vertices.len()];
+    let mut used = vec![false; mesh.vertices.len()];
+    let mut edge_counts: HashMap<(usize, usize), usize> = HashMap::new();
+
+    for face in &mesh.faces {
+        for i in 0..3 {
+            let corner = face[i];
+            let (a, b) = (face[(i + 1) % 3], face[(i + 2) % 3]);
+            let u = (mesh.vertices[a] - mesh.vertices[corner]).cast::<f64>();
+            let v = (mesh.vertices[b] - mesh.vertices[corner]).cast::<f64>();
+
+            angle_sums[corner] += u.cross(&v).norm().atan2(u.dot(&v));
+            used[corner] = true;
+            *edge_counts.entry((corner.min(a), corner.max(a))).or_insert(0) += 1;
+        }
+    }
+
+    let mut boundary = vec![false; mesh.vertices.len()];
+    for ((a, b), count) in edge_counts {
+        if count == 1 {
+            boundary[a] = true;
+            boundary[b] = true;
+        }
+    }
+
+    angle_sums
+        .into_iter()
+        .enumerate()
+        .map(|(v, sum)| match (used[v], boundary[v]) {
+            (false, _) => 0.0,
+            (true, true) => PI - sum,
+            (true, false) => 2.0 * PI - sum,
+        })
+        .collect()
+}
@@ -0,0 +1,4 @@
+pub mod geodesic;
+pub mod laplacian;
+pub mod simplification;
+pub mod topology;
@@ -0,0 +1,444 @@
+use crate::mesh::types::Mesh;
+use crate::progress::Progress;
+use nalgebra::{Matrix3, Matrix4, Point3, Vector3, Vector4};
+use pyo3::prelude::*;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+//Quadric error metric decimation (Garland & Heckbert 1997).
+//Every vertex carries the sum of the plane quadrics of its faces; collapsing an edge
+//merges the two quadrics and places the surviving vertex where their error is smallest.
+//Nothing here depends on hash iteration order: candidates come from sorted edge lists and
+//equal costs are resolved by vertex indices, so the same input always decimates the same way.
+
+//weight of the constraint planes along boundary and feature edges relative to the face planes
+const CONSTRAINT_WEIGHT: f64 = 1e3;
+
+#[derive(Default)]
+pub struct SimplifyOptions {
+    //keep open boundaries in place
+    pub preserve_boundary: bool,
+    //keep edges whose faces meet at more than this dihedral angle in degrees
+    pub feature_angle_deg: Option<f32>,
+}
+
+//heap entry for collapsing edge (a, b), a < b, into a
+//stale once either endpoint changed after the entry was pushed
+#[derive(PartialEq)]
+struct Candidate {
+    cost: f64,
+    a: usize,
+    b: usize,
+    version_a: u32,
+    version_b: u32,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+//reversed so the max-heap pops the cheapest collapse, then the smallest edge
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost
+            .total_cmp(&self.cost)
+            .then(other.a.cmp(&self.a))
+            .then(other.b.cmp(&self.b))
+    }
+}
+
+pub struct Simplifier {
+    vertices: Vec<Vector3<f64>>,
+    faces: Vec<[usize; 3]>,
+    face_alive: Vec<bool>,
+    //may still list faces that died since, filter with face_alive
+    vertex_faces: Vec<Vec<usize>>,
+    quadrics: Vec<Matrix4<f64>>,
+    version: Vec<u32>,
+    live_faces: usize,
+    //boundary and feature edges still in the mesh, keyed by (min vertex, max vertex)
+    constrained_edges: HashSet<(usize, usize)>,
+    constrained_vertices: Vec<bool>,
+}
+
+impl Simplifier {
+    pub fn new(mesh: &Mesh, options: &SimplifyOptions) -> Self {
+        let faces: Vec<usize> = (0..mesh.faces.len()).collect();
+        Self::over_faces(mesh, &faces, |_| true, options)
+    }
+
+    //state for collapsing just the edge (a, b): only the faces around a and b get quadrics and
+    //constraints, the rest of the mesh is carried along untouched
+    pub fn around_edge(mesh: &Mesh, a: usize, b: usize, options: &SimplifyOptions) -> Self {
+        let vertex_faces = mesh.vertex_faces();
+        let mut faces: Vec<usize> = vertex_faces[a].iter().chain(&vertex_faces[b]).copied().collect();
+        faces.sort();
+        faces.dedup();
+
+        //edges away from a and b are missing faces here, so they can't tell if they are boundary
+        Self::over_faces(mesh, &faces, |(u, v)| [u, v].iter().any(|w| *w == a || *w == b), options)
+    }
+
+    //quadrics and vertex_faces from the listed faces, given in increasing order, and constraints
+    //along the edges constrainable accepts; all faces of an accepted edge must be listed
+    fn over_faces(
+        mesh: &Mesh,
+        faces: &[usize],
+        constrainable: impl Fn((usize, usize)) -> bool,
+        options: &SimplifyOptions,
+    ) -> Self {
+        let vertices: Vec<Vector3<f64>> = mesh.vertices.iter().map(|v| v.coords.cast::<f64>()).collect();
+
+        let mut vertex_faces = vec![Vec::new(); vertices.len()];
+        let mut quadrics = vec![Matrix4::zeros(); vertices.len()];
+
+        for &face_idx in faces {
+            let face = mesh.faces[face_idx];
+            let quadric = plane_quadric(&vertices[face[0]], &vertices[face[1]], &vertices[face[2]]);
+            for v in face {
+                vertex_faces[v].push(face_idx);
+                quadrics[v] += quadric;
+            }
+        }
+
+        let mut edge_faces: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for &face_idx in faces {
+            let face = mesh.faces[face_idx];
+            for i in 0..3 {
+                let (a, b) = (face[i], face[(i + 1) % 3]);
+                edge_faces.entry((a.min(b), a.max(b))).or_default().push(face_idx);
+            }
+        }
+        edge_faces.retain(|&edge, _| constrainable(edge));
+        //sorted so the constraint quadrics are summed in the same order every run
+        let mut edge_faces: Vec<((usize, usize), Vec<usize>)> = edge_faces.into_iter().collect();
+        edge_faces.sort();
+
+        let face_normal = |f: usize| {
+            let [p0, p1, p2] = mesh.faces[f].map(|v| vertices[v]);
+            (p1 - p0).cross(&(p2 - p0)).try_normalize(0.0)
+        };
+        let cos_feature = options.feature_angle_deg.map(|angle| (angle as f64).to_radians().cos());
+
+        let mut constrained_edges = HashSet::new();
+        let mut constrained_vertices = vec![false; vertices.len()];
+        for ((a, b), faces) in edge_faces {
+            if a == b {
+                continue;
+            }
+
+            let constrained = match (faces.as_slice(), cos_feature) {
+                ([_], _) => options.preserve_boundary,
+                (&[f0, f1], Some(cos_feature)) => match (face_normal(f0), face_normal(f1)) {
+                    (Some(n0), Some(n1)) => n0.dot(&n1) < cos_feature,
+                    _ => false,
+                },
+                _ => false,
+            };
+            if !constrained {
+                continue;
+            }
+
+            //planes through the edge perpendicular to its faces keep the endpoints on the edge line
+            for f in faces {
+                let quadric = edge_quadric(&vertices[a], &vertices[b], face_normal(f)) * CONSTRAINT_WEIGHT;
+                quadrics[a] += quadric;
+                quadrics[b] += quadric;
+            }
+
+            constrained_edges.insert((a, b));
+            constrained_vertices[a] = true;
+            constrained_vertices[b] = true;
+        }
+
+        Simplifier {
+            constrained_edges,
+            constrained_vertices,
+            vertex_faces,
+            quadrics,
+            version: vec![0; vertices.len()],
+            face_alive: vec![true; mesh.faces.len()],
+            live_faces: mesh.faces.len(),
+            faces: mesh.faces.clone(),
+            vertices,
+        }
+    }
+
+    fn live_faces_of(&self, vertex: usize) -> impl Iterator<Item = usize> + '_ {
+        self.vertex_faces[vertex].iter().copied().filter(|&f| self.face_alive[f])
+    }
+
+    //sorted vertices sharing a live face with the vertex
+    fn neighbors(&self, vertex: usize) -> Vec<usize> {
+        let mut neighbors: Vec<usize> = self
+            .live_faces_of(vertex)
+            .flat_map(|f| self.faces[f])
+            .filter(|&v| v != vertex)
+            .collect();
+
+        neighbors.sort();
+        neighbors.dedup();
+        neighbors
+    }
+
+    //quadric error of collapsing (a, b) and the position the merged vertex would get
+    //falls back to the best of the endpoints and the midpoint when the optimum is ill-defined
+    pub fn collapse_cost(&self, a: usize, b: usize) -> (f64, Vector3<f64>) {
+        let quadric = self.quadrics[a] + self.quadrics[b];
+        let error = |p: &Vector3<f64>| {
+            let h = Vector4::new(p.x, p.y, p.z, 1.0);
+            (h.transpose() * quadric * h)[0].max(0.0)
+        };
+
+        let system: Matrix3<f64> = quadric.fixed_view::<3, 3>(0, 0).into();
+        let rhs: Vector3<f64> = -quadric.fixed_view::<3, 1>(0, 3).into_owned();
+
+        //relative determinant check, a nearly flat neighbourhood puts the optimum far away
+        let scale = system.trace() / 3.0;
+        if scale > 0.0 && system.determinant().abs() > 1e-6 * scale * scale * scale {
+            if let Some(inverse) = system.try_inverse() {
+                let optimum = inverse * rhs;
+                return (error(&optimum), optimum);
+            }
+        }
+
+        let midpoint = (self.vertices[a] + self.vertices[b]) * 0.5;
+        [self.vertices[a], self.vertices[b], midpoint]
+            .into_iter()
+            .map(|p| (error(&p), p))
+            .fold(None, |best: Option<(f64, Vector3<f64>)>, candidate| match best {
+                Some(best) if best.0 <= candidate.0 => Some(best),
+                _ => Some(candidate),
+            })
+            .unwrap()
+    }
+
+    //rejects collapses that would make the surface non-manifold or flip a face over, with the
+    //reason why
+    fn check_collapse(&self, a: usize, b: usize, position: &Vector3<f64>) -> Result<(), &'static str> {
+        let shared_faces = self
+            .live_faces_of(a)
+            .filter(|&f| self.faces[f].contains(&b))
+            .count();
+        if shared_faces == 0 {
+            return Err("no face has this edge");
+        }
+
+        //link condition: the only common neighbours are the tips of the faces on the edge
+        let neighbors_b = self.neighbors(b);
+        let common = self
+            .neighbors(a)
+            .into_iter()
+            .filter(|v| neighbors_b.binary_search(v).is_ok())
+            .count();
+        if common != shared_faces {
+            return Err("the surface would become non-manifold");
+        }
+
+        //two constrained vertices may only merge along the boundary or feature line joining them
+        if self.constrained_vertices[a]
+            && self.constrained_vertices[b]
+            && !self.constrained_edges.contains(&(a.min(b), a.max(b)))
+        {
+            return Err("both ends are on a preserved boundary or feature line that doesn't join them");
+        }
+
+        for vertex in [a, b] {
+            for f in self.live_faces_of(vertex) {
+                let face = self.faces[f];
+                if face.contains(&a) && face.contains(&b) {
+                    continue;
+                }
+
+                let corners = face.map(|v| self.vertices[v]);
+                let moved = face.map(|v| if v == vertex { *position } else { self.vertices[v] });
+
+                let before = (corners[1] - corners[0]).cross(&(corners[2] - corners[0]));
+                let after = (moved[1] - moved[0]).cross(&(moved[2] - moved[0]));
+                if after.dot(&before) <= 0.0 {
+                    return Err("a face would flip over");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    //merges b into a at the cheapest position if that keeps the surface valid
+    pub fn try_collapse(&mut self, a: usize, b: usize) -> Result<(), &'static str> {
+        let (_, position) = self.collapse_cost(a, b);
+        self.check_collapse(a, b, &position)?;
+        self.collapse(a, b, position);
+        Ok(())
+    }
+
+    //merges b into a at the given position, removing the faces on the edge
+    fn collapse(&mut self, a: usize, b: usize, position: Vector3<f64>) {
+        if self.constrained_vertices[b] {
+            for neighbor in self.neighbors(b) {
+                if self.constrained_edges.remove(&(b.min(neighbor), b.max(neighbor))) && neighbor != a {
+                    self.constrained_edges.insert((a.min(neighbor), a.max(neighbor)));
+                }
+            }
+            self.constrained_vertices[a] = true;
+        }
+
+        self.vertices[a] = position;
+        self.quadrics[a] = self.quadrics[a] + self.quadrics[b];
+
+        let faces_b = std::mem::take(&mut self.vertex_faces[b]);
+        for f in faces_b {
+            if !self.face_alive[f] {
+                continue;
+            }
+
+            if self.faces[f].contains(&a) {
+                self.face_alive[f] = false;
+                self.live_faces -= 1;
+            } else {
+                for v in self.faces[f].iter_mut() {
+                    if *v == b {
+                        *v = a;
+                    }
+                }
+                self.vertex_faces[a].push(f);
+            }
+        }
+
+        let face_alive = &self.face_alive;
+        self.vertex_faces[a].retain(|&f| face_alive[f]);
+        self.vertex_faces[a].sort();
+        self.vertex_faces[a].dedup();
+
+        self.version[a] += 1;
+        self.version[b] += 1;
+    }
+
+    fn candidate(&self, a: usize, b: usize) -> Candidate {
+        let (a, b) = (a.min(b), a.max(b));
+        Candidate {
+            cost: self.collapse_cost(a, b).0,
+            a,
+            b,
+            version_a: self.version[a],
+            version_b: self.version[b],
+        }
+    }
+
+    //collapses the cheapest valid edges until at most target_faces faces are left
+    //or nothing can be collapsed anymore
+    pub fn run(&mut self, target_faces: usize, progress: &mut Progress) -> PyResult<()> {
+        let initial_faces = self.live_faces;
+        if initial_faces <= target_faces {
+            return progress.finish();
+        }
+
+        let mut edges: Vec<(usize, usize)> = self.faces
+            .iter()
+            .flat_map(|face| (0..3).map(move |i| (face[i].min(face[(i + 1) % 3]), face[i].max(face[(i + 1) % 3]))))
+            .filter(|&(a, b)| a != b)
+            .collect();
+        edges.sort();
+        edges.dedup();
+
+        let mut heap: BinaryHeap<Candidate> = edges.into_iter().map(|(a, b)| self.candidate(a, b)).collect();
+
+        while self.live_faces > target_faces {
+            let candidate = match heap.pop() {
+                Some(candidate) => candidate,
+                None => break,
+            };
+
+            let (a, b) = (candidate.a, candidate.b);
+            if candidate.version_a != self.version[a] || candidate.version_b != self.version[b] {
+                continue;
+            }
+
+            if self.try_collapse(a, b).is_err() {
+                continue;
+            }
+
+            for neighbor in self.neighbors(a) {
+                heap.push(self.candidate(a, neighbor));
+            }
+
+            progress.report((initial_faces - self.live_faces) as f32 / (initial_faces - target_faces) as f32)?;
+        }
+
+        progress.finish()
+    }
+
+    //writes the collapses back into the mesh they started from without renumbering anything,
+    //merged away vertices stay behind without faces
+    pub fn apply_to(&self, mesh: &mut Mesh) {
+        for (vertex, position) in mesh.vertices.iter_mut().zip(&self.vertices) {
+            *vertex = Point3::from(position.cast::<f32>());
+        }
+        mesh.faces = self.faces.clone();
+        mesh.retain_faces(&self.face_alive);
+        mesh.geometry_changed();
+    }
+
+    //surviving faces in their original order over the vertices they still use
+    pub fn to_mesh(&self) -> Mesh {
+        let mut used = vec![false; self.vertices.len()];
+        for (face, _) in self.faces.iter().zip(&self.face_alive).filter(|(_, &alive)| alive) {
+            for &v in face {
+                used[v] = true;
+            }
+        }
+
+        let mut remap = vec![usize::MAX; self.vertices.len()];
+        let mut vertices = Vec::new();
+        for v in (0..self.vertices.len()).filter(|&v| used[v]) {
+            remap[v] = vertices.len();
+            vertices.push(Point3::from(self.vertices[v].cast::<f32>()));
+        }
+
+        let faces = self.faces
+            .iter()
+            .zip(&self.face_alive)
+            .filter(|(_, &alive)| alive)
+            .map(|(face, _)| face.map(|v| remap[v]))
+            .collect();
+
+        Mesh {
+            vertices,
+            faces,
+            normals: None,
+            ..Default::default()
+        }
+    }
+}
+
+//fundamental error quadric of the plane through a triangle, zero for degenerate triangles
+fn plane_quadric(p0: &Vector3<f64>, p1: &Vector3<f64>, p2: &Vector3<f64>) -> Matrix4<f64> {
+    match (p1 - p0).cross(&(p2 - p0)).try_normalize(0.0) {
+        Some(normal) => {
+            let plane = Vector4::new(normal.x, normal.y, normal.z, -normal.dot(p0));
+            plane * plane.transpose()
+        }
+        None => Matrix4::zeros(),
+    }
+}
+
+//quadric of the plane through edge (a, b) perpendicular to the face with the given normal
+fn edge_quadric(a: &Vector3<f64>, b: &Vector3<f64>, face_normal: Option<Vector3<f64>>) -> Matrix4<f64> {
+    match face_normal.and_then(|n| (b - a).cross(&n).try_normalize(0.0)) {
+        Some(normal) => {
+            let plane = Vector4::new(normal.x, normal.y, normal.z, -normal.dot(a));
+            plane * plane.transpose()
+        }
+        None => Matrix4::zeros(),
+    }
+}
+
+pub fn simplify(mesh: &Mesh, target_faces: usize, options: &SimplifyOptions, progress: &mut Progress) -> PyResult<Mesh> {
+    let mut simplifier = Simplifier::new(mesh, options);
+    simplifier.run(target_faces, progress)?;
+    Ok(simplifier.to_mesh())
+}
@@ -2,32 +2,26 @@ use crate::mesh::types::Mesh;
 use nalgebra::Point3;
 use pyo3::prelude::*;
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
-pub struct TopologyAnalyzer {
-    mesh: Mesh,
-    edge_to_faces: HashMap<(usize, usize), Vec<usize>>,
-    vertex_to_faces: HashMap<usize, Vec<usize>>,
+//edge and vertex incidence of a face list, cached on the mesh and shared by every analyzer
+//built from it until the geometry changes
+pub struct Topology {
+    pub edge_to_faces: HashMap<(usize, usize), Vec<usize>>,
+    pub vertex_to_faces: HashMap<usize, Vec<usize>>,
 }
 
-impl TopologyAnalyzer {
-    pub fn new(mesh: Mesh) -> Self{
-        let mut analyzer = TopologyAnalyzer{
-            mesh,
-            edge_to_faces: HashMap::new(),
-            vertex_to_faces: HashMap::new(),
-        };
-
-        analyzer.build_topology_maps();
-        analyzer
-    }
+impl Topology {
+    pub fn new(faces: &[[usize; 3]]) -> Self {
+        let mut edge_to_faces: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        let mut vertex_to_faces: HashMap<usize, Vec<usize>> = HashMap::new();
 
-    fn build_topology_maps(&mut self) {
-        for (face_idx, face) in self.mesh.faces.iter().enumerate() {
+        for (face_idx, face) in faces.iter().enumerate() {
 
             for &vertex_idx in face {
-                self.vertex_to_faces
+                vertex_to_faces
                     .entry(vertex_idx)
-                    .or_insert_with(Vec::new)
+                    .or_default()
                     .push(face_idx);
             }
 
@@ -38,12 +32,17 @@ impl TopologyAnalyzer {
             ];
 
             for edge in edges{
-                self.edge_to_faces
+                edge_to_faces
                     .entry(edge)
-                    .or_insert_with(Vec::new)
+                    .or_default()
                     .push(face_idx);
             }
         }
+
+        Topology {
+            edge_to_faces,
+            vertex_to_faces,
+        }
     }
 
     pub fn is_watertight(&self) -> bool {
@@ -52,50 +51,562 @@ impl TopologyAnalyzer {
             .all(|faces| faces.len() == 2 )
     }
 
+    //boundary edges directed along the winding of their single face in faces
+    pub fn boundary_half_edges(&self, faces: &[[usize; 3]]) -> Vec<(usize, usize)> {
+        let mut boundary_edges: Vec<(usize, usize)> = self.edge_to_faces
+            .iter()
+            .filter(|(_, edge_faces)| edge_faces.len() == 1)
+            .map(|(&(a, b), edge_faces)| {
+                let face = &faces[edge_faces[0]];
+                if (0..3).any(|i| face[i] == a && face[(i + 1) % 3] == b) {
+                    (a, b)
+                } else {
+                    (b, a)
+                }
+            })
+            .collect();
+
+        boundary_edges.sort();
+        boundary_edges
+    }
+
+    //boundary loops as vertex sequences following the winding of faces
+    //loops touching at a vertex are reported separately
+    pub fn find_holes(&self, faces: &[[usize; 3]]) -> Vec<Vec<usize>> {
+        let boundary_edges = self.boundary_half_edges(faces);
+
+        let mut outgoing: HashMap<usize, Vec<usize>> = HashMap::new();
+        for &(a, b) in boundary_edges.iter().rev() {
+            outgoing.entry(a).or_default().push(b);
+        }
+
+        let mut holes = Vec::new();
+
+        for &(start, _) in &boundary_edges {
+            while let Some(mut current) = outgoing.get_mut(&start).and_then(|next| next.pop()) {
+                let mut path = vec![start];
+
+                while current != start {
+                    path.push(current);
+                    match outgoing.get_mut(&current).and_then(|next| next.pop()) {
+                        Some(next) => current = next,
+                        None => break,
+                    }
+                }
+
+                holes.extend(split_at_repeated_vertices(path));
+            }
+        }
+
+        holes
+    }
+
+    //is_watertight for every one of components, given as face lists covering face_count faces
+    pub fn watertight_components(&self, components: &[Vec<usize>], face_count: usize) -> Vec<bool> {
+        let mut component_of_face = vec![0; face_count];
+        for (component, faces) in components.iter().enumerate() {
+            for &f in faces {
+                component_of_face[f] = component;
+            }
+        }
+
+        let mut watertight = vec![true; components.len()];
+        for faces in self.edge_to_faces.values() {
+            if faces.len() != 2 {
+                watertight[component_of_face[faces[0]]] = false;
+            }
+        }
+        watertight
+    }
+}
+
+//faces per undirected edge kept up to date as single faces are added and removed, so
+//editors can ask for watertightness after every edit without rebuilding Topology
+#[derive(Clone, Default)]
+pub struct EdgeCounts {
+    counts: HashMap<(usize, usize), usize>,
+    //edges not shared by exactly two faces
+    irregular: usize,
+}
+
+impl EdgeCounts {
+    pub fn new(faces: &[[usize; 3]]) -> Self {
+        let mut edge_counts = EdgeCounts::default();
+        for face in faces {
+            edge_counts.add(face);
+        }
+        edge_counts
+    }
+
+    pub fn add(&mut self, face: &[usize; 3]) {
+        for edge in Self::edges(face) {
+            let count = self.counts.entry(edge).or_insert(0);
+            *count += 1;
+            let after = *count;
+            self.recount(after - 1, after);
+        }
+    }
+
+    //the face must have been added before
+    pub fn remove(&mut self, face: &[usize; 3]) {
+        for edge in Self::edges(face) {
+            let count = self.counts.get_mut(&edge).expect("removed face was counted");
+            let before = *count;
+            *count -= 1;
+            let after = *count;
+            if after == 0 {
+                self.counts.remove(&edge);
+            }
+            self.recount(before, after);
+        }
+    }
+
+    pub fn is_watertight(&self) -> bool {
+        self.irregular == 0
+    }
+
+    fn recount(&mut self, before: usize, after: usize) {
+        //edges that vanish entirely aren't irregular anymore
+        let irregular = |count: usize| count != 0 && count != 2;
+        match (irregular(before), irregular(after)) {
+            (false, true) => self.irregular += 1,
+            (true, false) => self.irregular -= 1,
+            _ => {}
+        }
+    }
+
+    fn edges(face: &[usize; 3]) -> [(usize, usize); 3] {
+        [0, 1, 2].map(|i| {
+            let (a, b) = (face[i], face[(i + 1) % 3]);
+            (a.min(b), a.max(b))
+        })
+    }
+}
+
+pub struct TopologyAnalyzer {
+    mesh: Mesh,
+    topology: Arc<Topology>,
+}
+
+impl TopologyAnalyzer {
+    pub fn new(mesh: Mesh) -> Self{
+        let topology = mesh.topology();
+        TopologyAnalyzer{
+            mesh,
+            topology,
+        }
+    }
+
+    //analyzes a borrowed mesh through its cached topology, building it there on first use
+    //only positions and faces are copied, normals, attributes and search trees stay behind
+    pub fn of(mesh: &Mesh) -> Self {
+        let topology = mesh.topology();
+        let connectivity = Mesh {
+            vertices: mesh.vertices.clone(),
+            faces: mesh.faces.clone(),
+            ..Default::default()
+        };
+        let _ = connectivity.topology.set(topology.clone());
+        TopologyAnalyzer{
+            mesh: connectivity,
+            topology,
+        }
+    }
+
+    //analyzes the connectivity as if vertices closer than tolerance were welded
+    //faces are rewritten to the first vertex of each cluster, positions stay untouched, so
+    //returned vertex indices refer to the original mesh and welded duplicates show up as isolated
+    //a negative tolerance is relative to the mesh's characteristic_length
+    pub fn with_tolerance(mesh: &Mesh, tolerance: f32) -> Self {
+        let representative = mesh.weld_map(&vec![true; mesh.vertices.len()], tolerance);
+        let welded = Mesh {
+            vertices: mesh.vertices.clone(),
+            faces: mesh
+                .faces
+                .iter()
+                .map(|face| face.map(|index| representative[index]))
+                .collect(),
+            ..Default::default()
+        };
+
+        TopologyAnalyzer::new(welded)
+    }
+
+    pub fn is_watertight(&self) -> bool {
+        self.topology.is_watertight()
+    }
+
+    //is_watertight for every connected component, in connected_components order
+    pub fn watertight_components(&self) -> Vec<bool> {
+        let components = self.mesh.connected_components();
+        self.topology.watertight_components(&components, self.mesh.faces.len())
+    }
+
+    //indices of the components that are not watertight
+    pub fn open_components(&self) -> Vec<usize> {
+        self.watertight_components()
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, watertight)| !watertight)
+            .map(|(component, _)| component)
+            .collect()
+    }
+
+    //V - E + F over the vertices faces use, so stray vertices don't shift it
+    //it adds up over components: two disjoint tetrahedra give 4, not 2
+    pub fn euler_characteristic(&self) -> i64 {
+        let v = self.topology.vertex_to_faces.len() as i64;
+        let e = self.topology.edge_to_faces.len() as i64;
+        let f = self.mesh.faces.len() as i64;
+
+        v - e + f
+    }
+
+    //(interior, boundary, non-manifold) edge counts, edges with 2, 1 and more than 2 faces
+    //a closed manifold mesh gives (edge count, 0, 0)
+    pub fn edge_classification(&self) -> (usize, usize, usize) {
+        let mut counts = (0, 0, 0);
+        for faces in self.topology.edge_to_faces.values() {
+            match faces.len() {
+                1 => counts.1 += 1,
+                2 => counts.0 += 1,
+                _ => counts.2 += 1,
+            }
+        }
+        counts
+    }
+
+    //whether the faces around every vertex form a single fan, i.e. no two surface sheets
+    //touch at just a vertex; with a watertight mesh that makes the surface a manifold
+    pub fn has_manifold_vertices(&self) -> bool {
+        self.topology.vertex_to_faces.iter().all(|(&vertex, faces)| {
+            //the edges of the faces opposite the vertex must chain into one loop
+            let mut link: HashMap<usize, Vec<usize>> = HashMap::new();
+            for &f in faces {
+                let face = self.mesh.faces[f];
+                let others: Vec<usize> = face.iter().copied().filter(|&v| v != vertex).collect();
+                if others.len() != 2 {
+                    return false;
+                }
+                link.entry(others[0]).or_default().push(others[1]);
+                link.entry(others[1]).or_default().push(others[0]);
+            }
+
+            let start = match link.keys().next() {
+                Some(&start) => start,
+                None => return true,
+            };
+            let mut seen = HashSet::from([start]);
+            let mut stack = vec![start];
+            while let Some(v) = stack.pop() {
+                for &next in &link[&v] {
+                    if seen.insert(next) {
+                        stack.push(next);
+                    }
+                }
+            }
+            seen.len() == link.len()
+        })
+    }
+
+    //a single closed manifold surface of genus 0: watertight, connected, no pinched
+    //vertices and Euler characteristic 2
     pub fn is_sphere_like(&self) -> bool {
-        let v = self.mesh.vertices().len();
-        let f = self.mesh.faces().len();
-        let e = self.edge_to_faces.len();
+        self.is_watertight()
+            && self.mesh.connected_components().len() == 1
+            && self.has_manifold_vertices()
+            && self.euler_characteristic() == 2
+    }
 
-        self.is_watertight() && v - e + f == 2
+    //length of every unique edge, ordered by (min vertex, max vertex)
+    pub fn edge_lengths(&self) -> Vec<f32> {
+        let mut edges: Vec<&(usize, usize)> = self.topology.edge_to_faces.keys().collect();
+        edges.sort();
+
+        edges
+            .into_iter()
+            .map(|&(a, b)| (self.mesh.vertices[a] - self.mesh.vertices[b]).norm())
+            .collect()
     }
 
-    pub fn find_holes(&self) -> Vec<Vec<usize>> {
-        let mut boundary_edges: Vec<(usize, usize)> = self.edge_to_faces
+    pub fn min_edge_length(&self) -> Option<f32> {
+        self.edge_lengths().into_iter().reduce(f32::min)
+    }
+
+    pub fn max_edge_length(&self) -> Option<f32> {
+        self.edge_lengths().into_iter().reduce(f32::max)
+    }
+
+    pub fn mean_edge_length(&self) -> Option<f32> {
+        let lengths = self.edge_lengths();
+        if lengths.is_empty() {
+            return None;
+        }
+
+        Some(lengths.iter().sum::<f32>() / lengths.len() as f32)
+    }
+
+    //edges whose two faces meet at a dihedral angle above the threshold, plus non-manifold edges
+    pub fn sharp_edges(&self, angle_threshold_deg: f32) -> Vec<(usize, usize)> {
+        let cos_threshold = angle_threshold_deg.to_radians().cos();
+
+        let mut sharp: Vec<(usize, usize)> = self.topology.edge_to_faces
             .iter()
-            .filter(|_, faces| faces.len() == 1)
-            .map(|&edge, _| *edge)
+            .filter(|(_, faces)| match faces.len() {
+                2 => {
+                    let n0 = self.mesh.face_normal(faces[0]);
+                    let n1 = self.mesh.face_normal(faces[1]);
+                    n0.dot(&n1) < cos_threshold
+                }
+                count => count > 2,
+            })
+            .map(|(&edge, _)| edge)
             .collect();
 
-        if boundary_edges.is_empty() {
-            return Vec::new();
+        sharp.sort();
+        sharp
+    }
+
+    //groups of faces reachable from each other without crossing a sharp edge
+    pub fn segment_by_sharp_edges(&self, angle_threshold_deg: f32) -> Vec<Vec<usize>> {
+        let sharp: HashSet<(usize, usize)> = self.sharp_edges(angle_threshold_deg).into_iter().collect();
+
+        let mut visited = vec![false; self.mesh.faces.len()];
+        let mut segments = Vec::new();
+
+        for seed in 0..self.mesh.faces.len() {
+            if visited[seed] {
+                continue;
+            }
+
+            visited[seed] = true;
+            let mut segment = vec![seed];
+            let mut stack = vec![seed];
+
+            while let Some(face_idx) = stack.pop() {
+                let face = &self.mesh.faces[face_idx];
+
+                for i in 0..3 {
+                    let (a, b) = (face[i], face[(i + 1) % 3]);
+                    let edge = (a.min(b), a.max(b));
+                    if sharp.contains(&edge) {
+                        continue;
+                    }
+
+                    for &other in &self.topology.edge_to_faces[&edge] {
+                        if !visited[other] {
+                            visited[other] = true;
+                            segment.push(other);
+                            stack.push(other);
+                        }
+                    }
+                }
+            }
+
+            segment.sort();
+            segments.push(segment);
         }
 
-        let mut holes = Vec::new();
-        let mut remaining_edges = boundary_edges.clone();
+        segments
+    }
 
-        while !remaining_edges.is_empty() {
-            let mut holes = Vec::new();
-            let mut current_edge = remaining_edges.pop().unwrap();
-            let mut current_vertex = current_edge.1;
+    //groups of edge-connected faces whose normals stay within the tolerance of the
+    //normal of the group's first face, so slowly curving surfaces don't chain into one group
+    pub fn coplanar_regions(&self, angle_tolerance_deg: f32) -> Vec<Vec<usize>> {
+        let cos_tolerance = angle_tolerance_deg.to_radians().cos();
+        let adjacency = self.face_adjacency();
 
-            hole.push(current_vertex.0);
-            hole.push(current_vertex);
+        let mut visited = vec![false; self.mesh.faces.len()];
+        let mut regions = Vec::new();
 
-            while let Some(pos) = remaining_edges.iter().position(|&(a,b)|{
-                a == current_vertex || b == current_vertex
-            }) {
-                let edge = remaining_edges.swap_remove(pos);
-                current_vertex = if egde.0 == current_vertex { edge.1 } else { edge.0 };
-                hole.push(current_vertex);
+        for seed in 0..self.mesh.faces.len() {
+            if visited[seed] {
+                continue;
             }
 
-            holes.push(hole);
+            let seed_normal = self.mesh.face_normal(seed);
+            visited[seed] = true;
+            let mut region = vec![seed];
+            let mut stack = vec![seed];
 
+            while let Some(face_idx) = stack.pop() {
+                for &other in &adjacency[face_idx] {
+                    if !visited[other] && self.mesh.face_normal(other).dot(&seed_normal) >= cos_tolerance {
+                        visited[other] = true;
+                        region.push(other);
+                        stack.push(other);
+                    }
+                }
+            }
+
+            region.sort();
+            regions.push(region);
         }
 
-        holes
+        regions
+    }
+
+    //faces sharing an edge with each face, sorted and without repeats
+    //non-manifold edges link a face to every other face on them
+    pub fn face_adjacency(&self) -> Vec<Vec<usize>> {
+        self.mesh.faces
+            .iter()
+            .enumerate()
+            .map(|(face_idx, face)| {
+                let mut neighbors: Vec<usize> = (0..3)
+                    .flat_map(|i| {
+                        let (a, b) = (face[i], face[(i + 1) % 3]);
+                        self.topology.edge_to_faces[&(a.min(b), a.max(b))].iter().copied()
+                    })
+                    .filter(|&other| other != face_idx)
+                    .collect();
+
+                neighbors.sort();
+                neighbors.dedup();
+                neighbors
+            })
+            .collect()
+    }
+
+    //face adjacency as a directed COO edge list (src, dst, shared edge length), each pair of
+    //neighbouring faces in both directions, sorted by (src, dst)
+    //faces sharing more than one edge get a single entry weighted by the total shared length
+    pub fn dual_graph(&self) -> (Vec<usize>, Vec<usize>, Vec<f32>) {
+        let mut links: Vec<(usize, usize, f32)> = Vec::with_capacity(self.topology.edge_to_faces.len() * 2);
+
+        for (&(a, b), faces) in &self.topology.edge_to_faces {
+            let length = (self.mesh.vertices[b] - self.mesh.vertices[a]).norm();
+            for (i, &f) in faces.iter().enumerate() {
+                for &g in &faces[i + 1..] {
+                    if f != g {
+                        links.push((f, g, length));
+                        links.push((g, f, length));
+                    }
+                }
+            }
+        }
+
+        links.sort_unstable_by(|x, y| (x.0, x.1).cmp(&(y.0, y.1)).then(x.2.total_cmp(&y.2)));
+
+        let mut src = Vec::with_capacity(links.len());
+        let mut dst = Vec::with_capacity(links.len());
+        let mut weights: Vec<f32> = Vec::with_capacity(links.len());
+        for (f, g, length) in links {
+            if src.last() == Some(&f) && dst.last() == Some(&g) {
+                *weights.last_mut().unwrap() += length;
+                continue;
+            }
+            src.push(f);
+            dst.push(g);
+            weights.push(length);
+        }
+
+        (src, dst, weights)
+    }
+
+    //faces using the vertex, in face order
+    pub fn vertex_faces(&self, vertex: usize) -> PyResult<Vec<usize>> {
+        self.mesh.check_vertex_index(vertex)?;
+        Ok(self.topology.vertex_to_faces.get(&vertex).cloned().unwrap_or_default())
+    }
+
+    //faces on the edge in either direction, empty if there is no such edge
+    pub fn edge_faces(&self, a: usize, b: usize) -> PyResult<Vec<usize>> {
+        self.mesh.check_vertex_index(a)?;
+        self.mesh.check_vertex_index(b)?;
+        Ok(self.topology.edge_to_faces.get(&(a.min(b), a.max(b))).cloned().unwrap_or_default())
+    }
+
+    //pairs of faces over the same three vertices in any rotation or winding, each later copy
+    //paired with the first face of its group, sorted by the later face
+    pub fn duplicate_faces(&self) -> Vec<(usize, usize)> {
+        let mut pairs: Vec<(usize, usize)> = duplicate_face_groups(&self.mesh.faces)
+            .into_iter()
+            .flat_map(|group| group[1..].iter().map(|&f| (group[0], f)).collect::<Vec<_>>())
+            .collect();
+        pairs.sort_by_key(|&(_, f)| f);
+        pairs
+    }
+
+    pub fn isolated_vertices(&self) -> Vec<usize> {
+        (0..self.mesh.vertices.len())
+            .filter(|v| !self.topology.vertex_to_faces.contains_key(v))
+            .collect()
+    }
+
+    //boundary loops as vertex sequences following the face winding
+    //loops touching at a vertex are reported separately
+    pub fn find_holes(&self) -> Vec<Vec<usize>> {
+        self.topology.find_holes(&self.mesh.faces)
+    }
+
+    //every boundary loop of find_holes together with its perimeter
+    pub fn classify_holes(&self) -> Vec<(Vec<usize>, f32)> {
+        self.find_holes()
+            .into_iter()
+            .map(|hole| {
+                let perimeter = loop_perimeter(&self.mesh.vertices, &hole);
+                (hole, perimeter)
+            })
+            .collect()
+    }
+}
+
+//length of the closed polyline through the vertices of a loop, e.g. a hole from find_holes
+pub fn loop_perimeter(vertices: &[Point3<f32>], hole: &[usize]) -> f32 {
+    (0..hole.len())
+        .map(|i| (vertices[hole[(i + 1) % hole.len()]] - vertices[hole[i]]).norm())
+        .sum()
+}
+
+//faces over the same vertex set, in face order, for every set used more than once
+//groups are ordered by their first face
+pub fn duplicate_face_groups(faces: &[[usize; 3]]) -> Vec<Vec<usize>> {
+    let mut groups: HashMap<[usize; 3], Vec<usize>> = HashMap::new();
+    for (face_idx, face) in faces.iter().enumerate() {
+        let mut key = *face;
+        key.sort_unstable();
+        groups.entry(key).or_default().push(face_idx);
+    }
+
+    let mut duplicates: Vec<Vec<usize>> = groups.into_values().filter(|group| group.len() > 1).collect();
+    duplicates.sort_by_key(|group| group[0]);
+    duplicates
+}
+
+//whether the face is an even permutation of its sorted vertices, i.e. its winding class
+pub fn winding_parity(face: &[usize; 3]) -> bool {
+    //an odd number of out of order pairs makes an odd permutation
+    !((face[0] > face[1]) ^ (face[0] > face[2]) ^ (face[1] > face[2]))
+}
+
+//cuts a closed walk that passes a vertex more than once into simple loops
+fn split_at_repeated_vertices(path: Vec<usize>) -> Vec<Vec<usize>> {
+    let mut loops = Vec::new();
+    let mut stack: Vec<usize> = Vec::new();
+    let mut position: HashMap<usize, usize> = HashMap::new();
+
+    for vertex in path {
+        if let Some(&pos) = position.get(&vertex) {
+            let sub_loop: Vec<usize> = stack.drain(pos + 1..).collect();
+            for v in &sub_loop {
+                position.remove(v);
+            }
+
+            let mut closed = vec![vertex];
+            closed.extend(sub_loop);
+            loops.push(closed);
+        } else {
+            position.insert(vertex, stack.len());
+            stack.push(vertex);
+        }
     }
+
+    if !stack.is_empty() {
+        loops.push(stack);
+    }
+
+    loops
 }
 
 #[pyclass]
@@ -108,7 +619,14 @@ impl PyTopologyAnalyzer {
     #[new]
     fn new(mesh: &Mesh) -> Self {
         PyTopologyAnalyzer{
-            analyzer: TopologyAnalyzer::new(mesh.clone()),
+            analyzer: TopologyAnalyzer::of(mesh),
+        }
+    }
+
+    #[staticmethod]
+    fn with_tolerance(mesh: &Mesh, tolerance: f32) -> Self {
+        PyTopologyAnalyzer{
+            analyzer: TopologyAnalyzer::with_tolerance(mesh, tolerance),
         }
     }
 
@@ -116,11 +634,95 @@ impl PyTopologyAnalyzer {
         self.analyzer.is_watertight()
     }
 
+    fn watertight_components(&self) -> Vec<bool> {
+        self.analyzer.watertight_components()
+    }
+
+    fn open_components(&self) -> Vec<usize> {
+        self.analyzer.open_components()
+    }
+
     fn is_sphere_like(&self) -> bool {
         self.analyzer.is_sphere_like()
     }
 
+    fn euler_characteristic(&self) -> i64 {
+        self.analyzer.euler_characteristic()
+    }
+
+    fn edge_classification(&self) -> (usize, usize, usize) {
+        self.analyzer.edge_classification()
+    }
+
+    fn has_manifold_vertices(&self) -> bool {
+        self.analyzer.has_manifold_vertices()
+    }
+
     fn find_holes(&self) -> Vec<Vec<usize>> {
         self.analyzer.find_holes()
     }
+
+    fn classify_holes(&self) -> Vec<(Vec<usize>, f32)> {
+        self.analyzer.classify_holes()
+    }
+
+    fn coplanar_regions(&self, angle_tolerance_deg: f32) -> Vec<Vec<usize>> {
+        self.analyzer.coplanar_regions(angle_tolerance_deg)
+    }
+
+    fn face_adjacency(&self) -> Vec<Vec<usize>> {
+        self.analyzer.face_adjacency()
+    }
+
+    //dual graph as (src, dst) arrays for graph libraries, plus the shared edge lengths
+    //as a third array with edge_weights=True
+    #[pyo3(signature = (edge_weights=false))]
+    fn dual_graph(&self, py: Python, edge_weights: bool) -> PyObject {
+        let (src, dst, weights) = self.analyzer.dual_graph();
+        if edge_weights {
+            (src, dst, weights).into_py(py)
+        } else {
+            (src, dst).into_py(py)
+        }
+    }
+
+    fn vertex_faces(&self, vertex: usize) -> PyResult<Vec<usize>> {
+        self.analyzer.vertex_faces(vertex)
+    }
+
+    fn edge_faces(&self, a: usize, b: usize) -> PyResult<Vec<usize>> {
+        self.analyzer.edge_faces(a, b)
+    }
+
+    fn duplicate_faces(&self) -> Vec<(usize, usize)> {
+        self.analyzer.duplicate_faces()
+    }
+
+    fn isolated_vertices(&self) -> Vec<usize> {
+        self.analyzer.isolated_vertices()
+    }
+
+    fn sharp_edges(&self, angle_threshold_deg: f32) -> Vec<(usize, usize)> {
+        self.analyzer.sharp_edges(angle_threshold_deg)
+    }
+
+    fn segment_by_sharp_edges(&self, angle_threshold_deg: f32) -> Vec<Vec<usize>> {
+        self.analyzer.segment_by_sharp_edges(angle_threshold_deg)
+    }
+
+    fn edge_lengths(&self) -> Vec<f32> {
+        self.analyzer.edge_lengths()
+    }
+
+    fn min_edge_length(&self) -> Option<f32> {
+        self.analyzer.min_edge_length()
+    }
+
+    fn max_edge_length(&self) -> Option<f32> {
+        self.analyzer.max_edge_length()
+    }
+
+    fn mean_edge_length(&self) -> Option<f32> {
+        self.analyzer.mean_edge_length()
+    }
 }
\ No newline at end of file
@@ -0,0 +1,22 @@
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+
+//Exceptions raised by the module.
+//MeshError derives from ValueError, which everything used to raise, so existing
+//`except ValueError` blocks keep catching them. Invalid arguments still raise plain
+//ValueError and bad indices IndexError.
+
+create_exception!(meshalyzer, MeshError, PyValueError, "Base class of the errors raised for mesh data.");
+create_exception!(meshalyzer, MeshParseError, MeshError, "A mesh file could not be read.");
+create_exception!(
+    meshalyzer,
+    MeshTopologyError,
+    MeshError,
+    "The mesh connectivity doesn't allow the operation, e.g. it isn't watertight."
+);
+create_exception!(
+    meshalyzer,
+    MeshGeometryError,
+    MeshError,
+    "The mesh geometry doesn't allow the operation, e.g. it is empty or degenerate."
+);
@@ -0,0 +1,93 @@
+use crate::io::{bin, obj};
+use crate::mesh::types::Mesh;
+use pyo3::prelude::*;
+use std::path::Path;
+
+//Loading whole directories in one call, with the files parsed in parallel under the
+//`parallel` feature. The reader is picked by extension after stripping ".gz"; gzip itself is
+//detected by content like for single files.
+
+//(path, mesh) for every file loaded and (path, error) for every file that failed
+type Loaded = (Vec<(String, Mesh)>, Vec<(String, String)>);
+
+//loads the files in a directory whose names match pattern, where * matches any run of
+//characters and ? a single one, e.g. "*.obj" or "scan_??.obj.gz"; subdirectories are skipped
+//.obj and .bin (save_bin) files are read, other matches are reported as unsupported
+//files that fail don't stop the batch, both lists are sorted by path
+#[pyfunction]
+pub fn load_dir(py: Python, path: &str, pattern: &str) -> PyResult<Loaded> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if entry.file_type()?.is_file() && matches(pattern, &name) {
+            files.push(Path::new(path).join(name).to_string_lossy().into_owned());
+        }
+    }
+    files.sort();
+
+    let results: Vec<PyResult<Mesh>> = py.allow_threads(|| {
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            files.par_iter().map(|file| load(file)).collect()
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            files.iter().map(|file| load(file)).collect()
+        }
+    });
+
+    let mut meshes = Vec::new();
+    let mut errors = Vec::new();
+    for (file, result) in files.into_iter().zip(results) {
+        match result {
+            Ok(mesh) => meshes.push((file, mesh)),
+            Err(error) => errors.push((file, error.to_string())),
+        }
+    }
+
+    Ok((meshes, errors))
+}
+
+fn load(file: &str) -> PyResult<Mesh> {
+    let name = file.strip_suffix(".gz").unwrap_or(file);
+    let extension = Path::new(name).extension().and_then(|e| e.to_str()).unwrap_or("");
+
+    match extension.to_ascii_lowercase().as_str() {
+        "obj" => obj::load(file, false),
+        "bin" => bin::load(file),
+        _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "unsupported mesh format '.{}'",
+            extension
+        ))),
+    }
+}
+
+//whole-name wildcard match, * backtracks to the last star on a mismatch
+fn matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let (mut p, mut n) = (0, 0);
+    //pattern index after the last star and the name index it currently stands for
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p + 1, n));
+            p += 1;
+        } else if let Some((after_star, matched)) = star {
+            //let the star swallow one more character
+            star = Some((after_star, matched + 1));
+            p = after_star;
+            n = matched + 1;
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
@@ -0,0 +1,215 @@
+use crate::mesh::types::Mesh;
+use byteorder::{ByteOrder, LittleEndian, ReadBytesExt, WriteBytesExt};
+use nalgebra::Point3;
+use crate::errors::{MeshError, MeshParseError};
+use crate::io::gzip;
+use pyo3::prelude::*;
+use std::io::{BufWriter, Read, Write};
+
+//Raw binary dump for fast reloading, everything little endian:
+//  magic "MSHB", format version u32, flags u32, vertex count u64, face count u64,
+//  vertices as 3 x f32, faces as 3 x u32, then one block per flagged attribute in flag order
+//  (normals as 3 x f32, colors as 3 x f32, face groups as a u32 per face followed by the
+//  group name count u32 and every name as its byte length u32 and UTF-8 bytes, uvs as 2 x f32).
+//Bump VERSION whenever the layout changes so old readers refuse new files; files of earlier
+//versions still load, they just can't have the flags added since.
+
+const MAGIC: &[u8; 4] = b"MSHB";
+//2 added colors, 3 face groups, 4 uvs
+const VERSION: u32 = 4;
+
+const HAS_NORMALS: u32 = 1;
+const HAS_COLORS: u32 = 2;
+const HAS_FACE_GROUPS: u32 = 4;
+const HAS_UVS: u32 = 8;
+
+pub fn save(mesh: &Mesh, filename: &str) -> PyResult<()> {
+    if mesh.vertices.len() > u32::MAX as usize {
+        return Err(MeshError::new_err("binary format stores 32-bit indices, mesh has too many vertices"));
+    }
+
+    let mut writer = BufWriter::new(gzip::create(filename)?);
+
+    let mut flags = 0;
+    if mesh.normals.is_some() {
+        flags |= HAS_NORMALS;
+    }
+    if mesh.colors.is_some() {
+        flags |= HAS_COLORS;
+    }
+    if mesh.face_groups.is_some() {
+        flags |= HAS_FACE_GROUPS;
+    }
+    if mesh.uvs.is_some() {
+        flags |= HAS_UVS;
+    }
+
+    writer.write_all(MAGIC)?;
+    writer.write_u32::<LittleEndian>(VERSION)?;
+    writer.write_u32::<LittleEndian>(flags)?;
+    writer.write_u64::<LittleEndian>(mesh.vertices.len() as u64)?;
+    writer.write_u64::<LittleEndian>(mesh.faces.len() as u64)?;
+
+    write_points(&mut writer, &mesh.vertices)?;
+    for face in &mesh.faces {
+        for &index in face {
+            writer.write_u32::<LittleEndian>(index as u32)?;
+        }
+    }
+    if let Some(normals) = &mesh.normals {
+        write_points(&mut writer, normals)?;
+    }
+    if let Some(colors) = &mesh.colors {
+        for color in colors {
+            for &channel in color {
+                writer.write_f32::<LittleEndian>(channel)?;
+            }
+        }
+    }
+    if let Some(face_groups) = &mesh.face_groups {
+        for &group in face_groups {
+            writer.write_u32::<LittleEndian>(group)?;
+        }
+        writer.write_u32::<LittleEndian>(mesh.group_names.len() as u32)?;
+        for name in &mesh.group_names {
+            writer.write_u32::<LittleEndian>(name.len() as u32)?;
+            writer.write_all(name.as_bytes())?;
+        }
+    }
+    if let Some(uvs) = &mesh.uvs {
+        for uv in uvs {
+            writer.write_f32::<LittleEndian>(uv[0])?;
+            writer.write_f32::<LittleEndian>(uv[1])?;
+        }
+    }
+
+    gzip::finish(writer)?;
+    Ok(())
+}
+
+pub fn load(filename: &str) -> PyResult<Mesh> {
+    let mut reader = gzip::open(filename)?;
+
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(MeshParseError::new_err(format!("{} is not a meshalyzer binary mesh", filename)));
+    }
+
+    let version = reader.read_u32::<LittleEndian>()?;
+    if version == 0 || version > VERSION {
+        return Err(MeshParseError::new_err(format!(
+            "{} has binary format version {}, expected at most {}",
+            filename, version, VERSION
+        )));
+    }
+
+    let flags = reader.read_u32::<LittleEndian>()?;
+    let vertex_count = reader.read_u64::<LittleEndian>()? as usize;
+    let face_count = reader.read_u64::<LittleEndian>()? as usize;
+
+    let vertices = read_points(&mut reader, vertex_count, filename)?;
+
+    let indices = read_block(&mut reader, face_count, 12, filename)?;
+    let faces: Vec<[usize; 3]> = indices
+        .chunks_exact(12)
+        .map(|face| [0, 4, 8].map(|offset| LittleEndian::read_u32(&face[offset..]) as usize))
+        .collect();
+    if faces.iter().flatten().any(|&i| i >= vertex_count) {
+        return Err(MeshParseError::new_err(format!("{} has face indices out of range", filename)));
+    }
+
+    let normals = if flags & HAS_NORMALS != 0 {
+        Some(read_points(&mut reader, vertex_count, filename)?)
+    } else {
+        None
+    };
+    let colors = if flags & HAS_COLORS != 0 {
+        let points = read_points(&mut reader, vertex_count, filename)?;
+        Some(points.into_iter().map(|p| [p.x, p.y, p.z]).collect())
+    } else {
+        None
+    };
+    let (face_groups, group_names) = if flags & HAS_FACE_GROUPS != 0 {
+        let (groups, names) = read_face_groups(&mut reader, face_count, filename)?;
+        (Some(groups), names)
+    } else {
+        (None, Vec::new())
+    };
+    let uvs = if flags & HAS_UVS != 0 {
+        let uvs = read_block(&mut reader, vertex_count, 8, filename)?
+            .chunks_exact(8)
+            .map(|uv| [LittleEndian::read_f32(uv), LittleEndian::read_f32(&uv[4..])])
+            .collect();
+        Some(uvs)
+    } else {
+        None
+    };
+
+    Ok(Mesh {
+        vertices,
+        faces,
+        normals,
+        colors,
+        face_groups,
+        group_names,
+        uvs,
+        ..Default::default()
+    })
+}
+
+//the group id of every face and the group names, checking that every id has a name
+fn read_face_groups<R: Read>(reader: &mut R, face_count: usize, filename: &str) -> PyResult<(Vec<u32>, Vec<String>)> {
+    let groups: Vec<u32> = read_block(reader, face_count, 4, filename)?
+        .chunks_exact(4)
+        .map(LittleEndian::read_u32)
+        .collect();
+
+    let name_count = reader.read_u32::<LittleEndian>()?;
+    let mut names = Vec::new();
+    for _ in 0..name_count {
+        let length = reader.read_u32::<LittleEndian>()? as usize;
+        let name = String::from_utf8(read_block(reader, length, 1, filename)?)
+            .map_err(|_| MeshParseError::new_err(format!("{} has a group name that isn't UTF-8", filename)))?;
+        names.push(name);
+    }
+
+    if groups.iter().any(|&group| group as usize >= names.len()) {
+        return Err(MeshParseError::new_err(format!("{} has face groups without a name", filename)));
+    }
+    Ok((groups, names))
+}
+
+fn write_points<W: Write>(writer: &mut W, points: &[Point3<f32>]) -> std::io::Result<()> {
+    for point in points {
+        writer.write_f32::<LittleEndian>(point.x)?;
+        writer.write_f32::<LittleEndian>(point.y)?;
+        writer.write_f32::<LittleEndian>(point.z)?;
+    }
+    Ok(())
+}
+
+fn read_points<R: Read>(reader: &mut R, count: usize, filename: &str) -> PyResult<Vec<Point3<f32>>> {
+    Ok(read_block(reader, count, 12, filename)?
+        .chunks_exact(12)
+        .map(|p| {
+            let [x, y, z] = [0, 4, 8].map(|offset| LittleEndian::read_f32(&p[offset..]));
+            Point3::new(x, y, z)
+        })
+        .collect())
+}
+
+//the next count items of item_size bytes each; the counts come from the header, so the buffer
+//only grows as data actually arrives and a file shorter than its header claims is an error
+//instead of an allocation of whatever size the header names
+fn read_block<R: Read>(reader: &mut R, count: usize, item_size: usize, filename: &str) -> PyResult<Vec<u8>> {
+    let truncated = || MeshParseError::new_err(format!("{} is truncated", filename));
+
+    let size = count.checked_mul(item_size).ok_or_else(truncated)?;
+    let mut bytes = Vec::new();
+    reader.take(size as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != size {
+        return Err(truncated());
+    }
+    Ok(bytes)
+}
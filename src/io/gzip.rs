@@ -0,0 +1,92 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+
+//Transparent gzip for every file format, with the `compression` feature.
+//Files are decompressed when they start with the gzip magic, whatever they are called, and
+//compressed when written to a name ending in ".gz". Without the feature such files are
+//refused instead of being read or written as garbage.
+
+const MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+//opens a file for reading, decompressing it on the fly if it is gzip
+pub fn open(filename: &str) -> io::Result<Box<dyn BufRead>> {
+    let mut reader = BufReader::new(File::open(filename)?);
+    if !reader.fill_buf()?.starts_with(&MAGIC) {
+        return Ok(Box::new(reader));
+    }
+
+    #[cfg(feature = "compression")]
+    {
+        //multi-member so concatenated .gz files read whole, like gunzip does
+        Ok(Box::new(BufReader::new(flate2::bufread::MultiGzDecoder::new(reader))))
+    }
+    #[cfg(not(feature = "compression"))]
+    {
+        Err(unsupported(filename))
+    }
+}
+
+pub fn read_to_string(filename: &str) -> io::Result<String> {
+    let mut text = String::new();
+    open(filename)?.read_to_string(&mut text)?;
+    Ok(text)
+}
+
+//file being written, pass it to finish once done so a gzip stream gets its trailer
+pub enum Output {
+    Plain(File),
+    #[cfg(feature = "compression")]
+    Gzip(flate2::write::GzEncoder<File>),
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Output::Plain(file) => file.write(buf),
+            #[cfg(feature = "compression")]
+            Output::Gzip(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Output::Plain(file) => file.flush(),
+            #[cfg(feature = "compression")]
+            Output::Gzip(encoder) => encoder.flush(),
+        }
+    }
+}
+
+//creates a file for writing, compressed if the name ends in ".gz"
+pub fn create(filename: &str) -> io::Result<Output> {
+    if !filename.ends_with(".gz") {
+        return Ok(Output::Plain(File::create(filename)?));
+    }
+
+    #[cfg(feature = "compression")]
+    {
+        let file = File::create(filename)?;
+        Ok(Output::Gzip(flate2::write::GzEncoder::new(file, flate2::Compression::default())))
+    }
+    #[cfg(not(feature = "compression"))]
+    {
+        Err(unsupported(filename))
+    }
+}
+
+//flushes the buffer and ends the gzip stream, errors here mean the file is incomplete
+pub fn finish(writer: BufWriter<Output>) -> io::Result<()> {
+    match writer.into_inner().map_err(|error| error.into_error())? {
+        Output::Plain(_) => Ok(()),
+        #[cfg(feature = "compression")]
+        Output::Gzip(encoder) => encoder.finish().map(|_| ()),
+    }
+}
+
+#[cfg(not(feature = "compression"))]
+fn unsupported(filename: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("{} is gzip compressed, which needs the compression feature", filename),
+    )
+}
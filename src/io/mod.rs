@@ -0,0 +1,7 @@
+pub mod bin;
+pub mod obj;
+pub mod ply;
+pub mod text;
+pub mod gzip;
+pub mod batch;
+pub mod vtk;
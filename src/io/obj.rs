@@ -0,0 +1,385 @@
+use crate::errors::MeshParseError;
+use crate::io::gzip;
+use crate::mesh::double::MeshF64;
+use crate::mesh::types::Mesh;
+use nalgebra::{Point3, RealField};
+use pyo3::prelude::*;
+use std::ops::Range;
+use std::str::FromStr;
+
+//Wavefront OBJ loading.
+//The file is read whole and cut into line aligned chunks that are parsed independently, on
+//rayon threads with the `parallel` feature. Face indices can count back from the vertices
+//read so far, so chunks keep them as written along with their local vertex and normal counts,
+//and they are resolved once the offsets of all chunks are known. Both builds go through the
+//same chunks, so they load the same mesh and report the same first error.
+//`usemtl` statements work the same way: a face only knows the last statement of its own
+//chunk, faces before the first one take the group the previous chunks ended in.
+//Face groups are materials only, `g` statements are skipped: object groups and materials are
+//independent in OBJ, and save_obj writes the groups back as `usemtl`.
+//Coordinates are parsed straight into the scalar of the mesh being loaded, f32 for Mesh and
+//f64 for MeshF64, so double precision files never pass through f32.
+
+//chunks are cut at the first line end after this many bytes
+const CHUNK_BYTES: usize = 1 << 20;
+
+//a face line with its corners as written
+struct RawFace<'a> {
+    //line index within the chunk
+    line: usize,
+    text: &'a str,
+    //vertices and normals the chunk had read before this line
+    vertices_before: usize,
+    normals_before: usize,
+    //(vertex, normal) indices in the chunk's corner list
+    corners: Range<usize>,
+    //the token after the last corner isn't a valid face vertex
+    malformed: bool,
+    //index into the chunk's group names of the statement in effect, None before the first
+    group: Option<usize>,
+}
+
+struct Chunk<'a, T: RealField> {
+    //line ends in the chunk, i.e. where the next chunk starts in lines
+    newlines: usize,
+    vertices: Vec<Point3<T>>,
+    normals: Vec<Point3<T>>,
+    corners: Vec<(i64, Option<i64>)>,
+    faces: Vec<RawFace<'a>>,
+    //names from `usemtl` statements in order
+    groups: Vec<&'a str>,
+    //(line within the chunk, message) of the error that stopped parsing
+    error: Option<(usize, String)>,
+}
+
+//faces of a chunk with global indices
+struct Resolved {
+    faces: Vec<[usize; 3]>,
+    //(vertex, normal) pairs referenced through `v//vn` or `v/vt/vn`
+    normal_refs: Vec<(usize, usize)>,
+    //(line within the chunk, message) of the first bad face
+    error: Option<(usize, String)>,
+}
+
+//the whole file with global indices, before it is put into a mesh
+struct Parsed<T: RealField> {
+    vertices: Vec<Point3<T>>,
+    //`vn` statements in order
+    normals: Vec<Point3<T>>,
+    faces: Vec<[usize; 3]>,
+    normal_refs: Vec<(usize, usize)>,
+    face_groups: Option<Vec<u32>>,
+    group_names: Vec<String>,
+}
+
+//loads an .obj file, polygons are fan triangulated
+//reject_non_finite turns inf/nan coordinates into a MeshParseError instead of loading them
+pub fn load(filename: &str, reject_non_finite: bool) -> PyResult<Mesh> {
+    let parsed = parse::<f32>(filename, reject_non_finite)?;
+
+    let mut mesh = Mesh {
+        vertices: parsed.vertices,
+        faces: parsed.faces,
+        normals: None,
+        face_groups: parsed.face_groups,
+        group_names: parsed.group_names,
+        ..Default::default()
+    };
+
+    //normals referenced from faces win; otherwise fall back to one `vn` per `v` in order
+    if !parsed.normal_refs.is_empty() {
+        mesh.normals = Some(mesh.resolve_referenced_normals(&parsed.normals, parsed.normal_refs));
+    } else if !parsed.normals.is_empty() && parsed.normals.len() == mesh.vertices.len() {
+        mesh.normals = Some(parsed.normals);
+    }
+
+    Ok(mesh)
+}
+
+//load in double precision; MeshF64 only has vertices and faces, so normals and groups are
+//checked like for load but not kept
+pub fn load_f64(filename: &str, reject_non_finite: bool) -> PyResult<MeshF64> {
+    let parsed = parse::<f64>(filename, reject_non_finite)?;
+
+    Ok(MeshF64 {
+        vertices: parsed.vertices,
+        faces: parsed.faces,
+    })
+}
+
+fn parse<T: RealField + FromStr>(filename: &str, reject_non_finite: bool) -> PyResult<Parsed<T>> {
+    let text = gzip::read_to_string(filename)?;
+    let chunks = split_lines(&text, CHUNK_BYTES);
+
+    #[cfg(feature = "parallel")]
+    let parsed: Vec<Chunk<T>> = {
+        use rayon::prelude::*;
+        chunks.par_iter().map(|&chunk| parse_chunk(chunk, reject_non_finite)).collect()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let parsed: Vec<Chunk<T>> = chunks.iter().map(|&chunk| parse_chunk(chunk, reject_non_finite)).collect();
+
+    //where each chunk starts in lines, vertices and normals
+    let mut offsets = Vec::with_capacity(parsed.len());
+    let (mut lines, mut vertex_count, mut normal_count) = (0, 0, 0);
+    for chunk in &parsed {
+        offsets.push((lines, vertex_count, normal_count));
+        lines += chunk.newlines;
+        vertex_count += chunk.vertices.len();
+        normal_count += chunk.normals.len();
+    }
+
+    let resolve = |(chunk, &(_, vertices, normals)): (&Chunk<T>, &(usize, usize, usize))| {
+        resolve_chunk(chunk, vertices, normals)
+    };
+    #[cfg(feature = "parallel")]
+    let resolved: Vec<Resolved> = {
+        use rayon::prelude::*;
+        parsed.par_iter().zip(offsets.par_iter()).map(resolve).collect()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let resolved: Vec<Resolved> = parsed.iter().zip(offsets.iter()).map(resolve).collect();
+
+    //the first error in file order, a chunk stops at its first one so later chunks can't
+    //have an earlier line
+    for ((chunk, resolved), &(line_offset, _, _)) in parsed.iter().zip(&resolved).zip(&offsets) {
+        let first = [&chunk.error, &resolved.error].into_iter().flatten().min_by_key(|(line, _)| *line);
+        if let Some((line, message)) = first {
+            return Err(MeshParseError::new_err(format!(
+                "{}:{}: {}",
+                filename,
+                line_offset + line + 1,
+                message
+            )));
+        }
+    }
+
+    let (face_groups, group_names) = face_groups(&parsed);
+    let mut vertices = Vec::with_capacity(vertex_count);
+    let mut normals = Vec::with_capacity(normal_count);
+    for chunk in parsed {
+        vertices.extend(chunk.vertices);
+        normals.extend(chunk.normals);
+    }
+    let mut faces = Vec::new();
+    let mut normal_refs = Vec::new();
+    for chunk in resolved {
+        faces.extend(chunk.faces);
+        normal_refs.extend(chunk.normal_refs);
+    }
+
+    Ok(Parsed {
+        vertices,
+        normals,
+        faces,
+        normal_refs,
+        face_groups,
+        group_names,
+    })
+}
+
+//group id of every triangle and the names of the ids, numbered in order of first use
+//None when the file has no `usemtl` statement; faces before the first one are in
+//group "default"
+fn face_groups<T: RealField>(chunks: &[Chunk<T>]) -> (Option<Vec<u32>>, Vec<String>) {
+    use std::collections::HashMap;
+
+    if chunks.iter().all(|chunk| chunk.groups.is_empty()) {
+        return (None, Vec::new());
+    }
+
+    let mut ids: HashMap<&str, u32> = HashMap::new();
+    let mut names = Vec::new();
+    let mut groups = Vec::new();
+    let mut current = "default";
+    for chunk in chunks {
+        for face in &chunk.faces {
+            let name = face.group.map_or(current, |group| chunk.groups[group]);
+            let id = *ids.entry(name).or_insert_with(|| {
+                names.push(name.to_string());
+                names.len() as u32 - 1
+            });
+            //one per triangle of the fan
+            groups.extend(std::iter::repeat(id).take(face.corners.len().saturating_sub(2)));
+        }
+        current = chunk.groups.last().copied().unwrap_or(current);
+    }
+
+    (Some(groups), names)
+}
+
+//pieces of at least `size` bytes ending right after a line end, the last one takes the rest
+fn split_lines(text: &str, size: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = text;
+
+    while rest.len() > size {
+        match rest.as_bytes()[size..].iter().position(|&b| b == b'\n') {
+            Some(end) => {
+                let (chunk, tail) = rest.split_at(size + end + 1);
+                chunks.push(chunk);
+                rest = tail;
+            }
+            None => break,
+        }
+    }
+    chunks.push(rest);
+
+    chunks
+}
+
+fn parse_chunk<T: RealField + FromStr>(text: &str, reject_non_finite: bool) -> Chunk<'_, T> {
+    let mut chunk = Chunk {
+        newlines: text.bytes().filter(|&b| b == b'\n').count(),
+        vertices: Vec::new(),
+        normals: Vec::new(),
+        corners: Vec::new(),
+        faces: Vec::new(),
+        groups: Vec::new(),
+        error: None,
+    };
+
+    for (line_idx, line) in text.lines().enumerate() {
+        let mut parts = line.split_whitespace();
+        let keyword = parts.next();
+
+        if let Some("v" | "vn") = keyword {
+            let (x, y, z) = match (parts.next(), parts.next(), parts.next()) {
+                (Some(x), Some(y), Some(z)) => (x, y, z),
+                _ => continue,
+            };
+            let coordinate = |token: &str| token.parse::<T>().unwrap_or_else(|_| T::zero());
+            let point = Point3::new(coordinate(x), coordinate(y), coordinate(z));
+
+            if reject_non_finite && !point.coords.iter().all(|c| c.is_finite()) {
+                chunk.error = Some((line_idx, format!("non-finite coordinates in '{}'", line.trim())));
+                break;
+            }
+            if keyword == Some("v") {
+                chunk.vertices.push(point);
+            } else {
+                chunk.normals.push(point);
+            }
+        } else if keyword == Some("f") {
+            let start = chunk.corners.len();
+            let mut malformed = false;
+            for token in parts {
+                match parse_face_vertex(token) {
+                    Some((vertex, _, normal)) => chunk.corners.push((vertex, normal)),
+                    None => {
+                        malformed = true;
+                        break;
+                    }
+                }
+            }
+
+            chunk.faces.push(RawFace {
+                line: line_idx,
+                text: line,
+                vertices_before: chunk.vertices.len(),
+                normals_before: chunk.normals.len(),
+                corners: start..chunk.corners.len(),
+                malformed,
+                group: chunk.groups.len().checked_sub(1),
+            });
+        } else if keyword == Some("usemtl") {
+            let name = line.trim().split_once(char::is_whitespace).map_or("", |(_, name)| name.trim());
+            chunk.groups.push(if name.is_empty() { "default" } else { name });
+        }
+    }
+
+    chunk
+}
+
+//turns the chunk's faces into global indices, given how many vertices and normals come
+//before the chunk; stops at the first face with a bad reference
+fn resolve_chunk<T: RealField>(chunk: &Chunk<T>, vertex_offset: usize, normal_offset: usize) -> Resolved {
+    let mut resolved = Resolved {
+        faces: Vec::with_capacity(chunk.faces.len()),
+        normal_refs: Vec::new(),
+        error: None,
+    };
+    let mut polygon = Vec::new();
+
+    for face in &chunk.faces {
+        //the token a message is about, only looked up when there is an error
+        let token = |corner: usize| face.text.split_whitespace().nth(corner + 1).unwrap_or("");
+        let vertex_count = vertex_offset + face.vertices_before;
+        let normal_count = normal_offset + face.normals_before;
+
+        polygon.clear();
+        let mut error = None;
+        for (corner, &(vertex, normal)) in chunk.corners[face.corners.clone()].iter().enumerate() {
+            let index = match resolve_index(vertex, vertex_count) {
+                Some(index) => index,
+                None => {
+                    error = Some(format!("face vertex '{}' refers to a missing vertex", token(corner)));
+                    break;
+                }
+            };
+            polygon.push(index);
+
+            if let Some(normal) = normal {
+                match resolve_index(normal, normal_count) {
+                    Some(normal_index) => resolved.normal_refs.push((index, normal_index)),
+                    None => {
+                        error = Some(format!("face vertex '{}' refers to a missing normal", token(corner)));
+                        break;
+                    }
+                }
+            }
+        }
+
+        if error.is_none() && face.malformed {
+            error = Some(format!("malformed face vertex '{}'", token(face.corners.len())));
+        }
+        if error.is_none() && polygon.len() < 3 {
+            error = Some(format!("face needs at least 3 vertices, got {}", polygon.len()));
+        }
+        if let Some(message) = error {
+            resolved.error = Some((face.line, message));
+            break;
+        }
+
+        //polygons are fan triangulated around their first vertex
+        for i in 1..polygon.len() - 1 {
+            resolved.faces.push([polygon[0], polygon[i], polygon[i + 1]]);
+        }
+    }
+
+    resolved
+}
+
+//splits an OBJ face token `v`, `v/vt`, `v//vn` or `v/vt/vn` into its raw indices
+fn parse_face_vertex(token: &str) -> Option<(i64, Option<i64>, Option<i64>)> {
+    let mut fields = token.split('/');
+
+    let vertex = fields.next()?.parse::<i64>().ok()?;
+    let mut optional = || -> Option<Option<i64>> {
+        match fields.next() {
+            None | Some("") => Some(None),
+            Some(field) => field.parse::<i64>().ok().map(Some),
+        }
+    };
+    let texture = optional()?;
+    let normal = optional()?;
+
+    if fields.next().is_some() {
+        return None;
+    }
+
+    Some((vertex, texture, normal))
+}
+
+//turns a 1-based or negative (relative to the end) OBJ index into a 0-based one
+fn resolve_index(index: i64, count: usize) -> Option<usize> {
+    let count = count as i64;
+
+    if index > 0 && index <= count {
+        Some((index - 1) as usize)
+    } else if index < 0 && -index <= count {
+        Some((count + index) as usize)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,116 @@
+use crate::io::{gzip, text};
+use crate::mesh::types::Mesh;
+use byteorder::{LittleEndian, WriteBytesExt};
+use pyo3::prelude::*;
+use std::io::{BufWriter, Write};
+
+//Stanford PLY export.
+//Vertices and faces are encoded one at a time straight into a buffered file writer, so
+//peak memory stays at the size of the write buffer no matter how large the mesh is.
+
+const WRITE_BUFFER: usize = 1 << 20;
+
+pub fn save(mesh: &Mesh, filename: &str, binary: bool, precision: Option<usize>) -> PyResult<()> {
+    if mesh.vertices.len() > i32::MAX as usize {
+        return Err(crate::errors::MeshError::new_err(
+            "PLY vertex indices are 32-bit, mesh has too many vertices",
+        ));
+    }
+    if !binary {
+        text::check_finite(mesh)?;
+    }
+
+    let mut writer = BufWriter::with_capacity(WRITE_BUFFER, gzip::create(filename)?);
+
+    write_header(&mut writer, mesh, binary)?;
+    if binary {
+        write_binary_body(&mut writer, mesh)?;
+    } else {
+        write_ascii_body(&mut writer, mesh, precision)?;
+    }
+
+    gzip::finish(writer)?;
+    Ok(())
+}
+
+fn write_header<W: Write>(writer: &mut W, mesh: &Mesh, binary: bool) -> std::io::Result<()> {
+    writeln!(writer, "ply")?;
+    if binary {
+        writeln!(writer, "format binary_little_endian 1.0")?;
+    } else {
+        writeln!(writer, "format ascii 1.0")?;
+    }
+
+    writeln!(writer, "element vertex {}", mesh.vertices.len())?;
+    writeln!(writer, "property float x")?;
+    writeln!(writer, "property float y")?;
+    writeln!(writer, "property float z")?;
+    if mesh.normals.is_some() {
+        writeln!(writer, "property float nx")?;
+        writeln!(writer, "property float ny")?;
+        writeln!(writer, "property float nz")?;
+    }
+    if mesh.colors.is_some() {
+        writeln!(writer, "property uchar red")?;
+        writeln!(writer, "property uchar green")?;
+        writeln!(writer, "property uchar blue")?;
+    }
+
+    writeln!(writer, "element face {}", mesh.faces.len())?;
+    writeln!(writer, "property list uchar int vertex_indices")?;
+    writeln!(writer, "end_header")
+}
+
+fn write_binary_body<W: Write>(writer: &mut W, mesh: &Mesh) -> std::io::Result<()> {
+    for (i, vertex) in mesh.vertices.iter().enumerate() {
+        writer.write_f32::<LittleEndian>(vertex.x)?;
+        writer.write_f32::<LittleEndian>(vertex.y)?;
+        writer.write_f32::<LittleEndian>(vertex.z)?;
+
+        if let Some(normals) = &mesh.normals {
+            writer.write_f32::<LittleEndian>(normals[i].x)?;
+            writer.write_f32::<LittleEndian>(normals[i].y)?;
+            writer.write_f32::<LittleEndian>(normals[i].z)?;
+        }
+        if let Some(colors) = &mesh.colors {
+            writer.write_all(&color_bytes(&colors[i]))?;
+        }
+    }
+
+    for face in &mesh.faces {
+        writer.write_u8(3)?;
+        for &index in face {
+            writer.write_i32::<LittleEndian>(index as i32)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_ascii_body<W: Write>(writer: &mut W, mesh: &Mesh, precision: Option<usize>) -> std::io::Result<()> {
+    for (i, vertex) in mesh.vertices.iter().enumerate() {
+        match &mesh.normals {
+            Some(normals) => {
+                let n = normals[i];
+                text::write_floats(writer, &[vertex.x, vertex.y, vertex.z, n.x, n.y, n.z], precision)?
+            }
+            None => text::write_floats(writer, &[vertex.x, vertex.y, vertex.z], precision)?,
+        }
+        if let Some(colors) = &mesh.colors {
+            let [r, g, b] = color_bytes(&colors[i]);
+            write!(writer, " {} {} {}", r, g, b)?;
+        }
+        writeln!(writer)?;
+    }
+
+    for face in &mesh.faces {
+        writeln!(writer, "3 {} {} {}", face[0], face[1], face[2])?;
+    }
+
+    Ok(())
+}
+
+//rgb in [0, 1] as the 0..255 bytes PLY viewers expect
+fn color_bytes(color: &[f32; 3]) -> [u8; 3] {
+    color.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
@@ -0,0 +1,42 @@
+use crate::errors::MeshGeometryError;
+use crate::mesh::types::Mesh;
+use nalgebra::Point3;
+use pyo3::prelude::*;
+use std::fmt::Display;
+use std::io::Write;
+
+//Number formatting shared by the text formats.
+
+//space separated values with a fixed number of decimals, or without one the shortest
+//representation that reads back to the same f32 or f64
+pub fn write_floats<W: Write, T: Display>(writer: &mut W, values: &[T], precision: Option<usize>) -> std::io::Result<()> {
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            writer.write_all(b" ")?;
+        }
+        match precision {
+            Some(precision) => write!(writer, "{:.*}", precision, value)?,
+            None => write!(writer, "{}", value)?,
+        }
+    }
+
+    Ok(())
+}
+
+//inf and nan would be written as tokens most readers reject, so they are refused up front
+pub fn check_finite(mesh: &Mesh) -> PyResult<()> {
+    let finite = |points: &[Point3<f32>]| points.iter().all(|p| p.coords.iter().all(|c| c.is_finite()));
+
+    if !finite(&mesh.vertices) {
+        return Err(MeshGeometryError::new_err(
+            "mesh has inf or nan vertex coordinates, which text formats can't store; call sanitize() first",
+        ));
+    }
+    if mesh.normals.as_deref().is_some_and(|normals| !finite(normals)) {
+        return Err(MeshGeometryError::new_err(
+            "mesh has inf or nan normals, which text formats can't store; call compute_normals() first",
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use crate::io::{gzip, text};
+use crate::mesh::types::Mesh;
+use pyo3::prelude::*;
+use std::io::{BufWriter, Write};
+
+//Legacy ASCII VTK export as POLYDATA, the format ParaView and VisIt read without plugins.
+//Stored normals go out as point data; a scalar field is point data when there is one value
+//per vertex and cell data when there is one per face.
+
+//a named scalar per vertex or per face
+pub type Scalars<'a> = (&'a str, &'a [f32]);
+
+pub fn save(mesh: &Mesh, filename: &str, scalars: Option<Scalars>, precision: Option<usize>) -> PyResult<()> {
+    text::check_finite(mesh)?;
+    let (point_scalars, cell_scalars) = match scalars {
+        Some((name, values)) if check_scalars(mesh, name, values)? => (scalars, None),
+        Some(_) => (None, scalars),
+        None => (None, None),
+    };
+
+    let mut writer = BufWriter::new(gzip::create(filename)?);
+
+    writeln!(writer, "# vtk DataFile Version 3.0")?;
+    writeln!(writer, "meshalyzer mesh")?;
+    writeln!(writer, "ASCII")?;
+    writeln!(writer, "DATASET POLYDATA")?;
+
+    writeln!(writer, "POINTS {} float", mesh.vertices.len())?;
+    for v in &mesh.vertices {
+        text::write_floats(&mut writer, &[v.x, v.y, v.z], precision)?;
+        writeln!(writer)?;
+    }
+
+    //each cell is its vertex count followed by the indices
+    writeln!(writer, "POLYGONS {} {}", mesh.faces.len(), mesh.faces.len() * 4)?;
+    for face in &mesh.faces {
+        writeln!(writer, "3 {} {} {}", face[0], face[1], face[2])?;
+    }
+
+    if mesh.normals.is_some() || point_scalars.is_some() {
+        writeln!(writer, "POINT_DATA {}", mesh.vertices.len())?;
+    }
+    if let Some(normals) = &mesh.normals {
+        writeln!(writer, "NORMALS normals float")?;
+        for n in normals {
+            text::write_floats(&mut writer, &[n.x, n.y, n.z], precision)?;
+            writeln!(writer)?;
+        }
+    }
+    if let Some((name, values)) = point_scalars {
+        write_scalars(&mut writer, name, values, precision)?;
+    }
+
+    if let Some((name, values)) = cell_scalars {
+        writeln!(writer, "CELL_DATA {}", mesh.faces.len())?;
+        write_scalars(&mut writer, name, values, precision)?;
+    }
+
+    gzip::finish(writer)?;
+    Ok(())
+}
+
+//whether the values are per vertex (true) or per face (false), per vertex if both fit
+fn check_scalars(mesh: &Mesh, name: &str, values: &[f32]) -> PyResult<bool> {
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "scalar name '{}' must be a single word",
+            name
+        )));
+    }
+    if !values.iter().all(|v| v.is_finite()) {
+        return Err(pyo3::exceptions::PyValueError::new_err("values must be finite"));
+    }
+
+    if values.len() == mesh.vertices.len() {
+        Ok(true)
+    } else if values.len() == mesh.faces.len() {
+        Ok(false)
+    } else {
+        Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "got {} values for {} vertices and {} faces",
+            values.len(),
+            mesh.vertices.len(),
+            mesh.faces.len()
+        )))
+    }
+}
+
+fn write_scalars<W: Write>(writer: &mut W, name: &str, values: &[f32], precision: Option<usize>) -> std::io::Result<()> {
+    writeln!(writer, "SCALARS {} float 1", name)?;
+    writeln!(writer, "LOOKUP_TABLE default")?;
+    for value in values {
+        text::write_floats(writer, &[*value], precision)?;
+        writeln!(writer)?;
+    }
+    Ok(())
+}
@@ -1,14 +1,29 @@
 use pyo3::prelude::*;
+mod accel;
+mod errors;
 mod mesh;
 mod analyzers;
+mod io;
+mod ops;
+mod progress;
 
-use mesh::types::Mesh;
+use errors::{MeshError, MeshGeometryError, MeshParseError, MeshTopologyError};
+use mesh::double::MeshF64;
+use mesh::types::{Mesh, TriangleIter};
 use analyzers::topology::PyTopologyAnalyzer;
 
 #[pymodule]
-fn meshalyzer(_py: Python, m: &PyModule) -> PyResult<()> {
+fn meshalyzer(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<Mesh>()?;
+    m.add_class::<MeshF64>()?;
+    m.add_class::<TriangleIter>()?;
     m.add_class::<PyTopologyAnalyzer>()?;
+    m.add_function(wrap_pyfunction!(io::batch::load_dir, m)?)?;
+    m.add_function(wrap_pyfunction!(mesh::types::union_bounds, m)?)?;
+    m.add("MeshError", py.get_type::<MeshError>())?;
+    m.add("MeshParseError", py.get_type::<MeshParseError>())?;
+    m.add("MeshTopologyError", py.get_type::<MeshTopologyError>())?;
+    m.add("MeshGeometryError", py.get_type::<MeshGeometryError>())?;
     Ok(())
 }
 
@@ -0,0 +1,40 @@
+//Colormaps from [0, 1] to rgb in [0, 1].
+
+pub type Colormap = fn(f32) -> [f32; 3];
+
+pub fn by_name(name: &str) -> Option<Colormap> {
+    match name {
+        "viridis" => Some(viridis),
+        "jet" => Some(jet),
+        "grayscale" => Some(grayscale),
+        _ => None,
+    }
+}
+
+//degree 6 polynomial fit of matplotlib's viridis table
+fn viridis(t: f32) -> [f32; 3] {
+    const COEFFICIENTS: [[f32; 3]; 7] = [
+        [0.277_727_33, 0.005_407_344_5, 0.334_099_8],
+        [0.105_093_04, 1.404_613_5, 1.384_590_2],
+        [-0.330_861_83, 0.214_847_56, 0.095_095_16],
+        [-4.634_230_5, -5.799_101, -19.332_441],
+        [6.228_27, 14.179_933, 56.690_55],
+        [4.776_385, -13.745_146, -65.353_03],
+        [-5.435_456, 4.645_852_6, 26.312_435],
+    ];
+
+    [0, 1, 2].map(|channel| {
+        let value = COEFFICIENTS.iter().rev().fold(0.0, |acc, c| acc * t + c[channel]);
+        value.clamp(0.0, 1.0)
+    })
+}
+
+//blue through cyan, yellow and red, piecewise linear like MATLAB's jet
+fn jet(t: f32) -> [f32; 3] {
+    let ramp = |center: f32| (1.5 - (4.0 * t - center).abs()).clamp(0.0, 1.0);
+    [ramp(3.0), ramp(2.0), ramp(1.0)]
+}
+
+fn grayscale(t: f32) -> [f32; 3] {
+    [t, t, t]
+}
@@ -0,0 +1,56 @@
+use crate::mesh::types::Mesh;
+use nalgebra::Vector3;
+use std::collections::HashMap;
+
+//Angle weighted pseudonormals (Baerentzen & Aanaes 2005) for signing distances to a closed
+//surface. The pseudonormal of the feature closest to a point (face, edge or vertex) always
+//has a positive dot product with the offset to an outside point, even where the surface
+//folds back on itself so tightly that a parity ray would be unreliable.
+
+pub struct PseudoNormals {
+    faces: Vec<Vector3<f32>>,
+    //sum of the normals of the two faces on each edge, keyed by (min vertex, max vertex)
+    edges: HashMap<(usize, usize), Vector3<f32>>,
+    //face normals weighted by the face's angle at the vertex
+    vertices: Vec<Vector3<f32>>,
+}
+
+impl PseudoNormals {
+    pub fn new(mesh: &Mesh) -> Self {
+        let faces: Vec<Vector3<f32>> = (0..mesh.faces.len()).map(|f| mesh.face_normal(f)).collect();
+        let mut edges: HashMap<(usize, usize), Vector3<f32>> = HashMap::new();
+        let mut vertices = vec![Vector3::zeros(); mesh.vertices.len()];
+
+        for (face_idx, face) in mesh.faces.iter().enumerate() {
+            let normal = faces[face_idx];
+            for i in 0..3 {
+                let (corner, a, b) = (face[i], face[(i + 1) % 3], face[(i + 2) % 3]);
+                let u = mesh.vertices[a] - mesh.vertices[corner];
+                let v = mesh.vertices[b] - mesh.vertices[corner];
+
+                vertices[corner] += normal * u.angle(&v);
+                *edges.entry((corner.min(a), corner.max(a))).or_default() += normal;
+            }
+        }
+
+        PseudoNormals { faces, edges, vertices }
+    }
+
+    //pseudonormal at a point of the face given by barycentric coordinates: the face normal
+    //inside, the edge normal on an edge and the vertex normal on a corner
+    pub fn at(&self, mesh: &Mesh, face_idx: usize, bary: &[f32; 3]) -> Vector3<f32> {
+        let face = mesh.faces[face_idx];
+        let zeros: Vec<usize> = (0..3).filter(|&i| bary[i] == 0.0).collect();
+
+        match zeros.as_slice() {
+            //on the edge opposite the corner whose weight vanished
+            [i] => {
+                let (a, b) = (face[(i + 1) % 3], face[(i + 2) % 3]);
+                self.edges[&(a.min(b), a.max(b))]
+            }
+            //on the corner with the only weight left
+            [i, j] => self.vertices[face[3 - i - j]],
+            _ => self.faces[face_idx],
+        }
+    }
+}
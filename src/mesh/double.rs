@@ -0,0 +1,187 @@
+use crate::analyzers::topology::Topology;
+use crate::errors::MeshGeometryError;
+use crate::io::{gzip, obj, text};
+use crate::mesh::measure;
+use crate::mesh::transform::Affine;
+use crate::mesh::types::Mesh;
+use nalgebra::{Point3, Vector3};
+use pyo3::prelude::*;
+
+//Double precision counterpart of Mesh for large coordinates, e.g. UTM or geocentric data,
+//where f32 rounds away meters. Measurements and transforms run the same generic code as
+//Mesh (see measure and transform); to_f32(origin) hands the data to Mesh near the origin,
+//where f32 keeps its detail.
+
+#[pyclass]
+#[derive(Clone, Default)]
+pub struct MeshF64 {
+    pub vertices: Vec<Point3<f64>>,
+    pub faces: Vec<[usize; 3]>,
+}
+
+#[pymethods]
+impl MeshF64 {
+    #[new]
+    fn new(vertices: Vec<[f64; 3]>, faces: Vec<[usize; 3]>) -> Self {
+        MeshF64 {
+            vertices: vertices.into_iter().map(Point3::from).collect(),
+            faces,
+        }
+    }
+
+    #[getter]
+    fn vertices(&self) -> Vec<[f64; 3]> {
+        self.vertices.iter().map(|v| [v.x, v.y, v.z]).collect()
+    }
+
+    #[getter]
+    fn faces(&self) -> Vec<[usize; 3]> {
+        self.faces.clone()
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn face_count(&self) -> usize {
+        self.faces.len()
+    }
+
+    //len(mesh) is the face count
+    fn __len__(&self) -> usize {
+        self.faces.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("MeshF64(vertices={}, faces={})", self.vertices.len(), self.faces.len())
+    }
+
+    //returns bounding box of mesh, zeros for an empty mesh
+    fn compute_bounds(&self) -> ([f64; 3], [f64; 3]) {
+        measure::bounds(&self.vertices)
+    }
+
+    //bounding box diagonal, zero for an empty mesh
+    fn characteristic_length(&self) -> f64 {
+        measure::diagonal(self.compute_bounds())
+    }
+
+    //mean of the vertex positions
+    fn centroid(&self) -> [f64; 3] {
+        measure::centroid(&self.vertices)
+    }
+
+    fn compute_surface_area(&self) -> f64 {
+        measure::surface_area(&self.vertices, &self.faces)
+    }
+
+    //volume enclosed by the faces, positive for outward winding
+    //only meaningful for watertight meshes
+    fn compute_volume(&self) -> f64 {
+        measure::volume(&self.vertices, &self.faces)
+    }
+
+    //area of every face
+    fn face_areas(&self) -> Vec<f64> {
+        self.faces.iter().map(|face| measure::face_area(&self.vertices, face)).collect()
+    }
+
+    //unit normal of every face from its winding, zero for degenerate faces
+    fn compute_face_normals(&self) -> Vec<[f64; 3]> {
+        self.faces
+            .iter()
+            .map(|face| measure::face_normal(&self.vertices, face).into())
+            .collect()
+    }
+
+    //every edge is shared by exactly two faces
+    fn is_watertight(&self) -> bool {
+        Topology::new(&self.faces).is_watertight()
+    }
+
+    //generalized winding number of the surface around the point, about 1 inside and 0 outside
+    fn winding_number(&self, point: [f64; 3]) -> f64 {
+        measure::winding_number(&self.vertices, &self.faces, &Point3::from(point))
+    }
+
+    //inside if the winding number is above threshold, see Mesh.contains_point_robust
+    #[pyo3(signature = (point, threshold=0.5))]
+    fn contains_point_robust(&self, point: [f64; 3], threshold: f64) -> bool {
+        self.winding_number(point) > threshold
+    }
+
+    fn scaled(&self, scale_factor: f64) -> MeshF64 {
+        MeshF64 {
+            vertices: self.vertices.iter().map(|v| v * scale_factor).collect(),
+            faces: self.faces.clone(),
+        }
+    }
+
+    fn translated(&self, dx: f64, dy: f64, dz: f64) -> MeshF64 {
+        let offset = Vector3::new(dx, dy, dz);
+
+        MeshF64 {
+            vertices: self.vertices.iter().map(|v| v + offset).collect(),
+            faces: self.faces.clone(),
+        }
+    }
+
+    //applies a row-major affine 4x4 matrix in place
+    fn transform(&mut self, matrix: [[f64; 4]; 4]) -> PyResult<()> {
+        let affine = Affine::from_rows(matrix)?;
+        for vertex in &mut self.vertices {
+            *vertex = Point3::from(affine.apply(vertex.coords));
+        }
+
+        Ok(())
+    }
+
+    //single precision copy, optionally moved by -origin first so coordinates far from the
+    //origin keep their detail relative to each other
+    #[pyo3(signature = (origin=None))]
+    fn to_f32(&self, origin: Option<[f64; 3]>) -> Mesh {
+        let origin = Vector3::from(origin.unwrap_or([0.0; 3]));
+
+        Mesh {
+            vertices: self.vertices.iter().map(|v| Point3::from((v - origin).coords.cast::<f32>())).collect(),
+            faces: self.faces.clone(),
+            normals: None,
+            ..Default::default()
+        }
+    }
+
+    //saves mesh to .obj file, precision fixes the number of decimals written for each coordinate,
+    //by default every value is written with as many digits as it takes to read back exactly
+    #[pyo3(signature = (filename, precision=None))]
+    fn save_obj(&self, filename: &str, precision: Option<usize>) -> PyResult<()> {
+        use std::io::{BufWriter, Write};
+
+        if !self.vertices.iter().all(|v| v.coords.iter().all(|c| c.is_finite())) {
+            return Err(MeshGeometryError::new_err(
+                "mesh has inf or nan vertex coordinates, which text formats can't store",
+            ));
+        }
+
+        let mut writer = BufWriter::new(gzip::create(filename)?);
+
+        for v in &self.vertices {
+            write!(writer, "v ")?;
+            text::write_floats(&mut writer, &[v.x, v.y, v.z], precision)?;
+            writeln!(writer)?;
+        }
+        for face in &self.faces {
+            writeln!(writer, "f {} {} {}", face[0] + 1, face[1] + 1, face[2] + 1)?;
+        }
+
+        gzip::finish(writer)?;
+        Ok(())
+    }
+
+    //loads the vertices and faces of an .obj file in double precision, read the same way as
+    //Mesh.from_obj; normals, texture coordinates and groups are skipped
+    #[staticmethod]
+    #[pyo3(signature = (filename, reject_non_finite=false))]
+    fn from_obj(filename: &str, reject_non_finite: bool) -> PyResult<MeshF64> {
+        obj::load_f64(filename, reject_non_finite)
+    }
+}
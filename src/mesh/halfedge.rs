@@ -0,0 +1,272 @@
+use crate::errors::MeshTopologyError;
+use crate::mesh::types::Mesh;
+use nalgebra::{Point3, Vector3};
+use pyo3::PyResult;
+use std::collections::HashMap;
+
+//one directed edge of a face, pointing at `vertex`
+#[derive(Clone, Copy, Debug)]
+pub struct HalfEdge {
+    pub vertex: usize,
+    pub next: usize,
+    pub prev: usize,
+    pub twin: Option<usize>,
+}
+
+//half-edge view of a triangle mesh for O(1) neighbourhood traversal
+//face f owns half-edges 3f, 3f+1 and 3f+2, in the face's winding order
+pub struct HalfEdgeMesh {
+    pub vertices: Vec<Point3<f32>>,
+    pub half_edges: Vec<HalfEdge>,
+    vertex_half_edge: Vec<Option<usize>>,
+}
+
+impl HalfEdgeMesh {
+    //raises MeshTopologyError if two faces use the same edge in the same direction, i.e. the
+    //mesh is non-manifold there or inconsistently oriented, as that half-edge can't have one twin
+    pub fn from_mesh(mesh: &Mesh) -> PyResult<Self> {
+        HalfEdgeMesh::from_faces(mesh.vertices.clone(), &mesh.faces)
+    }
+
+    fn from_faces(vertices: Vec<Point3<f32>>, faces: &[[usize; 3]]) -> PyResult<Self> {
+        let mut half_edges = Vec::with_capacity(faces.len() * 3);
+        let mut directed: HashMap<(usize, usize), usize> = HashMap::new();
+
+        for (face_idx, face) in faces.iter().enumerate() {
+            let base = face_idx * 3;
+
+            for i in 0..3 {
+                let from = face[i];
+                let to = face[(i + 1) % 3];
+
+                half_edges.push(HalfEdge {
+                    vertex: to,
+                    next: base + (i + 1) % 3,
+                    prev: base + (i + 2) % 3,
+                    twin: None,
+                });
+
+                if let Some(other) = directed.insert((from, to), base + i) {
+                    return Err(MeshTopologyError::new_err(format!(
+                        "faces {} and {} both use edge ({}, {}) in the same direction",
+                        other / 3,
+                        face_idx,
+                        from,
+                        to
+                    )));
+                }
+            }
+        }
+
+        for (&(from, to), &he) in &directed {
+            if let Some(&twin) = directed.get(&(to, from)) {
+                half_edges[he].twin = Some(twin);
+            }
+        }
+
+        //boundary vertices keep their boundary outgoing half-edge so a ring walk covers the whole fan
+        let mut vertex_half_edge: Vec<Option<usize>> = vec![None; vertices.len()];
+        for (he_idx, he) in half_edges.iter().enumerate() {
+            let origin = half_edges[he.prev].vertex;
+            match vertex_half_edge[origin] {
+                None => vertex_half_edge[origin] = Some(he_idx),
+                Some(current) if half_edges[current].twin.is_some() && he.twin.is_none() => {
+                    vertex_half_edge[origin] = Some(he_idx);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(HalfEdgeMesh {
+            vertices,
+            half_edges,
+            vertex_half_edge,
+        })
+    }
+
+    //back to the indexed form, faces keep their order and winding
+    pub fn to_mesh(&self) -> Mesh {
+        let faces = (0..self.face_count())
+            .map(|f| {
+                let he = self.face_half_edge(f);
+                [
+                    self.origin(he),
+                    self.origin(self.next(he)),
+                    self.origin(self.prev(he)),
+                ]
+            })
+            .collect();
+
+        Mesh {
+            vertices: self.vertices.clone(),
+            faces,
+            ..Default::default()
+        }
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub fn face_count(&self) -> usize {
+        self.half_edges.len() / 3
+    }
+
+    pub fn next(&self, he: usize) -> usize {
+        self.half_edges[he].next
+    }
+
+    pub fn prev(&self, he: usize) -> usize {
+        self.half_edges[he].prev
+    }
+
+    pub fn twin(&self, he: usize) -> Option<usize> {
+        self.half_edges[he].twin
+    }
+
+    //vertex the half-edge points to
+    pub fn target(&self, he: usize) -> usize {
+        self.half_edges[he].vertex
+    }
+
+    //vertex the half-edge starts from
+    pub fn origin(&self, he: usize) -> usize {
+        self.half_edges[self.half_edges[he].prev].vertex
+    }
+
+    pub fn face_half_edge(&self, face: usize) -> usize {
+        face * 3
+    }
+
+    pub fn is_boundary_half_edge(&self, he: usize) -> bool {
+        self.half_edges[he].twin.is_none()
+    }
+
+    pub fn is_boundary_vertex(&self, vertex: usize) -> bool {
+        match self.vertex_half_edge[vertex] {
+            Some(he) => self.is_boundary_half_edge(he),
+            None => false,
+        }
+    }
+
+    //outgoing half-edges around a vertex, walking from face to face across shared edges
+    pub fn outgoing(&self, vertex: usize) -> OutgoingHalfEdges<'_> {
+        let start = self.vertex_half_edge[vertex];
+        OutgoingHalfEdges {
+            mesh: self,
+            start,
+            current: start,
+        }
+    }
+
+    //1-ring of a vertex, on boundaries this includes both boundary neighbours
+    pub fn vertex_neighbors(&self, vertex: usize) -> Vec<usize> {
+        let mut neighbors = Vec::new();
+        let mut last = None;
+
+        for he in self.outgoing(vertex) {
+            neighbors.push(self.target(he));
+            last = Some(he);
+        }
+
+        if let Some(he) = last {
+            let incoming = self.prev(he);
+            if self.is_boundary_half_edge(incoming) {
+                neighbors.push(self.origin(incoming));
+            }
+        }
+
+        neighbors
+    }
+
+    //one step of Loop subdivision: every face becomes four, faces 4f..4f+4 replace face f
+    //the old vertices keep their indices and move to the weighted average of their 1-ring,
+    //one new vertex per edge follows them, placed by the edge's two faces (its midpoint on
+    //boundary edges); boundaries are smoothed as curves so they stay put
+    pub fn loop_subdivision(&self) -> LoopSubdivision {
+        let mut vertices: Vec<Point3<f32>> = (0..self.vertex_count())
+            .map(|v| {
+                let ring = self.vertex_neighbors(v);
+                let sum = |ring: &[usize]| ring.iter().fold(Vector3::zeros(), |acc, &n| acc + self.vertices[n].coords);
+
+                if self.is_boundary_vertex(v) {
+                    let ends = [ring[0], ring[ring.len() - 1]];
+                    Point3::from(self.vertices[v].coords * 0.75 + sum(&ends) * 0.125)
+                } else if ring.is_empty() {
+                    self.vertices[v]
+                } else {
+                    let n = ring.len() as f32;
+                    let beta = if ring.len() == 3 { 3.0 / 16.0 } else { 3.0 / (8.0 * n) };
+                    Point3::from(self.vertices[v].coords * (1.0 - n * beta) + sum(&ring) * beta)
+                }
+            })
+            .collect();
+
+        //new vertex of every half-edge, shared with its twin
+        let mut edge_vertex = vec![usize::MAX; self.half_edges.len()];
+        let mut edge_ends = Vec::new();
+        for he in 0..self.half_edges.len() {
+            if edge_vertex[he] != usize::MAX {
+                continue;
+            }
+
+            let (a, b) = (self.origin(he), self.target(he));
+            let ends = self.vertices[a].coords + self.vertices[b].coords;
+            let position = match self.twin(he) {
+                Some(twin) => {
+                    let opposite = self.vertices[self.target(self.next(he))].coords
+                        + self.vertices[self.target(self.next(twin))].coords;
+                    edge_vertex[twin] = vertices.len();
+                    ends * 0.375 + opposite * 0.125
+                }
+                None => ends * 0.5,
+            };
+            edge_vertex[he] = vertices.len();
+            vertices.push(Point3::from(position));
+            edge_ends.push((a, b));
+        }
+
+        let faces: Vec<[usize; 3]> = (0..self.face_count())
+            .flat_map(|f| {
+                let h = [0, 1, 2].map(|i| self.face_half_edge(f) + i);
+                let [v0, v1, v2] = h.map(|he| self.origin(he));
+                let [m0, m1, m2] = h.map(|he| edge_vertex[he]);
+                [[v0, m0, m2], [v1, m1, m0], [v2, m2, m1], [m0, m1, m2]]
+            })
+            .collect();
+
+        //every new edge is half of an old one in its direction or lies inside one old face,
+        //so no two faces can share a directed edge
+        let mesh = HalfEdgeMesh::from_faces(vertices, &faces)
+            .expect("subdivision keeps directed edges unique");
+
+        LoopSubdivision { mesh, edge_ends }
+    }
+}
+
+pub struct LoopSubdivision {
+    pub mesh: HalfEdgeMesh,
+    //(a, b) of the edge every new vertex was made for, in the order they follow the old ones
+    pub edge_ends: Vec<(usize, usize)>,
+}
+
+pub struct OutgoingHalfEdges<'a> {
+    mesh: &'a HalfEdgeMesh,
+    start: Option<usize>,
+    current: Option<usize>,
+}
+
+impl<'a> Iterator for OutgoingHalfEdges<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let he = self.current?;
+
+        self.current = self
+            .mesh
+            .twin(self.mesh.prev(he))
+            .filter(|&next| Some(next) != self.start);
+
+        Some(he)
+    }
+}
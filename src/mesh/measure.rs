@@ -0,0 +1,83 @@
+use nalgebra::{Point3, RealField, Vector3};
+
+//Measurements generic over the scalar, shared by the single and double precision meshes.
+
+//bounding box from all vertices, zeros for an empty mesh
+pub fn bounds<T: RealField + Copy>(vertices: &[Point3<T>]) -> ([T; 3], [T; 3]) {
+    let first = match vertices.first() {
+        Some(first) => [first.x, first.y, first.z],
+        None => return ([T::zero(); 3], [T::zero(); 3]),
+    };
+
+    let (mut min, mut max) = (first, first);
+    for vertex in vertices {
+        for i in 0..3 {
+            min[i] = min[i].min(vertex[i]);
+            max[i] = max[i].max(vertex[i]);
+        }
+    }
+
+    (min, max)
+}
+
+pub fn face_area<T: RealField + Copy>(vertices: &[Point3<T>], face: &[usize; 3]) -> T {
+    let edge1 = vertices[face[1]] - vertices[face[0]];
+    let edge2 = vertices[face[2]] - vertices[face[0]];
+
+    edge1.cross(&edge2).norm() * nalgebra::convert(0.5)
+}
+
+pub fn surface_area<T: RealField + Copy>(vertices: &[Point3<T>], faces: &[[usize; 3]]) -> T {
+    faces.iter().fold(T::zero(), |sum, face| sum + face_area(vertices, face))
+}
+
+//unit normal from the face's winding, zero for degenerate faces
+pub fn face_normal<T: RealField + Copy>(vertices: &[Point3<T>], face: &[usize; 3]) -> Vector3<T> {
+    let edge1 = vertices[face[1]] - vertices[face[0]];
+    let edge2 = vertices[face[2]] - vertices[face[0]];
+
+    edge1.cross(&edge2).try_normalize(T::zero()).unwrap_or_else(Vector3::zeros)
+}
+
+//mean of the vertex positions, zeros for an empty mesh
+pub fn centroid<T: RealField + Copy>(vertices: &[Point3<T>]) -> [T; 3] {
+    if vertices.is_empty() {
+        return [T::zero(); 3];
+    }
+
+    let sum = vertices.iter().fold(Vector3::zeros(), |sum, v| sum + v.coords);
+    let mean = sum / nalgebra::convert::<f64, T>(vertices.len() as f64);
+
+    [mean.x, mean.y, mean.z]
+}
+
+//length of the bounding box diagonal, zero for an empty mesh
+pub fn diagonal<T: RealField + Copy>((min, max): ([T; 3], [T; 3])) -> T {
+    (Vector3::from(max) - Vector3::from(min)).norm()
+}
+
+//volume enclosed by the faces, positive for outward winding
+pub fn volume<T: RealField + Copy>(vertices: &[Point3<T>], faces: &[[usize; 3]]) -> T {
+    let sum = faces.iter().fold(T::zero(), |sum, face| {
+        sum + vertices[face[0]].coords.dot(&vertices[face[1]].coords.cross(&vertices[face[2]].coords))
+    });
+
+    sum / nalgebra::convert(6.0)
+}
+
+//generalized winding number of the faces around point (Jacobson et al. 2013), the sum of the
+//signed solid angles of the triangles over 4π: 1 inside and 0 outside a closed outward surface,
+//and close to that near small holes where ray parity breaks down
+pub fn winding_number<T: RealField + Copy>(vertices: &[Point3<T>], faces: &[[usize; 3]], point: &Point3<T>) -> T {
+    let sum = faces.iter().fold(T::zero(), |sum, face| {
+        let [a, b, c] = face.map(|v| vertices[v] - point);
+        let (la, lb, lc) = (a.norm(), b.norm(), c.norm());
+
+        //Van Oosterom and Strackee's formula for half the solid angle
+        let numerator = a.dot(&b.cross(&c));
+        let denominator = la * lb * lc + a.dot(&b) * lc + b.dot(&c) * la + c.dot(&a) * lb;
+        sum + numerator.atan2(denominator)
+    });
+
+    sum / T::two_pi()
+}
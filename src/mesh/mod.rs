@@ -0,0 +1,10 @@
+pub mod types;
+pub mod halfedge;
+pub mod sampling;
+pub mod distance;
+pub mod measure;
+pub mod transform;
+pub mod double;
+pub mod primitives;
+pub mod color;
+pub mod uv;
@@ -0,0 +1,129 @@
+use crate::mesh::types::Mesh;
+use nalgebra::Point3;
+use std::collections::HashMap;
+use std::f32::consts::TAU;
+
+//Parametric primitives centered at the origin, all faces wound counterclockwise seen from
+//outside. The closed ones are watertight: vertices are shared between faces instead of being
+//duplicated per face, so seams and caps are welded.
+
+fn mesh(vertices: Vec<Point3<f32>>, faces: Vec<[usize; 3]>) -> Mesh {
+    Mesh {
+        vertices,
+        faces,
+        normals: None,
+        ..Default::default()
+    }
+}
+
+//axis aligned cube with the given edge length
+pub fn cube(size: f32) -> Mesh {
+    let h = size * 0.5;
+    //vertex i has bit 0 set for +x, bit 1 for +y, bit 2 for +z
+    let vertices = (0..8)
+        .map(|i| {
+            let side = |bit: usize| if i & bit != 0 { h } else { -h };
+            Point3::new(side(1), side(2), side(4))
+        })
+        .collect();
+    let faces = vec![
+        [0, 2, 3], [0, 3, 1], //-z
+        [4, 5, 7], [4, 7, 6], //+z
+        [0, 1, 5], [0, 5, 4], //-y
+        [2, 6, 7], [2, 7, 3], //+y
+        [0, 4, 6], [0, 6, 2], //-x
+        [1, 3, 7], [1, 7, 5], //+x
+    ];
+
+    mesh(vertices, faces)
+}
+
+//icosahedron refined 1-to-4 `subdivisions` times, new vertices at edge midpoints pushed out
+//onto the sphere; 20 * 4^subdivisions faces
+pub fn icosphere(radius: f32, subdivisions: u32) -> Mesh {
+    let t = (1.0 + 5f32.sqrt()) * 0.5;
+    let mut vertices: Vec<Point3<f32>> = [
+        [-1.0, t, 0.0], [1.0, t, 0.0], [-1.0, -t, 0.0], [1.0, -t, 0.0],
+        [0.0, -1.0, t], [0.0, 1.0, t], [0.0, -1.0, -t], [0.0, 1.0, -t],
+        [t, 0.0, -1.0], [t, 0.0, 1.0], [-t, 0.0, -1.0], [-t, 0.0, 1.0],
+    ]
+    .iter()
+    .map(|&p| Point3::from(Point3::from(p).coords.normalize() * radius))
+    .collect();
+    let mut faces = vec![
+        [0, 11, 5], [0, 5, 1], [0, 1, 7], [0, 7, 10], [0, 10, 11],
+        [1, 5, 9], [5, 11, 4], [11, 10, 2], [10, 7, 6], [7, 1, 8],
+        [3, 9, 4], [3, 4, 2], [3, 2, 6], [3, 6, 8], [3, 8, 9],
+        [4, 9, 5], [2, 4, 11], [6, 2, 10], [8, 6, 7], [9, 8, 1],
+    ];
+
+    for _ in 0..subdivisions {
+        //midpoint vertex of each edge, shared by the two faces on it
+        let mut midpoints: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut midpoint = |a: usize, b: usize, vertices: &mut Vec<Point3<f32>>| {
+            *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                let middle = (vertices[a].coords + vertices[b].coords).normalize() * radius;
+                vertices.push(Point3::from(middle));
+                vertices.len() - 1
+            })
+        };
+
+        let mut refined = Vec::with_capacity(faces.len() * 4);
+        for [a, b, c] in faces {
+            let ab = midpoint(a, b, &mut vertices);
+            let bc = midpoint(b, c, &mut vertices);
+            let ca = midpoint(c, a, &mut vertices);
+            refined.extend([[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]);
+        }
+        faces = refined;
+    }
+
+    mesh(vertices, faces)
+}
+
+//closed cylinder along z with `segments` sides, the caps are fans around a center vertex
+pub fn cylinder(radius: f32, height: f32, segments: usize) -> Mesh {
+    let h = height * 0.5;
+    let mut vertices = Vec::with_capacity(2 * segments + 2);
+    for z in [-h, h] {
+        for i in 0..segments {
+            let angle = TAU * i as f32 / segments as f32;
+            vertices.push(Point3::new(radius * angle.cos(), radius * angle.sin(), z));
+        }
+    }
+    let (bottom, top) = (2 * segments, 2 * segments + 1);
+    vertices.push(Point3::new(0.0, 0.0, -h));
+    vertices.push(Point3::new(0.0, 0.0, h));
+
+    let mut faces = Vec::with_capacity(4 * segments);
+    for i in 0..segments {
+        let next = (i + 1) % segments;
+        let (b0, b1, t0, t1) = (i, next, segments + i, segments + next);
+        faces.extend([[b0, b1, t1], [b0, t1, t0], [bottom, b1, b0], [top, t0, t1]]);
+    }
+
+    mesh(vertices, faces)
+}
+
+//open grid in the xy plane facing +z, `divisions` quads along each side
+pub fn plane(width: f32, height: f32, divisions: usize) -> Mesh {
+    let row = divisions + 1;
+    let mut vertices = Vec::with_capacity(row * row);
+    for j in 0..row {
+        for i in 0..row {
+            let (u, v) = (i as f32 / divisions as f32, j as f32 / divisions as f32);
+            vertices.push(Point3::new((u - 0.5) * width, (v - 0.5) * height, 0.0));
+        }
+    }
+
+    let mut faces = Vec::with_capacity(2 * divisions * divisions);
+    for j in 0..divisions {
+        for i in 0..divisions {
+            let corner = j * row + i;
+            let (right, up) = (corner + 1, corner + row);
+            faces.extend([[corner, right, up + 1], [corner, up + 1, up]]);
+        }
+    }
+
+    mesh(vertices, faces)
+}
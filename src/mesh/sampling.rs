@@ -0,0 +1,135 @@
+use crate::mesh::types::Mesh;
+use nalgebra::Vector3;
+
+//small seeded generator (SplitMix64) so sampling is reproducible without an extra dependency
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    //uniform in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+//Walker's alias method (Vose's construction): O(n) setup, then O(1) per draw
+//bucket i is kept with probability[i] and otherwise redirects to alias[i]
+pub struct AliasTable {
+    probability: Vec<f64>,
+    alias: Vec<usize>,
+}
+
+impl AliasTable {
+    //None when the weights don't sum to a positive finite total
+    pub fn new(weights: &[f64]) -> Option<Self> {
+        let total: f64 = weights.iter().sum();
+        if !total.is_finite() || total <= 0.0 {
+            return None;
+        }
+
+        let n = weights.len();
+        let mut scaled: Vec<f64> = weights.iter().map(|w| w * n as f64 / total).collect();
+        let mut probability = vec![1.0; n];
+        let mut alias: Vec<usize> = (0..n).collect();
+
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) = (0..n).partition(|&i| scaled[i] < 1.0);
+        while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+            small.pop();
+            probability[s] = scaled[s];
+            alias[s] = l;
+
+            scaled[l] -= 1.0 - scaled[s];
+            if scaled[l] < 1.0 {
+                large.pop();
+                small.push(l);
+            }
+        }
+        //whatever is left over is 1 up to rounding and keeps its own bucket
+
+        Some(AliasTable { probability, alias })
+    }
+
+    pub fn sample(&self, rng: &mut SplitMix64) -> usize {
+        let n = self.probability.len();
+        let position = rng.next_f64() * n as f64;
+        let bucket = (position as usize).min(n - 1);
+
+        //the fractional part decides between the bucket and its alias
+        if position - (bucket as f64) < self.probability[bucket] {
+            bucket
+        } else {
+            self.alias[bucket]
+        }
+    }
+}
+
+//uniformly distributed points on the surface as (face index, barycentric coordinates)
+//faces are picked proportionally to their area, empty for meshes without area
+pub fn sample_surface(mesh: &Mesh, count: usize, seed: u64) -> Vec<(usize, [f32; 3])> {
+    let areas: Vec<f64> = (0..mesh.faces.len()).map(|f| mesh.face_area(f) as f64).collect();
+    let table = match AliasTable::new(&areas) {
+        Some(table) => table,
+        None => return Vec::new(),
+    };
+
+    let mut rng = SplitMix64::new(seed);
+    (0..count)
+        .map(|_| {
+            let face = table.sample(&mut rng);
+
+            //square root warp keeps the points uniform over the triangle
+            let s = rng.next_f64().sqrt();
+            let t = rng.next_f64();
+            let bary = [(1.0 - s) as f32, (s * (1.0 - t)) as f32, (s * t) as f32];
+
+            (face, bary)
+        })
+        .collect()
+}
+
+//unit direction in the hemisphere around a unit normal with density proportional to the cosine
+//of its angle to the normal (Malley's method: uniform on the disk, lifted onto the hemisphere)
+pub fn cosine_hemisphere(normal: &Vector3<f32>, rng: &mut SplitMix64) -> Vector3<f32> {
+    let (tangent, bitangent) = tangent_frame(normal);
+
+    let radius = rng.next_f64().sqrt();
+    let angle = 2.0 * std::f64::consts::PI * rng.next_f64();
+    let (x, y) = (radius * angle.cos(), radius * angle.sin());
+    let z = (1.0 - radius * radius).max(0.0).sqrt();
+
+    (tangent * x as f32 + bitangent * y as f32 + normal * z as f32).normalize()
+}
+
+//unit direction uniformly distributed over the solid angle of the cone around a unit axis whose
+//half-angle has the given cosine
+pub fn cone(axis: &Vector3<f32>, cos_half_angle: f32, rng: &mut SplitMix64) -> Vector3<f32> {
+    let (tangent, bitangent) = tangent_frame(axis);
+
+    let z = 1.0 - rng.next_f64() * (1.0 - cos_half_angle as f64);
+    let radius = (1.0 - z * z).max(0.0).sqrt();
+    let angle = 2.0 * std::f64::consts::PI * rng.next_f64();
+
+    (tangent * (radius * angle.cos()) as f32 + bitangent * (radius * angle.sin()) as f32 + axis * z as f32)
+        .normalize()
+}
+
+//two unit vectors spanning the plane orthogonal to a unit normal
+pub fn tangent_frame(normal: &Vector3<f32>) -> (Vector3<f32>, Vector3<f32>) {
+    //any unit vector not parallel to the normal spans the tangent plane with it
+    let helper = if normal.x.abs() < 0.9 { Vector3::x() } else { Vector3::y() };
+    let tangent = normal.cross(&helper).normalize();
+    (tangent, normal.cross(&tangent))
+}
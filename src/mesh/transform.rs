@@ -0,0 +1,45 @@
+use crate::errors::MeshGeometryError;
+use nalgebra::{Matrix3, RealField, Vector3};
+use pyo3::PyResult;
+
+//Affine transforms generic over the scalar, shared by the single and double precision meshes.
+
+pub struct Affine<T: RealField + Copy> {
+    pub linear: Matrix3<T>,
+    pub offset: Vector3<T>,
+}
+
+impl<T: RealField + Copy> Affine<T> {
+    //from a row-major 4x4 matrix, which must be affine
+    pub fn from_rows(matrix: [[T; 4]; 4]) -> PyResult<Self> {
+        if matrix[3] != [T::zero(), T::zero(), T::zero(), T::one()] {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "expected an affine matrix with last row [0, 0, 0, 1], got {:?}",
+                matrix[3]
+            )));
+        }
+
+        Ok(Affine {
+            linear: Matrix3::from_fn(|r, c| matrix[r][c]),
+            offset: Vector3::new(matrix[0][3], matrix[1][3], matrix[2][3]),
+        })
+    }
+
+    pub fn apply(&self, v: Vector3<T>) -> Vector3<T> {
+        self.linear * v + self.offset
+    }
+
+    //each output coordinate depends on a single input one, so boxes map to boxes
+    //(scalings, translations, axis swaps)
+    pub fn is_axis_aligned(&self) -> bool {
+        (0..3).all(|r| (0..3).filter(|&c| self.linear[(r, c)] != T::zero()).count() <= 1)
+    }
+
+    //inverse transpose of the linear part, which keeps normals perpendicular to the surface
+    pub fn normal_matrix(&self) -> PyResult<Matrix3<T>> {
+        self.linear
+            .try_inverse()
+            .map(|inverse| inverse.transpose())
+            .ok_or_else(|| MeshGeometryError::new_err("cannot transform normals with a singular matrix"))
+    }
+}
@@ -1,7 +1,25 @@
-use nalgebra::Point3;
+use crate::accel::bvh::Bvh;
+use crate::accel::kdtree::KdTree;
+use crate::accel::union_find::UnionFind;
+use crate::analyzers::geodesic;
+use crate::analyzers::laplacian;
+use crate::analyzers::simplification;
+use crate::analyzers::topology::{EdgeCounts, Topology};
+use crate::errors::{MeshError, MeshGeometryError, MeshTopologyError};
+use crate::io::{bin, gzip, obj, ply, text, vtk};
+use crate::mesh::halfedge::HalfEdgeMesh;
+use crate::mesh::double::MeshF64;
+use crate::mesh::transform::Affine;
+use crate::mesh::{color, measure, primitives, sampling, uv};
+use crate::ops::{boolean, decomposition, remesh};
+use crate::ops::hull::Hull;
+use crate::progress::Progress;
+use nalgebra::{Matrix3, Point3, SymmetricEigen, Vector3};
 use pyo3::prelude::*;
+use std::sync::{Arc, OnceLock};
 
 #[pyclass]
+#[derive(Clone, Default)]
 pub struct Mesh {
     #[pyo3(get)]
     pub vertices: Vec<Point3<f32>>,
@@ -9,52 +27,278 @@ pub struct Mesh {
     pub faces: Vec<[usize; 3]>,
     #[pyo3(get)]
     pub normals: Option<Vec<Point3<f32>>>,
+    //per-vertex rgb in [0, 1], e.g. from colorize_from_scalars
+    #[pyo3(get)]
+    pub colors: Option<Vec<[f32; 3]>>,
+    //per-vertex texture coordinates, e.g. from generate_uvs
+    #[pyo3(get)]
+    pub uvs: Option<Vec<[f32; 2]>>,
+    //per-face group id, e.g. from `usemtl` in an OBJ, named by group_names
+    //filtered along with the faces, dropped by edits that add faces
+    #[pyo3(get)]
+    pub face_groups: Option<Vec<u32>>,
+    #[pyo3(get)]
+    pub group_names: Vec<String>,
+    //built on first nearest-vertex query, reset by geometry_changed
+    pub(crate) vertex_tree: OnceLock<KdTree>,
+    //built on first ray query, reset by geometry_changed
+    pub(crate) face_bvh: OnceLock<Bvh>,
+    //(min, max) from the last compute_bounds, carried through translations and scalings
+    pub(crate) bounds: OnceLock<([f32; 3], [f32; 3])>,
+    //faces using each vertex in face order, built on first use, reset by geometry_changed
+    pub(crate) vertex_faces: OnceLock<Vec<Vec<usize>>>,
+    //edge and vertex incidence shared with the topology analyzers, reset by geometry_changed
+    pub(crate) topology: OnceLock<Arc<Topology>>,
+    //faces per edge for is_watertight, kept up to date by add_face and remove_face and reset
+    //by geometry_changed otherwise
+    pub(crate) edge_counts: OnceLock<EdgeCounts>,
 }
 
 #[pymethods]
 impl Mesh {
+    //normals are stored as given, one per vertex, e.g. authored ones from a parsed file
     #[new]
-    fn new(vertices: Vec<[f32; 3]>, faces: Vec<[usize; 3]>) -> Self {
+    #[pyo3(signature = (vertices, faces, normals=None))]
+    fn new(vertices: Vec<[f32; 3]>, faces: Vec<[usize; 3]>, normals: Option<Vec<[f32; 3]>>) -> PyResult<Self> {
+        if let Some(normals) = &normals {
+            if normals.len() != vertices.len() {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "got {} normals for {} vertices",
+                    normals.len(),
+                    vertices.len()
+                )));
+            }
+        }
+
         let vertices = vertices.into_iter()
             .map(|v| Point3::new(v[0], v[1], v[2]))
             .collect();
+        let normals = normals.map(|normals| {
+            normals.into_iter().map(|n| Point3::new(n[0], n[1], n[2])).collect()
+        });
 
-        Mesh {
+        Ok(Mesh {
             vertices,
             faces,
-            normals: None,
-        }
+            normals,
+            ..Default::default()
+        })
     }
 
     //Calculate normals based on mesh
     fn compute_normals(&mut self) -> PyResult<()> {
-        let mut vertex_normals: Vec<Point3<f32>> = vec![Point3::new(0.0, 0.0, 0.0); self.vertices.len()];
-
-        for face in self.faces {
-            let v0 = &self.vertices[face[0]];
-            let v1 = &self.vertices[face[1]];
-            let v2 = &self.vertices[face[2]];
+        self.normals = Some(self.averaged_vertex_normals());
+        Ok(())
+    }
 
-            let edge1 = v1 - v0;
-            let edge2 = v2 - v0;
+    //compute_normals for just the listed vertices, from the faces around them
+    //the rest of the stored normals stay untouched, so normals must already exist
+    fn recompute_normals_for(&mut self, vertices: Vec<usize>) -> PyResult<()> {
+        if self.normals.is_none() {
+            return Err(MeshError::new_err(
+                "mesh has no normals to update, call compute_normals first",
+            ));
+        }
+        for &vertex in &vertices {
+            self.check_vertex_index(vertex)?;
+        }
 
-            let normal = edge1.cross(&edge2).normalize();
+        let updated: Vec<(usize, Point3<f32>)> = vertices
+            .iter()
+            .map(|&vertex| {
+                let sum = self.vertex_faces()[vertex]
+                    .iter()
+                    .fold(Vector3::zeros(), |sum, &f| sum + self.face_normal(f));
+                (vertex, unit_or_zero_normal(sum))
+            })
+            .collect();
 
-            vertex_normals[face[0]] += normal;
-            vertex_normals[face[1]] += normal;
-            vertex_normals[face[2]] += normal;
+        if let Some(normals) = &mut self.normals {
+            for (vertex, normal) in updated {
+                normals[vertex] = normal;
+            }
         }
+        Ok(())
+    }
+
+    //flips stored normals pointing away from the normal of the faces around their vertex,
+    //e.g. imported vn data with inverted entries; expects consistent winding
+    //vertices without faces are left alone, returns how many normals were flipped
+    fn fix_normal_orientation(&mut self) -> usize {
+        let geometric = self.averaged_vertex_normals();
+        let normals = match &mut self.normals {
+            Some(normals) => normals,
+            None => return 0,
+        };
 
-        for normal in &mut vertex_normals {
-            if normal.norm() > 1e-6 {
-                *normal = normal.normalize();
+        let mut flipped = 0;
+        for (normal, reference) in normals.iter_mut().zip(&geometric) {
+            if normal.coords.dot(&reference.coords) < 0.0 {
+                *normal = -*normal;
+                flipped += 1;
             }
         }
+        flipped
+    }
+
+    //iterator over the faces as triples of corner positions, one face at a time
+    //it reads the mesh as it goes, so edits made meanwhile show up in the remaining faces
+    fn iter_triangles(slf: &PyCell<Self>) -> TriangleIter {
+        TriangleIter {
+            mesh: slf.into(),
+            next: 0,
+        }
+    }
+
+    //stores per-vertex colors for a scalar per vertex, e.g. curvature or thickness, mapped
+    //through colormap "viridis", "jet" or "grayscale" after scaling the values from their
+    //min..max to 0..1; a constant field gets the middle color everywhere
+    fn colorize_from_scalars(&mut self, values: Vec<f32>, colormap: &str) -> PyResult<()> {
+        let colormap = color::by_name(colormap).ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "unknown colormap '{}', expected 'viridis', 'jet' or 'grayscale'",
+                colormap
+            ))
+        })?;
+        if values.len() != self.vertices.len() {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "got {} values for {} vertices",
+                values.len(),
+                self.vertices.len()
+            )));
+        }
+        if !values.iter().all(|v| v.is_finite()) {
+            return Err(pyo3::exceptions::PyValueError::new_err("values must be finite"));
+        }
+
+        let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let range = max - min;
+        self.colors = Some(
+            values
+                .iter()
+                .map(|&v| colormap(if range > 0.0 { (v - min) / range } else { 0.5 }))
+                .collect(),
+        );
 
-        self.normals = Some(vertex_normals);
         Ok(())
     }
 
+    //indices of the faces in a group, in face order; group_names[group] is its name
+    //e.g. submesh(faces_in_group(g)) splits out one material of a loaded OBJ
+    fn faces_in_group(&self, group: u32) -> PyResult<Vec<usize>> {
+        let groups = self.face_groups.as_ref().ok_or_else(|| MeshError::new_err("mesh has no face groups"))?;
+
+        Ok(groups
+            .iter()
+            .enumerate()
+            .filter(|&(_, &g)| g == group)
+            .map(|(face, _)| face)
+            .collect())
+    }
+
+    //the normals compute_normals would store, returned without modifying the mesh
+    fn vertex_normals(&self) -> Vec<[f32; 3]> {
+        self.averaged_vertex_normals()
+            .iter()
+            .map(|n| [n.x, n.y, n.z])
+            .collect()
+    }
+
+    //stores per-vertex uvs projected from the bounding box center: "spherical", "cylindrical"
+    //(around z) or "planar" (across the dominant_normal, z for closed meshes)
+    //faces straddling the seam of the wrapping projections get their own copies of the
+    //vertices on the low side with u + 1, so u goes a little past 1 there and the mesh is no
+    //longer watertight along the seam; returns how many vertices were added
+    fn generate_uvs(&mut self, projection: &str) -> PyResult<usize> {
+        let (min, max) = self.compute_bounds();
+        let center = Point3::from((Vector3::from(min) + Vector3::from(max)) * 0.5);
+
+        let mut uvs = match projection {
+            "spherical" => uv::spherical(&self.vertices, &center),
+            "cylindrical" => uv::cylindrical(&self.vertices, &center, (min[2], max[2])),
+            "planar" => {
+                let normal = Vector3::from(self.dominant_normal()).try_normalize(1e-3).unwrap_or_else(Vector3::z);
+                uv::planar(&self.vertices, &normal)
+            }
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "unknown projection '{}', expected 'spherical', 'cylindrical' or 'planar'",
+                    projection
+                )))
+            }
+        };
+
+        //planar uvs don't wrap, a wide face there is just wide
+        let added = if projection == "planar" { 0 } else { uv::split_seam(self, &mut uvs) };
+        self.uvs = Some(uvs);
+        Ok(added)
+    }
+
+    //per-vertex (tangent, bitangent) for normal mapping from one uv per vertex (Lengyel):
+    //the uv gradients of the faces around a vertex are summed, the tangent is made orthogonal
+    //to the stored normal and the bitangent is normal x tangent, flipped for mirrored uvs
+    //vertices without usable uv gradients get an arbitrary frame around their normal
+    //uvs default to the stored ones
+    #[pyo3(signature = (uvs=None))]
+    fn compute_tangents(&self, uvs: Option<Vec<[f32; 2]>>) -> PyResult<TangentFrames> {
+        let normals = self.normals.as_ref().ok_or_else(|| {
+            MeshError::new_err("compute_tangents needs vertex normals, call compute_normals first")
+        })?;
+        let uvs = match uvs.as_ref().or(self.uvs.as_ref()) {
+            Some(uvs) => uvs,
+            None => return Err(MeshError::new_err("mesh has no uvs, pass them or call generate_uvs first")),
+        };
+        if uvs.len() != self.vertices.len() {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "got {} uvs for {} vertices",
+                uvs.len(),
+                self.vertices.len()
+            )));
+        }
+
+        let mut tangents = vec![Vector3::zeros(); self.vertices.len()];
+        let mut bitangents = vec![Vector3::zeros(); self.vertices.len()];
+        for face in &self.faces {
+            let edge1 = self.vertices[face[1]] - self.vertices[face[0]];
+            let edge2 = self.vertices[face[2]] - self.vertices[face[0]];
+            let (du1, dv1) = (uvs[face[1]][0] - uvs[face[0]][0], uvs[face[1]][1] - uvs[face[0]][1]);
+            let (du2, dv2) = (uvs[face[2]][0] - uvs[face[0]][0], uvs[face[2]][1] - uvs[face[0]][1]);
+
+            //faces without area in uv space have no gradient to contribute
+            let determinant = du1 * dv2 - du2 * dv1;
+            if determinant.abs() <= f32::EPSILON * (du1.abs() + du2.abs()) * (dv1.abs() + dv2.abs()) {
+                continue;
+            }
+            let tangent = (edge1 * dv2 - edge2 * dv1) / determinant;
+            let bitangent = (edge2 * du1 - edge1 * du2) / determinant;
+            for &v in face {
+                tangents[v] += tangent;
+                bitangents[v] += bitangent;
+            }
+        }
+
+        Ok(normals
+            .iter()
+            .zip(tangents.iter().zip(&bitangents))
+            .map(|(normal, (tangent, bitangent))| {
+                let normal = match normal.coords.try_normalize(0.0) {
+                    Some(normal) => normal,
+                    None => return ([0.0; 3], [0.0; 3]),
+                };
+                let tangent = match (tangent - normal * normal.dot(tangent)).try_normalize(1e-12) {
+                    Some(tangent) => tangent,
+                    None => sampling::tangent_frame(&normal).0,
+                };
+                let mut side = normal.cross(&tangent);
+                if side.dot(bitangent) < 0.0 {
+                    side = -side;
+                }
+                ([tangent.x, tangent.y, tangent.z], [side.x, side.y, side.z])
+            })
+            .unzip())
+    }
+
     fn vertex_count(&self) -> usize {
         self.vertices.len()
     }
@@ -63,45 +307,230 @@ impl Mesh {
         self.faces.len()
     }
 
+    //true when there is no surface, i.e. no faces or no vertices
+    pub fn is_empty(&self) -> bool {
+        self.faces.is_empty() || self.vertices.is_empty()
+    }
+
+    //len(mesh) is the face count
+    fn __len__(&self) -> usize {
+        self.faces.len()
+    }
+
+    fn __bool__(&self) -> bool {
+        !self.is_empty()
+    }
+
+    //a + b is Mesh.merge(a, b)
+    fn __add__(&self, other: &Mesh) -> Mesh {
+        Mesh::merge(self, other)
+    }
+
+    //a += b appends b in place without copying a, for assembling scenes in a loop
+    fn __iadd__(slf: &PyCell<Self>, other: &PyCell<Mesh>) -> PyResult<()> {
+        //a += a needs a copy, a can't be read while it is being extended
+        if slf.is(other) {
+            let copy = other.try_borrow()?.clone();
+            slf.try_borrow_mut()?.append(&copy);
+        } else {
+            slf.try_borrow_mut()?.append(&*other.try_borrow()?);
+        }
+        Ok(())
+    }
+
     //surface area of mesh
     fn compute_surface_area(&self) -> f32 {
-        let mut area = 0.0;
+        measure::surface_area(&self.vertices, &self.faces)
+    }
 
-        for face in &self.faces {
-            let v0 = &self.vertices[face[0]];
-            let v1 = &self.vertices[face[1]];
-            let v2 = &self.vertices[face[2]];
+    //volume enclosed by the faces, positive for outward winding
+    //only meaningful for watertight meshes
+    fn compute_volume(&self) -> f32 {
+        measure::volume(&self.vertices, &self.faces)
+    }
+
+    //area of every face
+    fn face_areas(&self) -> Vec<f32> {
+        (0..self.faces.len()).map(|f| self.face_area(f)).collect()
+    }
+
+    //unit normal of every face from its winding, zero for degenerate faces
+    fn compute_face_normals(&self) -> Vec<[f32; 3]> {
+        (0..self.faces.len())
+            .map(|f| {
+                let n = self.face_normal(f);
+                [n.x, n.y, n.z]
+            })
+            .collect()
+    }
+
+    //area weighted average of the face normals, e.g. the "up" of a mostly flat part
+    //its length says how much the faces agree: 1 for a flat patch, near zero for a closed
+    //surface where the normals cancel; zero for a mesh without area
+    fn dominant_normal(&self) -> [f32; 3] {
+        let areas = self.face_areas();
+        let total: f32 = areas.iter().sum();
+        if total.is_nan() || total <= 0.0 {
+            return [0.0; 3];
+        }
 
-            let edge1 = v1 - v0;
-            let edge2 = v2 - v0;
+        let sum = areas
+            .iter()
+            .zip(self.compute_face_normals())
+            .fold(Vector3::zeros(), |sum, (&area, n)| sum + Vector3::from(n) * area);
+        (sum / total).into()
+    }
 
-            area += edge1.cross(&edge2).norm();
+    //fraction of the surface area whose normal is within 90 degrees of direction, 0 for a mesh
+    //without area; e.g. how much of a part faces up, or is visible from a pull direction
+    fn orientation_coverage(&self, direction: [f32; 3]) -> PyResult<f32> {
+        let direction = unit_direction(direction)?;
+        let areas = self.face_areas();
+        let total: f32 = areas.iter().sum();
+        if total.is_nan() || total <= 0.0 {
+            return Ok(0.0);
         }
 
-        area
+        let facing: f32 = areas
+            .iter()
+            .zip(self.compute_face_normals())
+            .filter(|(_, n)| Vector3::from(*n).dot(&direction) > 0.0)
+            .map(|(area, _)| area)
+            .sum();
+        Ok(facing / total)
     }
 
-    //checks if mesh is watertight
-    fn has_holes(&self) -> bool {
-        use std::collections::HashMap;
-        let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+    //outline edges seen along direction as sorted (a, b) with a < b: boundary edges and edges
+    //between a face whose normal points along direction and one that doesn't (edge-on faces
+    //count as pointing away), so a closed shape gives the contour of its projection
+    fn silhouette(&self, direction: [f32; 3]) -> PyResult<Vec<(usize, usize)>> {
+        let direction = unit_direction(direction)?;
+        let facing: Vec<bool> = self
+            .compute_face_normals()
+            .into_iter()
+            .map(|n| Vector3::from(n).dot(&direction) > 0.0)
+            .collect();
 
-        for face in &self.faces {
-            let v0 = faces[0];
-            let v1 = faces[1];
-            let v2 = faces[2];
+        let mut edges: Vec<(usize, usize)> = self
+            .topology()
+            .edge_to_faces
+            .iter()
+            .filter(|(_, faces)| {
+                faces.len() == 1 || faces.iter().any(|&f| facing[f]) != faces.iter().all(|&f| facing[f])
+            })
+            .map(|(&edge, _)| edge)
+            .collect();
+        edges.sort_unstable();
+        Ok(edges)
+    }
+
+    //faces whose winding disagrees with the normals of their vertices
+    //without stored normals they are computed on a consistently oriented copy, and the
+    //smaller of the two winding classes is reported as flipped
+    fn flipped_faces(&self) -> Vec<usize> {
+        let disagreeing = |normals: &[Vector3<f32>]| -> Vec<usize> {
+            (0..self.faces.len())
+                .filter(|&f| {
+                    let face = &self.faces[f];
+                    let average = normals[face[0]] + normals[face[1]] + normals[face[2]];
+                    self.face_normal(f).dot(&average) < 0.0
+                })
+                .collect()
+        };
 
-            let edges_to_add = [
-                (v0.min(v1), v0.max(v1)),
-                (v1.min(v2), v1.max(v2)),
-                (v0.min(v2), v0.max(v2)),
-            ];
+        if let Some(normals) = &self.normals {
+            let normals: Vec<Vector3<f32>> = normals.iter().map(|n| n.coords).collect();
+            return disagreeing(&normals);
+        }
+
+        let mut oriented = self.clone();
+        oriented.orient_consistent();
+        let flipped = disagreeing(&oriented.accumulate_vertex_normals(NormalWeighting::Uniform));
 
-            for edge in edges_to_add {
-                *edges.entry(edge).or_insert(0) += 1;
+        if flipped.len() * 2 > self.faces.len() {
+            let mut is_flipped = vec![false; self.faces.len()];
+            for &f in &flipped {
+                is_flipped[f] = true;
             }
+            return (0..self.faces.len()).filter(|&f| !is_flipped[f]).collect();
+        }
+
+        flipped
+    }
+
+    //area of the faces seen from `direction`, i.e. the shadow cast along it
+    fn projected_area(&self, direction: [f32; 3]) -> PyResult<f32> {
+        let direction = unit_direction(direction)?;
+
+        Ok((0..self.faces.len())
+            .map(|f| self.face_normal(f).dot(&direction).max(0.0) * self.face_area(f))
+            .sum())
+    }
+
+    //area of all faces projected onto the plane orthogonal to `direction`, front and back facing
+    fn silhouette_area(&self, direction: [f32; 3]) -> PyResult<f32> {
+        let direction = unit_direction(direction)?;
+
+        Ok((0..self.faces.len())
+            .map(|f| self.face_normal(f).dot(&direction).abs() * self.face_area(f))
+            .sum())
+    }
+
+    //true if any edge doesn't have exactly two faces, the opposite of is_watertight
+    //non-manifold edges count as well, since they don't close the surface either
+    fn has_holes(&self) -> bool {
+        !self.topology().is_watertight()
+    }
+
+    //every edge is shared by exactly two faces; cheap to repeat between add_face and remove_face
+    fn is_watertight(&self) -> bool {
+        self.edge_counts.get_or_init(|| EdgeCounts::new(&self.faces)).is_watertight()
+    }
+
+    //appends a face, returns its index
+    //unlike editing faces wholesale this keeps is_watertight's edge counts, so it stays cheap
+    //the new face has no group, so face_groups is dropped
+    fn add_face(&mut self, face: [usize; 3]) -> PyResult<usize> {
+        for &vertex in &face {
+            self.check_vertex_index(vertex)?;
+        }
+
+        let edge_counts = self.edge_counts.take();
+        self.faces.push(face);
+        self.face_groups = None;
+        self.group_names.clear();
+        self.geometry_changed();
+        if let Some(mut edge_counts) = edge_counts {
+            edge_counts.add(&face);
+            self.edge_counts = OnceLock::from(edge_counts);
+        }
+
+        Ok(self.faces.len() - 1)
+    }
+
+    //removes a face in constant time by moving the last face into its slot, returns the
+    //removed face; like add_face it keeps is_watertight's edge counts
+    fn remove_face(&mut self, index: usize) -> PyResult<[usize; 3]> {
+        if index >= self.faces.len() {
+            return Err(pyo3::exceptions::PyIndexError::new_err(format!(
+                "face index {} out of range for mesh with {} faces",
+                index,
+                self.faces.len()
+            )));
+        }
+
+        let edge_counts = self.edge_counts.take();
+        let face = self.faces.swap_remove(index);
+        if let Some(groups) = &mut self.face_groups {
+            groups.swap_remove(index);
+        }
+        self.geometry_changed();
+        if let Some(mut edge_counts) = edge_counts {
+            edge_counts.remove(&face);
+            self.edge_counts = OnceLock::from(edge_counts);
         }
-        edges.values().any(|&count| count == 1)
+
+        Ok(face)
     }
 
     fn scaled(&self, scale_factor: f32) -> Mesh {
@@ -118,6 +547,12 @@ impl Mesh {
             vertices: scaled_vertices,
             faces: self.faces.clone(),
             normals: scaled_normals,
+            colors: self.colors.clone(),
+            uvs: self.uvs.clone(),
+            face_groups: self.face_groups.clone(),
+            group_names: self.group_names.clone(),
+            bounds: self.mapped_bounds(|v| v * scale_factor),
+            ..Default::default()
         }
     }
 
@@ -127,203 +562,2979 @@ impl Mesh {
 
         let translated_vertices = self.vertices
             .iter()
-            .map(|v| v + offset.coords())
+            .map(|v| v + offset.coords)
             .collect();
 
         Mesh{
             vertices: translated_vertices,
             faces: self.faces.clone(),
             normals: self.normals.clone(),
+            colors: self.colors.clone(),
+            uvs: self.uvs.clone(),
+            face_groups: self.face_groups.clone(),
+            group_names: self.group_names.clone(),
+            bounds: self.mapped_bounds(|v| v + offset.coords),
+            ..Default::default()
         }
     }
 
-    //returns bounding box of mesh
-    fn compute_bounds(&self) -> ([f32; 3], [f32; 3]) {
-        if self.vertices.is_empty() {
-            return ([0.0, 0.0, 0.0], [0.0, 0.0, 0.0]);
+    //applies a row-major affine 4x4 matrix in place, normals go through its inverse transpose
+    //cached bounds are kept for axis-aligned matrices (scalings, translations, axis swaps)
+    //and recomputed on the next compute_bounds after rotations and shears
+    fn transform(&mut self, matrix: [[f32; 4]; 4]) -> PyResult<()> {
+        let affine = Affine::from_rows(matrix)?;
+        let map = |v: Vector3<f32>| affine.apply(v);
+
+        let normal_matrix = match &self.normals {
+            Some(_) => Some(affine.normal_matrix()?),
+            None => None,
+        };
+
+        let bounds = if affine.is_axis_aligned() { self.mapped_bounds(map) } else { OnceLock::new() };
+
+        for vertex in &mut self.vertices {
+            *vertex = Point3::from(map(vertex.coords));
+        }
+        if let (Some(normals), Some(normal_matrix)) = (&mut self.normals, normal_matrix) {
+            for normal in normals.iter_mut() {
+                let transformed = normal_matrix * normal.coords;
+                *normal = Point3::from(transformed.try_normalize(0.0).unwrap_or(transformed));
+            }
         }
 
-        let mut min_x = f32::INFINITY;
-        let mut min_y = f32::INFINITY;
-        let mut min_z = f32::INFINITY;
-        let mut max_x = f32::NEG_INFINITY;
-        let mut max_y = f32::NEG_INFINITY;
-        let mut max_z = f32::NEG_INFINITY;
+        self.geometry_changed();
+        self.bounds = bounds;
+        Ok(())
+    }
 
-        for vertex in &self.vertices {
-            min_x = min_x.min(vertex.x);
-            min_y = min_y.min(vertex.y);
-            min_z = min_z.min(vertex.z);
-            max_x = max_x.max(vertex.x);
-            max_y = max_y.max(vertex.y);
-            max_z = max_z.max(vertex.z);
+    //drops the cached bounding box, the next compute_bounds scans the vertices again
+    fn invalidate_bounds(&mut self) {
+        self.bounds = OnceLock::new();
+    }
+
+    //new mesh with every vertex moved along its normal by distance, negative offsets inward
+    //uses the stored normals, or area-weighted ones when there are none
+    //this is a plain vertex offset: faces are kept as they are, and on concave regions
+    //tighter than the distance the offset surface intersects itself
+    fn offset_surface(&self, distance: f32) -> Mesh {
+        let normals: Vec<Vector3<f32>> = match &self.normals {
+            Some(normals) => normals.iter().map(|n| n.coords).collect(),
+            None => self.accumulate_vertex_normals(NormalWeighting::Area),
+        };
+
+        let vertices = self.vertices
+            .iter()
+            .zip(&normals)
+            .map(|(v, n)| v + n * distance)
+            .collect();
+
+        Mesh {
+            vertices,
+            faces: self.faces.clone(),
+            normals: self.normals.clone(),
+            colors: self.colors.clone(),
+            uvs: self.uvs.clone(),
+            face_groups: self.face_groups.clone(),
+            group_names: self.group_names.clone(),
+            ..Default::default()
+        }
+    }
+
+    //reverses winding of every face and negates normals if present
+    fn flip_normals(&mut self) {
+        for face in &mut self.faces {
+            face.swap(1, 2);
         }
+        //the BVH keeps the corner order of every face
+        self.geometry_changed();
 
-        ([min_x, min_y, min_z], [max_x, max_y, max_z])
+        if let Some(normals) = &mut self.normals {
+            for normal in normals.iter_mut() {
+                *normal = -*normal;
+            }
+        }
     }
 
-    //mesh in text representation
-    fn __repr__(&self) -> String {
-        format!(
-            "Mesh(vertices={}, faces={}, has_normals={})",
-            self.vertices.len(),
-            self.faces.len(),
-            self.normals.is_some()
-        )
+    //moves every vertex by factor towards the weighted average of its 1-ring, per iteration
+    //weighting is "uniform" (plain average) or "cotangent", which follows the surface shape
+    //instead of the tessellation and so avoids sliding vertices along the surface
+    //"uniform" walks half-edges and raises MeshTopologyError if two faces share a directed edge
+    #[pyo3(signature = (iterations, factor=0.5, weighting="uniform"))]
+    fn laplacian_smooth(&mut self, iterations: usize, factor: f32, weighting: &str) -> PyResult<()> {
+        if factor.is_nan() || factor <= 0.0 || factor > 1.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "factor must be in (0, 1], got {}",
+                factor
+            )));
+        }
+
+        let mut rings = self.smoothing_rings(weighting)?;
+        for iteration in 0..iterations {
+            //cotangent weights depend on the triangle shapes, which every step changes
+            if iteration > 0 && weighting == "cotangent" {
+                rings = laplacian::cotangent_rings(self);
+            }
+            self.laplacian_step(&rings, factor);
+        }
+
+        if self.normals.is_some() {
+            self.compute_normals()?;
+        }
+
+        Ok(())
     }
 
-    fn find_isolated_vertices(&self) -> Vec<usize> {
-        let mut is_connected = vec![false; self.vertices.len()];
+    //Taubin lambda|mu smoothing, a shrinking step followed by an inflating one per iteration
+    //requires 0 < lambda < -mu < 1, e.g. lambda = 0.5, mu = -0.53
+    //raises MeshTopologyError if two faces share an edge in the same direction
+    fn taubin_smooth(&mut self, iterations: usize, lambda: f32, mu: f32) -> PyResult<()> {
+        if lambda.is_nan() || lambda <= 0.0 || lambda >= 1.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "lambda must be in (0, 1), got {}",
+                lambda
+            )));
+        }
+        if mu.is_nan() || mu >= -lambda || mu <= -1.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "mu must satisfy -1 < mu < -lambda, got mu = {} for lambda = {}",
+                mu, lambda
+            )));
+        }
 
-        for face in &self.faces {
-            is_connected[face[0]] = true;
-            is_connected[face[1]] = true;
-            is_connected[face[2]] = true;
+        let rings = self.smoothing_rings("uniform")?;
+        for _ in 0..iterations {
+            self.laplacian_step(&rings, lambda);
+            self.laplacian_step(&rings, mu);
         }
 
-        is_connected.iter()
-            .enumerate()
-            .filter(|(_, &connected)| !connected)
-            .map(|(i, _)| i)
-            .collect()
+        if self.normals.is_some() {
+            self.compute_normals()?;
+        }
+
+        Ok(())
     }
 
-    //saves mesh to .obj file
-    fn save_obj(&self, filename: &str) -> PyResult<()> {
-        use std::fs::File;
-        use std::io::{BufWriter, Write};
+    //approximate curve skeleton as a graph of (nodes, edges), e.g. to measure the branches of
+    //tubular shapes: a copy of the surface is contracted towards its medial curve by
+    //iterations cotangent Laplacian steps, then vertices joined by contracted edges shorter
+    //than resolution are merged into one node at their mean, and nodes are joined wherever a
+    //mesh edge runs between them
+    //meant for closed surfaces, open boundaries flatten into their neighbors instead of
+    //contracting; a negative resolution is relative to characteristic_length, it should stay
+    //below the edge lengths along the tubes or whole branches merge
+    //nodes are ordered by their first vertex and edges are sorted
+    #[pyo3(signature = (iterations, resolution=-0.02))]
+    fn curve_skeleton(&self, iterations: usize, resolution: f32) -> PyResult<Skeleton> {
+        use std::collections::BTreeSet;
 
-        let file = File::create(filename)?;
-        let mut writer = BufWriter::new(file);
+        if resolution.is_nan() {
+            return Err(pyo3::exceptions::PyValueError::new_err("resolution must not be nan"));
+        }
+        let merge_length = self.absolute_tolerance(resolution);
 
-        for v in &self.vertices {
-            writeln!(writer, "v {} {} {}", v.x, v.y, v.z)?;
-            }
+        let mut contracted = Mesh {
+            vertices: self.vertices.clone(),
+            faces: self.faces.clone(),
+            ..Default::default()
+        };
+        for _ in 0..iterations {
+            //the weights follow the shrinking triangles, collapsed ones stop pulling
+            contracted.laplacian_step(&laplacian::cotangent_rings(&contracted), 1.0);
+        }
 
-            if let Some(normals) = &self.normals {
-                for n in normals {
-                    writeln!(writer, "vn {} {} {}", n.x, n.y, n.z)?;
-                }
+        let edges: Vec<(usize, usize)> = self.topology().edge_to_faces.keys().copied().collect();
+        let mut clusters = UnionFind::new(self.vertices.len());
+        for &(a, b) in &edges {
+            if (contracted.vertices[a] - contracted.vertices[b]).norm() <= merge_length {
+                clusters.union(a, b);
+            }
+        }
 
-                for face in &self.faces {
-                    writeln!(
-                        writer,
-                        "f {}//{} {}//{} {}//{}",
-                        face[0] + 1, face[0] + 1,
-                        face[1] + 1, face[1] + 1,
-                        face[2] + 1, face[2] + 1,
-                    )?;
-                }
-            } else {
-                for face in &self.faces {
-                    writeln!(
-                        writer,
-                        "f {} {} {}",
-                        face[0] + 1, face[1] = 1, face[2] + 1
-                    )?;
-                }
+        let mut node_of_root = vec![usize::MAX; self.vertices.len()];
+        let mut sums: Vec<(Vector3<f32>, usize)> = Vec::new();
+        let mut node_of_vertex = vec![usize::MAX; self.vertices.len()];
+        for &v in self.faces.iter().flatten().collect::<BTreeSet<_>>() {
+            let root = clusters.find(v);
+            if node_of_root[root] == usize::MAX {
+                node_of_root[root] = sums.len();
+                sums.push((Vector3::zeros(), 0));
             }
+            let node = node_of_root[root];
+            sums[node].0 += contracted.vertices[v].coords;
+            sums[node].1 += 1;
+            node_of_vertex[v] = node;
+        }
 
-            Ok(())
+        let nodes = sums.iter().map(|(sum, count)| (sum / *count as f32).into()).collect();
+        let links: BTreeSet<(usize, usize)> = edges
+            .iter()
+            .map(|&(a, b)| (node_of_vertex[a], node_of_vertex[b]))
+            .filter(|(a, b)| a != b)
+            .map(|(a, b)| (a.min(b), a.max(b)))
+            .collect();
+
+        Ok((nodes, links.into_iter().collect()))
+    }
 
+    //vertices farther from the centroid of their 1-ring than threshold times the mean length
+    //of the ring's own edges, i.e. the edges opposite the vertex in its faces, which unlike
+    //the vertex's edges don't stretch with the spike
+    //raises MeshTopologyError if two faces share an edge in the same direction
+    fn detect_spikes(&self, threshold: f32) -> PyResult<Vec<usize>> {
+        Ok(self.spikes(threshold)?.into_iter().map(|(v, _)| v).collect())
+    }
+
+    //moves every detect_spikes vertex onto the centroid of its 1-ring, a Laplacian step on just
+    //those vertices; normals are recomputed if present, returns how many vertices moved
+    fn remove_spikes(&mut self, threshold: f32) -> PyResult<usize> {
+        let spikes = self.spikes(threshold)?;
+        if spikes.is_empty() {
+            return Ok(0);
         }
 
-    #[staticmethod]
-    fn from_obj(filename: &str) -> PyResult<Mesh> {
-        use std::io::{BufRead, BufReader};
-        use std::fs::File;
+        for &(v, centroid) in &spikes {
+            self.vertices[v] = centroid;
+        }
+        self.geometry_changed();
+        if self.normals.is_some() {
+            self.compute_normals()?;
+        }
 
-        let file = File::open(filename)?;
-        let reader = BufReader::new(file);
+        Ok(spikes.len())
+    }
 
-        let mut vertices = Vec::new();
-        let mut faces = Vec::new();
-        let mut normals_data = Vec::new();
-        let mut has_normals = false;
+    //returns bounding box of mesh, cached until the geometry changes
+    pub fn compute_bounds(&self) -> ([f32; 3], [f32; 3]) {
+        *self.bounds.get_or_init(|| self.scan_bounds())
+    }
 
-        for line in reader.lines() {
-            let line = line?;
-            let parts: Vec<&str> = line.split_whitespace().collect();
+    //reference length for tolerances that should scale with the mesh, the bounding box
+    //diagonal; zero for an empty mesh
+    //distance tolerances read negative values as a fraction of it: tolerance=-1e-4 means
+    //1e-4 * characteristic_length(), so the same pipeline works in millimeters and meters
+    fn characteristic_length(&self) -> f32 {
+        measure::diagonal(self.compute_bounds())
+    }
 
-            if parts.is_empty(){
-                continue;
+    //mean of the vertex positions
+    fn centroid(&self) -> [f32; 3] {
+        measure::centroid(&self.vertices)
+    }
+
+    //moves and uniformly scales the mesh into a canonical frame
+    //"unit_sphere": centroid at the origin, farthest vertex at distance 1
+    //"unit_cube": bounding box centered at the origin and fitted into [-0.5, 0.5]^3
+    fn normalize(&mut self, mode: &str) -> PyResult<()> {
+        let (center, size) = match mode {
+            "unit_sphere" => {
+                let center = self.centroid();
+                let radius = self.vertices
+                    .iter()
+                    .map(|v| (v.coords - Vector3::from(center)).norm())
+                    .fold(0.0f32, f32::max);
+                (center, radius)
+            },
+            "unit_cube" => {
+                let (center, half_extents) = self.compute_aabb();
+                let extent = half_extents.iter().fold(0.0f32, |a, &b| a.max(b)) * 2.0;
+                (center, extent)
+            },
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "unknown normalization mode '{}', expected 'unit_sphere' or 'unit_cube'",
+                    mode
+                )));
             }
+        };
 
-            match parts[0] {
-                "v" => {
-                    if parts.len() >= 4 {
-                        let x = parts[1].parse::<f32>().unwrap_or(0.0);
-                        let y = parts[2].parse::<f32>().unwrap_or(0.0);
-                        let z = parts[3].parse::<f32>().unwrap_or(0.0);
-                        vertices.push(Point3::new(x, y, z));
-                    }
-                },
-                "vn" => {
-                    if parts.len() >= 4 {
-                        let x = parts[1].parse::<f32>().unwrap_or(0.0);
-                        let y = parts[2].parse::<f32>().unwrap_or(0.0);
-                        let z = parts[3].parse::<f32>().unwrap_or(0.0);
-                        normals_data.push(Point3::new(x, y, z));
-                        has_normals = true;
-                    }
-                },
-                "f" => {
-                    if parts.len() >= 4 {
-                        let mut face_indices = [0; 3];
-
-                        for i in 0..3 {
-                            let vertex_str = parts[i+1].split('/').next().unwrap("1");
-                            let vertex_idx = vertex_str.parse::<usize>().unwrap(1) - 1;
-                            face_indices[i] = vertex_idx;
-                        }
+        if size.is_nan() || size <= 0.0 {
+            return Err(MeshGeometryError::new_err("cannot normalize a mesh with zero extent"));
+        }
 
-                        faces.push(face_indices);
-                    }
-                },
-                _ => {}
-            }
+        *self = self
+            .translated(-center[0], -center[1], -center[2])
+            .scaled(1.0 / size);
+        Ok(())
+    }
 
-            let normals = if has_normals && normals_data.len() == vertices.len() {
-                Some(normals_data)
-            } else {
-                None
-            };
+    //closest mesh vertex to a point as (index, distance)
+    fn nearest_vertex(&self, point: [f32; 3]) -> PyResult<(usize, f32)> {
+        self.vertex_tree()?
+            .nearest(&Point3::from(point))
+            .ok_or_else(|| MeshGeometryError::new_err("mesh has no vertices"))
+    }
 
-            Ok(Mesh{
-                vertices,
-                faces,
-                normals,
-            })
+    //k closest vertices as (index, distance) pairs sorted by distance
+    fn k_nearest_vertices(&self, point: [f32; 3], k: usize) -> PyResult<Vec<(usize, f32)>> {
+        Ok(self.vertex_tree()?.k_nearest(&Point3::from(point), k))
+    }
+
+    //nearest hit of every ray as (distance, face index), None for rays that miss
+    //directions don't need to be unit length, distances are measured along the normalized direction
+    fn ray_intersect_batch(
+        &self,
+        py: Python,
+        origins: Vec<[f32; 3]>,
+        directions: Vec<[f32; 3]>,
+    ) -> PyResult<Vec<Option<(f32, usize)>>> {
+        if origins.len() != directions.len() {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "got {} origins but {} directions",
+                origins.len(),
+                directions.len()
+            )));
         }
 
-        #[staticmethod]
-        fn merge(mesh1: &Mesh, mesh2: &Mesh) -> Mesh {
-            let offset = mesh1.vertices.len();
+        let directions = directions
+            .into_iter()
+            .map(unit_direction)
+            .collect::<PyResult<Vec<Vector3<f32>>>>()?;
+        let bvh = self.face_bvh();
 
-            let mut vertices = mesh1.vertices.clone();
-            vertices.extend(mesh2.vertices.clone());
+        let cast = |(origin, direction): (&[f32; 3], &Vector3<f32>)| {
+            bvh.intersect_ray(&Point3::from(*origin), direction)
+        };
 
-            let mut faces = mesh1.faces.clone();
-            let shifted_faces: Vec<[usize; 3]> = mesh2.faces
-                .iter()
-                .map(|face| [face[0] + offset, face[1] + offset, face[2] + offset])
-                .collect();
-            faces.extend(shifted_faces);
-
-            let normals = match (&mesh1.normals, &mesh2.normals) {
-                (Some(n1), Some(n2)) => {
-                    let mut normals = n1.clone();
-                    normals.extend(n2.clone());
-                    Some(normals)
-                },
-                _ => None,
-            };
+        Ok(py.allow_threads(|| {
+            #[cfg(feature = "parallel")]
+            {
+                use rayon::prelude::*;
+                origins.par_iter().zip(directions.par_iter()).map(cast).collect()
+            }
+            #[cfg(not(feature = "parallel"))]
+            {
+                origins.iter().zip(directions.iter()).map(cast).collect()
+            }
+        }))
+    }
+
+    //indices of the faces whose bounding box overlaps the box from min to max, sorted
+    //pass them to submesh to cut the region out as a mesh of its own
+    fn faces_in_box(&self, min: [f32; 3], max: [f32; 3]) -> PyResult<Vec<usize>> {
+        if (0..3).any(|axis| min[axis].is_nan() || max[axis].is_nan() || min[axis] > max[axis]) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "box min {:?} must not exceed max {:?}",
+                min, max
+            )));
+        }
+
+        Ok(self.face_bvh().faces_in_box(&Vector3::from(min), &Vector3::from(max)))
+    }
+
+    //every surface crossing of the ray as (distance, face index) sorted by distance
+    //a ray through an edge or vertex shared by several faces is reported once there
+    fn ray_intersect_all(&self, origin: [f32; 3], direction: [f32; 3]) -> PyResult<Vec<(f32, usize)>> {
+        Ok(self.ray_crossings(&Point3::from(origin), &unit_direction(direction)?))
+    }
+
+    //closest point on the surface as (face index, point, barycentric coordinates, distance)
+    //the barycentric coordinates sum to 1 and can be passed to interpolate_at
+    fn closest_point(&self, point: [f32; 3]) -> PyResult<ClosestPoint> {
+        let (face, closest, bary, distance) = self
+            .face_bvh()
+            .closest_point(&Point3::from(point))
+            .ok_or_else(|| MeshGeometryError::new_err("mesh has no faces"))?;
+
+        Ok((face, [closest.x, closest.y, closest.z], bary, distance))
+    }
+
+    //closest_point for every point, in order; the queries share the face BVH and run on
+    //rayon threads with the `parallel` feature, without holding the GIL
+    fn closest_points(&self, py: Python, points: Vec<[f32; 3]>) -> PyResult<Vec<ClosestPoint>> {
+        if self.faces.is_empty() {
+            return Err(MeshGeometryError::new_err("mesh has no faces"));
+        }
 
-            Mesh {
-                vertices,
-                faces,
-                normals,
+        let bvh = self.face_bvh();
+        let closest = |point: &[f32; 3]| {
+            let (face, closest, bary, distance) = bvh.closest_point(&Point3::from(*point)).expect("mesh has faces");
+            (face, [closest.x, closest.y, closest.z], bary, distance)
+        };
+
+        Ok(py.allow_threads(|| {
+            #[cfg(feature = "parallel")]
+            {
+                use rayon::prelude::*;
+                points.par_iter().map(closest).collect()
+            }
+            #[cfg(not(feature = "parallel"))]
+            {
+                points.iter().map(closest).collect()
             }
+        }))
+    }
+
+    //per-vertex ambient occlusion as the fraction of cosine weighted rays over the hemisphere
+    //around the vertex normal that escape the mesh, 1 for fully open vertices
+    //uses the stored normals if there are any, vertices without a normal count as open
+    #[pyo3(signature = (samples, seed=0))]
+    fn ambient_occlusion(&self, py: Python, samples: usize, seed: u64) -> PyResult<Vec<f32>> {
+        if samples == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err("samples must be at least 1"));
         }
+
+        let normals: Vec<Vector3<f32>> = match &self.normals {
+            Some(normals) => normals.iter().map(|n| n.coords.try_normalize(1e-6).unwrap_or_else(Vector3::zeros)).collect(),
+            None => self.averaged_vertex_normals().iter().map(|n| n.coords).collect(),
+        };
+        let (min, max) = self.compute_bounds();
+        //rays start this far off the surface so they can't hit the faces around their vertex
+        let start_offset = (Vector3::from(max) - Vector3::from(min)).norm() * 1e-5;
+        let bvh = self.face_bvh();
+
+        let occlusion = |v: usize| {
+            let normal = normals[v];
+            if normal == Vector3::zeros() {
+                return 1.0;
+            }
+
+            //a generator per vertex keeps the result independent of the evaluation order
+            let mut rng = sampling::SplitMix64::new(seed ^ (v as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+            let origin = Point3::from(self.vertices[v].coords + normal * start_offset);
+            let escaped = (0..samples)
+                .filter(|_| bvh.intersect_ray(&origin, &sampling::cosine_hemisphere(&normal, &mut rng)).is_none())
+                .count();
+            escaped as f32 / samples as f32
+        };
+
+        Ok(py.allow_threads(|| {
+            #[cfg(feature = "parallel")]
+            {
+                use rayon::prelude::*;
+                (0..self.vertices.len()).into_par_iter().map(occlusion).collect()
+            }
+            #[cfg(not(feature = "parallel"))]
+            {
+                (0..self.vertices.len()).map(occlusion).collect()
+            }
+        }))
     }
+
+    //thinnest wall found from random surface samples as (thickness, sample point)
+    //each sample casts a ray against its face normal and measures the distance to the
+    //nearest hit on a face pointing away from it, i.e. the other side of the wall
+    //expects outward winding, samples whose ray escapes or hits a front face are ignored
+    #[pyo3(signature = (samples, seed=0))]
+    fn min_wall_thickness(&self, py: Python, samples: usize, seed: u64) -> PyResult<(f32, [f32; 3])> {
+        let (min, max) = self.compute_bounds();
+        let diagonal = (Vector3::from(max) - Vector3::from(min)).norm();
+        //start rays slightly inside so they can't hit their own face
+        let start_offset = diagonal * 1e-6;
+
+        let bvh = self.face_bvh();
+        let points = sampling::sample_surface(self, samples, seed);
+
+        let thinnest = py.allow_threads(|| {
+            points
+                .iter()
+                .filter_map(|&(face, bary)| {
+                    let [a, b, c] = self.faces[face].map(|v| self.vertices[v].coords);
+                    let point = a * bary[0] + b * bary[1] + c * bary[2];
+                    let inward = -self.face_normal(face);
+                    if inward == Vector3::zeros() {
+                        return None;
+                    }
+
+                    let origin = Point3::from(point + inward * start_offset);
+                    let (distance, hit) = bvh.intersect_ray(&origin, &inward)?;
+                    if self.face_normal(hit).dot(&inward) <= 0.0 {
+                        return None;
+                    }
+
+                    Some((distance + start_offset, point))
+                })
+                .fold(None, |best: Option<(f32, Vector3<f32>)>, candidate| match best {
+                    Some(best) if best.0 <= candidate.0 => Some(best),
+                    _ => Some(candidate),
+                })
+        });
+
+        let (thickness, point) = thinnest.ok_or_else(|| {
+            MeshGeometryError::new_err("no sample reached an opposite wall")
+        })?;
+        Ok((thickness, [point.x, point.y, point.z]))
+    }
+
+    //per-vertex wall thickness: the depth along the inward normal of the opposite side, found
+    //by casting rays into the mesh, the first straight along the normal and the rest spread
+    //over a 30 degree cone; the median depth of the rays that reach a back face is reported,
+    //INFINITY where none does. uses the stored normals if there are any, expects outward winding
+    fn vertex_thickness(&self, py: Python, samples: usize) -> PyResult<Vec<f32>> {
+        if samples == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err("samples must be at least 1"));
+        }
+
+        let normals: Vec<Vector3<f32>> = match &self.normals {
+            Some(normals) => normals.iter().map(|n| n.coords.try_normalize(1e-6).unwrap_or_else(Vector3::zeros)).collect(),
+            None => self.averaged_vertex_normals().iter().map(|n| n.coords).collect(),
+        };
+        let (min, max) = self.compute_bounds();
+        //rays start this far inside so they can't hit the faces around their vertex
+        let start_offset = (Vector3::from(max) - Vector3::from(min)).norm() * 1e-5;
+        let cos_half_angle = 30f32.to_radians().cos();
+        let bvh = self.face_bvh();
+
+        let thickness = |v: usize| {
+            let inward = -normals[v];
+            if inward == Vector3::zeros() {
+                return f32::INFINITY;
+            }
+
+            //a generator per vertex keeps the result independent of the evaluation order
+            let mut rng = sampling::SplitMix64::new((v as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+            let origin = Point3::from(self.vertices[v].coords + inward * start_offset);
+            let mut depths: Vec<f32> = (0..samples)
+                .filter_map(|i| {
+                    let direction = if i == 0 { inward } else { sampling::cone(&inward, cos_half_angle, &mut rng) };
+                    let (distance, hit) = bvh.intersect_ray(&origin, &direction)?;
+                    if self.face_normal(hit).dot(&direction) <= 0.0 {
+                        return None;
+                    }
+                    Some((distance * direction.dot(&inward)) + start_offset)
+                })
+                .collect();
+
+            if depths.is_empty() {
+                return f32::INFINITY;
+            }
+            depths.sort_by(f32::total_cmp);
+            depths[depths.len() / 2]
+        };
+
+        Ok(py.allow_threads(|| {
+            #[cfg(feature = "parallel")]
+            {
+                use rayon::prelude::*;
+                (0..self.vertices.len()).into_par_iter().map(thickness).collect()
+            }
+            #[cfg(not(feature = "parallel"))]
+            {
+                (0..self.vertices.len()).map(thickness).collect()
+            }
+        }))
+    }
+
+    //axis aligned bounding box as (center, half extents)
+    fn compute_aabb(&self) -> ([f32; 3], [f32; 3]) {
+        let (min, max) = self.compute_bounds();
+
+        (
+            [(min[0] + max[0]) * 0.5, (min[1] + max[1]) * 0.5, (min[2] + max[2]) * 0.5],
+            [(max[0] - min[0]) * 0.5, (max[1] - min[1]) * 0.5, (max[2] - min[2]) * 0.5],
+        )
+    }
+
+    //oriented bounding box from PCA of the vertices as (center, axes, half extents)
+    //axes are sorted by decreasing variance, planar input gives a zero-thickness box
+    fn compute_obb(&self) -> ([f32; 3], [[f32; 3]; 3], [f32; 3]) {
+        if self.vertices.is_empty() {
+            return ([0.0; 3], [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]], [0.0; 3]);
+        }
+
+        let (mean, axes) = self.principal_axes();
+
+        let mut min = [f32::INFINITY; 3];
+        let mut max = [f32::NEG_INFINITY; 3];
+
+        for vertex in &self.vertices {
+            let offset = vertex.coords - mean;
+            for i in 0..3 {
+                let t = offset.dot(&axes[i]);
+                min[i] = min[i].min(t);
+                max[i] = max[i].max(t);
+            }
+        }
+
+        let mut center = mean;
+        let mut half_extents = [0.0; 3];
+        for i in 0..3 {
+            center += axes[i] * ((min[i] + max[i]) * 0.5);
+            half_extents[i] = (max[i] - min[i]) * 0.5;
+        }
+
+        (
+            [center.x, center.y, center.z],
+            [
+                [axes[0].x, axes[0].y, axes[0].z],
+                [axes[1].x, axes[1].y, axes[1].z],
+                [axes[2].x, axes[2].y, axes[2].z],
+            ],
+            half_extents,
+        )
+    }
+
+    //rotates the mesh about its vertex mean so the principal axes line up with the world
+    //axes, largest variance along x; each eigenvector's sign is picked so the vertices are
+    //skewed towards its positive side, falling back to its largest component being positive
+    //for symmetric shapes; z follows from x and y so this stays a rotation
+    fn align_principal_axes(&mut self) -> PyResult<()> {
+        if self.vertices.is_empty() {
+            return Ok(());
+        }
+
+        let (mean, mut axes) = self.principal_axes();
+
+        for axis in &mut axes[..2] {
+            let (skew, spread) = self.vertices.iter().fold((0.0f32, 0.0f32), |(skew, spread), v| {
+                let t = (v.coords - mean).dot(axis);
+                (skew + t * t * t, spread + (t * t * t).abs())
+            });
+            let flip = if skew.abs() > spread * 1e-4 {
+                skew < 0.0
+            } else {
+                let largest = (0..3).fold(0, |best, i| if axis[i].abs() > axis[best].abs() { i } else { best });
+                axis[largest] < 0.0
+            };
+            if flip {
+                *axis = -*axis;
+            }
+        }
+        axes[2] = axes[0].cross(&axes[1]);
+
+        let rotation = Matrix3::from_rows(&[axes[0].transpose(), axes[1].transpose(), axes[2].transpose()]);
+        let offset = mean - rotation * mean;
+        let mut matrix = [[0.0, 0.0, 0.0, 1.0]; 4];
+        for r in 0..3 {
+            matrix[r] = [rotation[(r, 0)], rotation[(r, 1)], rotation[(r, 2)], offset[r]];
+        }
+
+        self.transform(matrix)
+    }
+
+    //plane as (point, unit normal) the mesh is mirror symmetric about, within tolerance as
+    //the largest distance of a mirrored vertex from the surface; None if there is none
+    //candidates are the principal axes and the world axes through the vertex mean, the best
+    //one wins; normals have their largest component positive
+    //a negative tolerance is relative to characteristic_length
+    fn detect_symmetry_plane(&self, py: Python, tolerance: f32) -> PyResult<Option<([f32; 3], [f32; 3])>> {
+        if tolerance.is_nan() {
+            return Err(pyo3::exceptions::PyValueError::new_err("tolerance must not be nan"));
+        }
+        let tolerance = self.absolute_tolerance(tolerance);
+        if self.is_empty() {
+            return Ok(None);
+        }
+
+        let (mean, axes) = self.principal_axes();
+        let bvh = self.face_bvh();
+
+        //largest distance of a mirrored vertex from the surface, stopping once over tolerance
+        let deviation = |normal: &Vector3<f32>| {
+            let mut worst = 0.0f32;
+            for vertex in &self.vertices {
+                let offset = (vertex.coords - mean).dot(normal);
+                let mirrored = Point3::from(vertex.coords - normal * (2.0 * offset));
+                let distance = bvh.closest_point(&mirrored).map_or(f32::INFINITY, |(.., d)| d);
+                worst = worst.max(distance);
+                if worst > tolerance {
+                    break;
+                }
+            }
+            worst
+        };
+
+        let best = py.allow_threads(|| {
+            axes.into_iter()
+                .chain([Vector3::x(), Vector3::y(), Vector3::z()])
+                .filter_map(|axis| axis.try_normalize(1e-6))
+                .map(|normal| (deviation(&normal), normal))
+                .filter(|&(distance, _)| distance <= tolerance)
+                .fold(None, |best: Option<(f32, Vector3<f32>)>, candidate| match best {
+                    Some(best) if best.0 <= candidate.0 => Some(best),
+                    _ => Some(candidate),
+                })
+        });
+
+        Ok(best.map(|(_, normal)| {
+            let largest = (0..3).fold(0, |best, i| if normal[i].abs() > normal[best].abs() { i } else { best });
+            let normal = if normal[largest] < 0.0 { -normal } else { normal };
+            ([mean.x, mean.y, mean.z], [normal.x, normal.y, normal.z])
+        }))
+    }
+
+    //mesh in text representation
+    fn __repr__(&self) -> String {
+        format!(
+            "Mesh(vertices={}, faces={}, has_normals={})",
+            self.vertices.len(),
+            self.faces.len(),
+            self.normals.is_some()
+        )
+    }
+
+    //edges no longer than tolerance as sorted (a, b) with a <= b, e.g. between coincident
+    //vertices, which give faces without area and nan normals downstream; a diagnostic that
+    //changes nothing, remove_duplicate_vertices usually fixes them
+    //a negative tolerance is relative to characteristic_length
+    fn degenerate_edges(&self, tolerance: f32) -> Vec<(usize, usize)> {
+        let tolerance = self.absolute_tolerance(tolerance);
+        let mut edges: Vec<(usize, usize)> = self
+            .topology()
+            .edge_to_faces
+            .keys()
+            .copied()
+            .filter(|&(a, b)| (self.vertices[a] - self.vertices[b]).norm() <= tolerance)
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    fn find_isolated_vertices(&self) -> Vec<usize> {
+        let mut is_connected = vec![false; self.vertices.len()];
+
+        for face in &self.faces {
+            is_connected[face[0]] = true;
+            is_connected[face[1]] = true;
+            is_connected[face[2]] = true;
+        }
+
+        is_connected.iter()
+            .enumerate()
+            .filter(|(_, &connected)| !connected)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    //drops vertices no face references and remaps faces, returns how many were removed
+    fn remove_isolated_vertices(&mut self) -> usize {
+        let isolated = self.find_isolated_vertices();
+        if isolated.is_empty() {
+            return 0;
+        }
+
+        let mut keep = vec![true; self.vertices.len()];
+        for &i in &isolated {
+            keep[i] = false;
+        }
+
+        self.compact_vertices(&keep);
+        isolated.len()
+    }
+
+    //merges vertices closer than tolerance into the first one seen, returns how many were merged
+    //a negative tolerance is relative to characteristic_length
+    fn remove_duplicate_vertices(&mut self, tolerance: f32) -> usize {
+        let eligible = vec![true; self.vertices.len()];
+        self.weld_vertices(&eligible, tolerance)
+    }
+
+    //merges every vertex i into remap[i], e.g. from a correspondence computed elsewhere, then
+    //drops the merged vertices with their normals, colors and uvs and renumbers the rest
+    //targets must map to themselves; returns how many vertices were merged
+    //faces left with a repeated vertex are kept, remove_degenerate_faces drops them
+    fn weld_by_map(&mut self, remap: Vec<usize>) -> PyResult<usize> {
+        if remap.len() != self.vertices.len() {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "got {} remap entries for {} vertices",
+                remap.len(),
+                self.vertices.len()
+            )));
+        }
+        for (vertex, &target) in remap.iter().enumerate() {
+            self.check_vertex_index(target)?;
+            if remap[target] != target {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "vertex {} maps to {}, which maps to {} instead of itself",
+                    vertex, target, remap[target]
+                )));
+            }
+        }
+
+        Ok(self.apply_weld_map(&remap))
+    }
+
+    //flips edges towards a Delaunay triangulation where that improves the minimum angle
+    //only interior manifold edges are flipped, returns the number of flips
+    fn improve_by_edge_flips(&mut self, iterations: usize) -> usize {
+        remesh::improve_by_edge_flips(self, iterations)
+    }
+
+    //true if the point is inside the closed surface, by the parity of the crossings of a ray
+    //points exactly on the surface may go either way
+    fn contains_point(&self, point: [f32; 3]) -> bool {
+        //skewed so the ray doesn't run along axis aligned edges and faces
+        let direction = Vector3::new(0.5773, 0.5774, 0.5776).normalize();
+        self.ray_crossings(&Point3::from(point), &direction).len() % 2 == 1
+    }
+
+    //generalized winding number of the surface around the point, about 1 inside and 0 outside
+    //unlike ray parity it degrades gracefully on surfaces with small holes or cracks, e.g. scans
+    fn winding_number(&self, point: [f32; 3]) -> f32 {
+        measure::winding_number(&self.vertices, &self.faces, &Point3::from(point))
+    }
+
+    //contains_point for surfaces that aren't quite closed: inside if the winding number is
+    //above threshold
+    #[pyo3(signature = (point, threshold=0.5))]
+    fn contains_point_robust(&self, point: [f32; 3], threshold: f32) -> bool {
+        self.winding_number(point) > threshold
+    }
+
+    //signed distance from each point to the closed surface, negative inside
+    //the sign comes from ray parity away from the surface and from the angle weighted
+    //pseudonormal of the closest feature close to it, where a parity ray could slip
+    //through thin walls or graze edges
+    fn signed_distance(&self, py: Python, points: Vec<[f32; 3]>) -> PyResult<Vec<f32>> {
+        use crate::mesh::distance::PseudoNormals;
+
+        if self.faces.is_empty() {
+            return Err(MeshGeometryError::new_err("mesh has no faces"));
+        }
+        if !self.topology().is_watertight() {
+            return Err(MeshTopologyError::new_err(
+                "mesh is not watertight, inside and outside are undefined",
+            ));
+        }
+
+        let (min, max) = self.compute_bounds();
+        let near = (Vector3::from(max) - Vector3::from(min)).norm() * 1e-2;
+        let pseudonormals = PseudoNormals::new(self);
+        let bvh = self.face_bvh();
+        //skewed so the ray doesn't run along axis aligned edges and faces
+        let direction = Vector3::new(0.5773, 0.5774, 0.5776).normalize();
+
+        let signed = |point: &[f32; 3]| {
+            let point = Point3::from(*point);
+            let (face, closest, bary, distance) = match bvh.closest_point(&point) {
+                Some(hit) => hit,
+                None => return f32::INFINITY,
+            };
+
+            let inside = if distance < near {
+                pseudonormals.at(self, face, &bary).dot(&(point.coords - closest)) < 0.0
+            } else {
+                self.ray_crossings(&point, &direction).len() % 2 == 1
+            };
+            if inside { -distance } else { distance }
+        };
+
+        Ok(py.allow_threads(|| {
+            #[cfg(feature = "parallel")]
+            {
+                use rayon::prelude::*;
+                points.par_iter().map(signed).collect()
+            }
+            #[cfg(not(feature = "parallel"))]
+            {
+                points.iter().map(signed).collect()
+            }
+        }))
+    }
+
+    //convex hull of the vertices as a closed, outward wound mesh
+    fn convex_hull(&self, py: Python) -> PyResult<Mesh> {
+        let points: Vec<Vector3<f64>> = self.vertices.iter().map(|v| v.coords.cast::<f64>()).collect();
+        py.allow_threads(|| Hull::new(&points))
+            .map(|hull| hull.to_mesh())
+            .ok_or_else(|| MeshGeometryError::new_err("convex hull needs at least 4 vertices that are not coplanar"))
+    }
+
+    //true for a closed, outward wound mesh enclosing the volume of the convex hull of its vertices
+    //comparing volumes instead of testing vertices against face planes keeps thin faces, whose
+    //planes are tilted by rounding, from failing otherwise convex meshes
+    fn is_convex(&self) -> bool {
+        if self.is_empty() || !self.topology().is_watertight() {
+            return false;
+        }
+
+        let points: Vec<Vector3<f64>> = self.vertices.iter().map(|v| v.coords.cast::<f64>()).collect();
+        let hull_volume = match Hull::new(&points) {
+            Some(hull) => hull.volume(),
+            None => return false,
+        };
+        let volume = self
+            .faces
+            .iter()
+            .map(|face| points[face[0]].dot(&points[face[1]].cross(&points[face[2]])))
+            .sum::<f64>()
+            / 6.0;
+
+        volume > 0.0 && hull_volume - volume <= hull_volume * 1e-5
+    }
+
+    //approximate convex decomposition into at most max_hulls convex meshes, splitting until
+    //no hull encloses more than concavity times the mesh volume outside of the mesh
+    //expects a closed mesh, neighbouring pieces meet at the cut planes
+    fn convex_decomposition(&self, py: Python, max_hulls: usize, concavity: f32) -> PyResult<Vec<Mesh>> {
+        if max_hulls == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err("max_hulls must be at least 1"));
+        }
+        if concavity.is_nan() || concavity < 0.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "concavity must be non-negative, got {}",
+                concavity
+            )));
+        }
+
+        py.allow_threads(|| decomposition::convex_decomposition(self, max_hulls, concavity))
+    }
+
+    //centers of the cells inside the mesh of a grid with spacing pitch centered on the bounding
+    //box; expects a closed mesh, cells are classified by counting surface crossings along z
+    //progress is called once per slab of cells along y
+    #[pyo3(signature = (pitch, progress=None))]
+    fn voxelize(&self, py: Python, pitch: f32, progress: Option<PyObject>) -> PyResult<Vec<[f32; 3]>> {
+        if pitch.is_nan() || pitch <= 0.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "pitch must be positive, got {}",
+                pitch
+            )));
+        }
+        if self.faces.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (min, max) = self.compute_bounds();
+        let min = Vector3::from(min).cast::<f64>();
+        let extent = Vector3::from(max).cast::<f64>() - min;
+        let mut progress = Progress::new(progress);
+        let centers = py.allow_threads(|| {
+            decomposition::voxelize(self, &min, &extent, pitch as f64, &mut progress)
+        })?;
+
+        Ok(centers.iter().map(|c| [c.x as f32, c.y as f32, c.z as f32]).collect())
+    }
+
+    //new mesh with edges close to target_edge_length, projected onto this surface
+    //boundary and non-manifold vertices stay fixed; with preserve_normals the stored normals
+    //are carried over from the closest points on this surface, otherwise they are dropped
+    #[pyo3(signature = (target_edge_length, iterations, preserve_normals=true))]
+    fn isotropic_remesh(
+        &self,
+        py: Python,
+        target_edge_length: f32,
+        iterations: usize,
+        preserve_normals: bool,
+    ) -> PyResult<Mesh> {
+        if target_edge_length.is_nan() || target_edge_length <= 0.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "target_edge_length must be positive, got {}",
+                target_edge_length
+            )));
+        }
+
+        Ok(py.allow_threads(|| {
+            let mut remeshed = remesh::isotropic_remesh(self, target_edge_length, iterations);
+            if preserve_normals {
+                remeshed.normals = remeshed.normals_transferred_from(self);
+            }
+            remeshed
+        }))
+    }
+
+    //subdivides faces larger than max_area 1-to-4 until none is left, splitting the faces next to
+    //them as needed so the mesh stays conforming; returns how many faces were added
+    //new vertices sit on edge midpoints with the averaged normals, colors and uvs of the edge's
+    //ends, and the pieces of a face keep its face group
+    fn split_large_faces(&mut self, max_area: f32) -> PyResult<usize> {
+        if max_area.is_nan() || max_area <= 0.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "max_area must be positive, got {}",
+                max_area
+            )));
+        }
+
+        Ok(remesh::split_large_faces(self, max_area))
+    }
+
+    //Loop subdivision, every step splits each face into four and smooths the surface towards
+    //its limit; boundary loops are smoothed as curves of their own
+    //colors and uvs of the new vertices are the averages of their edge's ends, face_groups
+    //pass on to the four faces of every face and stored normals are recomputed
+    //raises MeshTopologyError if two faces share an edge in the same direction
+    //progress is called after every step, cancelling leaves the mesh as it was
+    #[pyo3(signature = (iterations, progress=None))]
+    fn subdivide_loop(&mut self, py: Python, iterations: usize, progress: Option<PyObject>) -> PyResult<()> {
+        let mut half_edges = HalfEdgeMesh::from_mesh(self)?;
+        let mut steps = Vec::with_capacity(iterations);
+        let mut progress = Progress::new(progress);
+        py.allow_threads(|| {
+            for step in 0..iterations {
+                let subdivided = half_edges.loop_subdivision();
+                steps.push(subdivided.edge_ends);
+                half_edges = subdivided.mesh;
+                progress.report((step + 1) as f32 / iterations as f32)?;
+            }
+            progress.finish()
+        })?;
+
+        for edge_ends in &steps {
+            if let Some(colors) = &mut self.colors {
+                for &(a, b) in edge_ends {
+                    colors.push([0, 1, 2].map(|i| 0.5 * (colors[a][i] + colors[b][i])));
+                }
+            }
+            if let Some(uvs) = &mut self.uvs {
+                for &(a, b) in edge_ends {
+                    uvs.push([0.5 * (uvs[a][0] + uvs[b][0]), 0.5 * (uvs[a][1] + uvs[b][1])]);
+                }
+            }
+            if let Some(groups) = &mut self.face_groups {
+                *groups = groups.iter().flat_map(|&g| [g; 4]).collect();
+            }
+        }
+
+        let subdivided = half_edges.to_mesh();
+        self.vertices = subdivided.vertices;
+        self.faces = subdivided.faces;
+        self.geometry_changed();
+
+        if self.normals.is_some() {
+            self.compute_normals()?;
+        }
+        Ok(())
+    }
+
+    //removes faces with repeated vertices or zero area, returns how many were removed
+    fn remove_degenerate_faces(&mut self) -> usize {
+        let vertices = &self.vertices;
+        let keep: Vec<bool> = self.faces
+            .iter()
+            .map(|face| {
+                if face[0] == face[1] || face[1] == face[2] || face[0] == face[2] {
+                    return false;
+                }
+
+                let edge1 = vertices[face[1]] - vertices[face[0]];
+                let edge2 = vertices[face[2]] - vertices[face[0]];
+                edge1.cross(&edge2).norm() > 0.0
+            })
+            .collect();
+
+        let removed = self.retain_faces(&keep);
+        if removed > 0 {
+            self.geometry_changed();
+        }
+        removed
+    }
+
+    //keeps the first of every group of faces over the same three vertices and removes the rest
+    //with drop_opposite, oppositely wound copies cancel in pairs first and a group that cancels
+    //out completely is removed entirely; returns how many faces were removed
+    #[pyo3(signature = (drop_opposite=false))]
+    fn remove_duplicate_faces(&mut self, drop_opposite: bool) -> usize {
+        use crate::analyzers::topology::{duplicate_face_groups, winding_parity};
+
+        let mut keep = vec![true; self.faces.len()];
+        for group in duplicate_face_groups(&self.faces) {
+            let kept = if drop_opposite {
+                let (even, odd): (Vec<usize>, Vec<usize>) =
+                    group.iter().partition(|&&f| winding_parity(&self.faces[f]));
+                //the first face of the more common winding survives if any are left over
+                match even.len().cmp(&odd.len()) {
+                    std::cmp::Ordering::Greater => Some(even[0]),
+                    std::cmp::Ordering::Less => Some(odd[0]),
+                    std::cmp::Ordering::Equal => None,
+                }
+            } else {
+                Some(group[0])
+            };
+
+            for f in group {
+                keep[f] = Some(f) == kept;
+            }
+        }
+
+        let removed = self.retain_faces(&keep);
+        if removed > 0 {
+            self.geometry_changed();
+        }
+        removed
+    }
+
+    //makes neighbouring faces agree on winding and turns closed parts outward
+    //returns false when some part is non-orientable or has non-manifold edges
+    fn orient_consistent(&mut self) -> bool {
+        use std::collections::VecDeque;
+
+        //flipping faces doesn't change which faces share an edge
+        let topology = self.topology();
+        let mut flipped = false;
+
+        let has_directed = |face: &[usize; 3], a: usize, b: usize| {
+            (0..3).any(|i| face[i] == a && face[(i + 1) % 3] == b)
+        };
+
+        let mut consistent = true;
+        let mut visited = vec![false; self.faces.len()];
+
+        for seed in 0..self.faces.len() {
+            if visited[seed] {
+                continue;
+            }
+
+            visited[seed] = true;
+            let mut component = vec![seed];
+            let mut closed = true;
+            let mut queue = VecDeque::from([seed]);
+
+            while let Some(face_idx) = queue.pop_front() {
+                let face = self.faces[face_idx];
+
+                for i in 0..3 {
+                    let (a, b) = (face[i], face[(i + 1) % 3]);
+                    let neighbours = &topology.edge_to_faces[&(a.min(b), a.max(b))];
+
+                    match neighbours.len() {
+                        1 => closed = false,
+                        2 => {}
+                        _ => {
+                            consistent = false;
+                            continue;
+                        }
+                    }
+
+                    for &other in neighbours.iter().filter(|&&other| other != face_idx) {
+                        if !visited[other] {
+                            if has_directed(&self.faces[other], a, b) {
+                                self.faces[other].swap(1, 2);
+                                flipped = true;
+                            }
+                            visited[other] = true;
+                            component.push(other);
+                            queue.push_back(other);
+                        } else if has_directed(&self.faces[other], a, b) {
+                            consistent = false;
+                        }
+                    }
+                }
+            }
+
+            if closed {
+                let volume: f32 = component
+                    .iter()
+                    .map(|&f| {
+                        let face = &self.faces[f];
+                        self.vertices[face[0]].coords.dot(
+                            &self.vertices[face[1]].coords.cross(&self.vertices[face[2]].coords),
+                        )
+                    })
+                    .sum();
+
+                if volume < 0.0 {
+                    for &f in &component {
+                        self.faces[f].swap(1, 2);
+                    }
+                    flipped = true;
+                }
+            }
+        }
+
+        if flipped {
+            self.geometry_changed();
+        }
+        consistent
+    }
+
+    //closes every boundary loop with a triangle fan, returns how many holes were filled
+    fn fill_holes(&mut self) -> usize {
+        let holes = self.topology().find_holes(&self.faces);
+        self.fill_hole_loops(&holes)
+    }
+
+    //like fill_holes but leaves loops with a perimeter above max_perimeter open
+    fn fill_holes_below(&mut self, max_perimeter: f32) -> usize {
+        use crate::analyzers::topology::loop_perimeter;
+
+        let holes: Vec<Vec<usize>> = self.topology()
+            .find_holes(&self.faces)
+            .into_iter()
+            .filter(|hole| loop_perimeter(&self.vertices, hole) <= max_perimeter)
+            .collect();
+        self.fill_hole_loops(&holes)
+    }
+
+    //vertices lying on an open edge they aren't part of, with that edge as (min, max), e.g.
+    //where a CAD export split one side of a seam and not the other; sorted by vertex
+    //the vertex must be within tolerance of the edge and further than that from its ends
+    //a negative tolerance is relative to characteristic_length
+    fn find_t_junctions(&self, tolerance: f32) -> PyResult<Vec<(usize, (usize, usize))>> {
+        if tolerance.is_nan() {
+            return Err(pyo3::exceptions::PyValueError::new_err("tolerance must not be nan"));
+        }
+        Ok(self.t_junctions(tolerance))
+    }
+
+    //splits the face on every edge find_t_junctions reports at the vertices lying on it, so the
+    //two sides of the seam share edges again; the pieces keep the face's winding and group
+    //returns how many vertices were joined in
+    fn fix_t_junctions(&mut self, tolerance: f32) -> PyResult<usize> {
+        if tolerance.is_nan() {
+            return Err(pyo3::exceptions::PyValueError::new_err("tolerance must not be nan"));
+        }
+        Ok(self.split_t_junctions(tolerance))
+    }
+
+    //runs dedup, degenerate and isolated cleanup, orientation and optionally hole filling
+    //tolerance is the dedup one, negative is relative to characteristic_length
+    //returns a dict describing what was changed
+    fn repair(&mut self, py: Python, fill: bool, tolerance: f32) -> PyResult<PyObject> {
+        use pyo3::types::PyDict;
+
+        let vertices_merged = self.remove_duplicate_vertices(tolerance);
+        let faces_removed = self.remove_degenerate_faces();
+        let isolated_removed = self.remove_isolated_vertices();
+        let mut oriented = self.orient_consistent();
+
+        let holes_filled = if fill { self.fill_holes() } else { 0 };
+        if holes_filled > 0 {
+            oriented = self.orient_consistent();
+        }
+
+        let topology = self.topology();
+        let watertight = topology.is_watertight();
+        let open_holes = topology.find_holes(&self.faces).len();
+
+        let report = PyDict::new(py);
+        report.set_item("vertices_merged", vertices_merged)?;
+        report.set_item("degenerate_faces_removed", faces_removed)?;
+        report.set_item("isolated_vertices_removed", isolated_removed)?;
+        report.set_item("holes_filled", holes_filled)?;
+        report.set_item("orientation_consistent", oriented)?;
+        report.set_item("watertight", watertight)?;
+        report.set_item("open_holes", open_holes)?;
+
+        Ok(report.into())
+    }
+
+    //summary of the mesh as a dict of plain Python values, e.g. for JSON logs
+    //volume is None unless the mesh is watertight
+    fn stats(&self, py: Python) -> PyResult<PyObject> {
+        use pyo3::types::PyDict;
+
+        let watertight = self.topology().is_watertight();
+        let volume = if watertight { Some(self.compute_volume()) } else { None };
+
+        let stats = PyDict::new(py);
+        stats.set_item("vertex_count", self.vertex_count())?;
+        stats.set_item("face_count", self.face_count())?;
+        stats.set_item("surface_area", self.compute_surface_area())?;
+        stats.set_item("volume", volume)?;
+        stats.set_item("bounds", self.compute_bounds())?;
+        stats.set_item("centroid", self.centroid())?;
+        stats.set_item("has_normals", self.normals.is_some())?;
+        stats.set_item("has_colors", self.colors.is_some())?;
+        stats.set_item("has_uvs", self.uvs.is_some())?;
+        stats.set_item("watertight", watertight)?;
+
+        Ok(stats.into())
+    }
+
+    //how b differs from a as a dict of b - a deltas, e.g. to check what a processing step did
+    //volume deltas are None unless both are watertight, max_displacement compares the
+    //vertices both meshes have by index; topology_changed flags a different euler
+    //characteristic or component count
+    #[staticmethod]
+    fn diff(py: Python, a: &Mesh, b: &Mesh) -> PyResult<PyObject> {
+        use pyo3::types::PyDict;
+
+        let euler = |mesh: &Mesh| {
+            let topology = mesh.topology();
+            topology.vertex_to_faces.len() as i64 - topology.edge_to_faces.len() as i64 + mesh.faces.len() as i64
+        };
+        let volume = |mesh: &Mesh| mesh.topology().is_watertight().then(|| mesh.compute_volume());
+        let ((min_a, max_a), (min_b, max_b)) = (a.compute_bounds(), b.compute_bounds());
+
+        let volume_delta = volume(a).zip(volume(b)).map(|(va, vb)| (vb - va, va));
+        let max_displacement = a.vertices
+            .iter()
+            .zip(&b.vertices)
+            .map(|(va, vb)| (vb - va).norm())
+            .fold(0.0f32, f32::max);
+        let euler_delta = euler(b) - euler(a);
+        let component_delta = b.component_count() as i64 - a.component_count() as i64;
+
+        let diff = PyDict::new(py);
+        diff.set_item("vertex_count_delta", b.vertices.len() as i64 - a.vertices.len() as i64)?;
+        diff.set_item("face_count_delta", b.faces.len() as i64 - a.faces.len() as i64)?;
+        diff.set_item("bounds_min_delta", [0, 1, 2].map(|i| min_b[i] - min_a[i]))?;
+        diff.set_item("bounds_max_delta", [0, 1, 2].map(|i| max_b[i] - max_a[i]))?;
+        diff.set_item("volume_delta", volume_delta.map(|(delta, _)| delta))?;
+        //relative to a's volume, None if that is zero
+        diff.set_item(
+            "volume_relative_delta",
+            volume_delta.and_then(|(delta, va)| (va != 0.0).then(|| delta / va.abs())),
+        )?;
+        diff.set_item("max_displacement", max_displacement)?;
+        diff.set_item("euler_characteristic_delta", euler_delta)?;
+        diff.set_item("component_count_delta", component_delta)?;
+        diff.set_item("topology_changed", euler_delta != 0 || component_delta != 0)?;
+
+        Ok(diff.into())
+    }
+
+    //quadric error edge-collapse decimation down to at most target_faces faces
+    //deterministic, equal costs are resolved by vertex indices so repeated runs match exactly
+    //preserve_boundary and preserve_features pin open boundaries and edges sharper than
+    //feature_angle_deg so collapses only slide along them
+    //with preserve_normals the stored normals are carried over from the closest points on
+    //this surface, otherwise the result has none
+    #[pyo3(signature = (target_faces, progress=None, preserve_boundary=false, preserve_features=false, feature_angle_deg=30.0, preserve_normals=true))]
+    #[allow(clippy::too_many_arguments)]
+    fn simplify(
+        &self,
+        py: Python,
+        target_faces: usize,
+        progress: Option<PyObject>,
+        preserve_boundary: bool,
+        preserve_features: bool,
+        feature_angle_deg: f32,
+        preserve_normals: bool,
+    ) -> PyResult<Mesh> {
+        let options = simplification::SimplifyOptions {
+            preserve_boundary,
+            feature_angle_deg: preserve_features.then_some(feature_angle_deg),
+        };
+        let mut progress = Progress::new(progress);
+        py.allow_threads(|| {
+            let mut simplified = simplification::simplify(self, target_faces, &options, &mut progress)?;
+            if preserve_normals {
+                simplified.normals = simplified.normals_transferred_from(self);
+            }
+            Ok(simplified)
+        })
+    }
+
+    //quadric error and merged position simplify would use to collapse edge (a, b), without
+    //changing the mesh; preserve_boundary adds the same boundary constraints as in simplify
+    #[pyo3(signature = (a, b, preserve_boundary=false))]
+    fn collapse_cost(&self, a: usize, b: usize, preserve_boundary: bool) -> PyResult<(f32, [f32; 3])> {
+        let (cost, position) = self.edge_simplifier(a, b, preserve_boundary)?.collapse_cost(a, b);
+        Ok((cost as f32, position.cast::<f32>().into()))
+    }
+
+    //collapses edge (a, b) like one step of simplify: a moves to the collapse_cost position and
+    //takes over b's faces, the faces on the edge are removed and b is left isolated, so vertex
+    //indices stay valid; stored normals of a and its neighbours are recomputed
+    //raises MeshTopologyError for collapses simplify would skip, with the reason
+    #[pyo3(signature = (a, b, preserve_boundary=false))]
+    fn collapse_edge(&mut self, a: usize, b: usize, preserve_boundary: bool) -> PyResult<()> {
+        let mut simplifier = self.edge_simplifier(a, b, preserve_boundary)?;
+        simplifier.try_collapse(a, b).map_err(|reason| {
+            MeshTopologyError::new_err(format!("cannot collapse edge ({}, {}): {}", a, b, reason))
+        })?;
+        simplifier.apply_to(self);
+
+        if self.normals.is_some() {
+            let mut ring: Vec<usize> = self.vertex_faces()[a].iter().flat_map(|&f| self.faces[f]).collect();
+            ring.sort();
+            ring.dedup();
+            self.recompute_normals_for(ring)?;
+        }
+        Ok(())
+    }
+
+    //groups of faces connected through shared vertices, each sorted, ordered by first face
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let mut sets = self.vertex_sets();
+        let mut component_of_root = vec![usize::MAX; self.vertices.len()];
+        let mut components: Vec<Vec<usize>> = Vec::new();
+
+        for (face_idx, face) in self.faces.iter().enumerate() {
+            let root = sets.find(face[0]);
+            if component_of_root[root] == usize::MAX {
+                component_of_root[root] = components.len();
+                components.push(Vec::new());
+            }
+            components[component_of_root[root]].push(face_idx);
+        }
+
+        components
+    }
+
+    //number of connected_components without building the face lists
+    fn component_count(&self) -> usize {
+        let sets = self.vertex_sets();
+        //vertices no face uses are singleton sets but not components
+        sets.set_count() - self.find_isolated_vertices().len()
+    }
+
+    //for every connected component, in connected_components order, the other components
+    //inside it, tested with contains_point on their first vertex; nesting is reported at
+    //every level, so a shell inside a shell inside a third is inside both outer ones
+    //components that aren't watertight have no inside and contain nothing
+    fn nested_shells(&self) -> Vec<(usize, Vec<usize>)> {
+        let components = self.connected_components();
+        let watertight = self.topology().watertight_components(&components, self.faces.len());
+        //skewed so the ray doesn't run along axis aligned edges and faces
+        let direction = Vector3::new(0.5773, 0.5774, 0.5776).normalize();
+
+        components
+            .iter()
+            .enumerate()
+            .map(|(c, faces)| {
+                if !watertight[c] {
+                    return (c, Vec::new());
+                }
+
+                let shell = Mesh {
+                    vertices: self.vertices.clone(),
+                    faces: faces.iter().map(|&f| self.faces[f]).collect(),
+                    normals: None,
+                    ..Default::default()
+                };
+                let inside = (0..components.len())
+                    .filter(|&other| other != c)
+                    .filter(|&other| {
+                        let sample = self.vertices[self.faces[components[other][0]][0]];
+                        shell.ray_crossings(&sample, &direction).len() % 2 == 1
+                    })
+                    .collect();
+                (c, inside)
+            })
+            .collect()
+    }
+
+    //true if any vertex coordinate is inf or nan
+    fn has_non_finite(&self) -> bool {
+        self.vertices.iter().any(|v| !v.coords.iter().all(|c| c.is_finite()))
+    }
+
+    //removes vertices with inf or nan coordinates together with every face using them
+    //returns how many vertices were removed
+    fn sanitize(&mut self) -> usize {
+        let keep: Vec<bool> = self.vertices
+            .iter()
+            .map(|v| v.coords.iter().all(|c| c.is_finite()))
+            .collect();
+
+        let removed = keep.iter().filter(|&&k| !k).count();
+        if removed == 0 {
+            return 0;
+        }
+
+        let keep_faces: Vec<bool> = self.faces.iter().map(|face| face.iter().all(|&v| keep[v])).collect();
+        self.retain_faces(&keep_faces);
+        self.compact_vertices(&keep);
+        removed
+    }
+
+    //new mesh made of the given faces and only the vertices they use
+    //repeated face indices are kept once, in order of first appearance
+    fn submesh(&self, face_indices: Vec<usize>) -> PyResult<Mesh> {
+        use std::collections::{HashMap, HashSet};
+
+        let mut seen = HashSet::new();
+        let mut remap: HashMap<usize, usize> = HashMap::new();
+        let mut vertices = Vec::new();
+        let mut normals = self.normals.as_ref().map(|_| Vec::new());
+        let mut colors = self.colors.as_ref().map(|_| Vec::new());
+        let mut uvs = self.uvs.as_ref().map(|_| Vec::new());
+        let mut face_groups = self.face_groups.as_ref().map(|_| Vec::new());
+        let mut faces = Vec::new();
+
+        for face_idx in face_indices {
+            let face = self.faces.get(face_idx).ok_or_else(|| {
+                pyo3::exceptions::PyIndexError::new_err(format!(
+                    "face index {} out of range for mesh with {} faces",
+                    face_idx,
+                    self.faces.len()
+                ))
+            })?;
+
+            if !seen.insert(face_idx) {
+                continue;
+            }
+
+            let mut new_face = [0; 3];
+            for (corner, &vertex) in face.iter().enumerate() {
+                new_face[corner] = *remap.entry(vertex).or_insert_with(|| {
+                    vertices.push(self.vertices[vertex]);
+                    if let (Some(normals), Some(source)) = (&mut normals, &self.normals) {
+                        normals.push(source[vertex]);
+                    }
+                    if let (Some(colors), Some(source)) = (&mut colors, &self.colors) {
+                        colors.push(source[vertex]);
+                    }
+                    if let (Some(uvs), Some(source)) = (&mut uvs, &self.uvs) {
+                        uvs.push(source[vertex]);
+                    }
+                    vertices.len() - 1
+                });
+            }
+            faces.push(new_face);
+            if let (Some(face_groups), Some(source)) = (&mut face_groups, &self.face_groups) {
+                face_groups.push(source[face_idx]);
+            }
+        }
+
+        Ok(Mesh {
+            vertices,
+            faces,
+            normals,
+            colors,
+            uvs,
+            group_names: if face_groups.is_some() { self.group_names.clone() } else { Vec::new() },
+            face_groups,
+            ..Default::default()
+        })
+    }
+
+    //sparse cotangent Laplacian as ((row, col) pairs, values), symmetric with zero row sums
+    //edges shared by more than two faces are left out with a warning
+    fn cotangent_laplacian(&self, py: Python) -> PyResult<SparseMatrix> {
+        let weights = laplacian::cotangent_weights(self);
+
+        if weights.non_manifold_edges > 0 {
+            PyErr::warn(
+                py,
+                py.get_type::<pyo3::exceptions::PyRuntimeWarning>(),
+                &format!(
+                    "skipped {} non-manifold edges in the cotangent Laplacian",
+                    weights.non_manifold_edges
+                ),
+                0,
+            )?;
+        }
+
+        Ok(laplacian::cotangent_matrix(&weights, self.vertices.len()))
+    }
+
+    //diagonal of the lumped mass matrix, the mixed Voronoi area of every vertex
+    fn mass_matrix(&self) -> Vec<f32> {
+        laplacian::mixed_areas(self)
+    }
+
+    //distance from source to every vertex along mesh edges, infinite where unreachable
+    fn geodesic_distances(&self, source: usize) -> PyResult<Vec<f32>> {
+        self.check_vertex_index(source)?;
+        Ok(geodesic::dijkstra(self, &[source], None).distances)
+    }
+
+    //vertices along the shortest edge path from source to target, inclusive
+    //empty when they are in different connected components
+    fn shortest_path(&self, source: usize, target: usize) -> PyResult<Vec<usize>> {
+        self.check_vertex_index(source)?;
+        self.check_vertex_index(target)?;
+        Ok(geodesic::dijkstra(self, &[source], Some(target)).path_to(target))
+    }
+
+    //length of the shortest edge path from source to target, None if there is none
+    fn path_length(&self, source: usize, target: usize) -> PyResult<Option<f32>> {
+        self.check_vertex_index(source)?;
+        self.check_vertex_index(target)?;
+
+        let distance = geodesic::dijkstra(self, &[source], Some(target)).distances[target];
+        Ok(if distance.is_finite() { Some(distance) } else { None })
+    }
+
+    //count vertices spread over the surface by edge-path distance, starting with start and
+    //then always taking the vertex farthest from those taken so far (lowest index on ties)
+    //unreachable vertices count as farthest, so other components get samples early
+    fn farthest_point_sampling(&self, count: usize, start: usize) -> PyResult<Vec<usize>> {
+        self.check_vertex_index(start)?;
+        if count > self.vertices.len() {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "cannot take {} samples from {} vertices",
+                count,
+                self.vertices.len()
+            )));
+        }
+
+        let mut samples = Vec::with_capacity(count);
+        let mut sampled = vec![false; self.vertices.len()];
+        //distance from every vertex to the nearest sample so far, lowered one sample at a time
+        //and compared in single precision like geodesic_distances
+        let mut nearest = geodesic::NearestSource::new(self);
+        let mut next = start;
+        while samples.len() < count {
+            samples.push(next);
+            sampled[next] = true;
+            if samples.len() == count {
+                break;
+            }
+
+            nearest.add(next);
+            let distance = |v: usize| nearest.distances[v] as f32;
+            next = (0..self.vertices.len())
+                .filter(|&v| !sampled[v])
+                .fold(None, |best: Option<usize>, v| match best {
+                    Some(b) if distance(b) >= distance(v) => Some(b),
+                    _ => Some(v),
+                })
+                .expect("count is at most the vertex count");
+        }
+
+        Ok(samples)
+    }
+
+    //per-vertex angle deficit, the Gaussian curvature integrated over the vertex's area
+    //positive on convex and negative on saddle-shaped regions, zero for unused vertices
+    //skip_degenerate leaves out faces with an edge no longer than it, see degenerate_edges
+    #[pyo3(signature = (skip_degenerate=None))]
+    fn gaussian_curvature(&self, skip_degenerate: Option<f32>) -> Vec<f32> {
+        let mesh = self.skipping_degenerate(skip_degenerate);
+        laplacian::angle_deficits(&mesh).into_iter().map(|k| k as f32).collect()
+    }
+
+    //sum of the angle deficits, 2 pi (2 - 2 genus) for a closed connected surface
+    #[pyo3(signature = (skip_degenerate=None))]
+    fn total_gaussian_curvature(&self, skip_degenerate: Option<f32>) -> f32 {
+        let mesh = self.skipping_degenerate(skip_degenerate);
+        laplacian::angle_deficits(&mesh).into_iter().sum::<f64>() as f32
+    }
+
+    //per-vertex mean curvature normal, length is the mean curvature, direction inward where convex
+    //zero on boundary vertices; skip_degenerate works like for gaussian_curvature
+    #[pyo3(signature = (skip_degenerate=None))]
+    fn mean_curvature_normals(&self, skip_degenerate: Option<f32>) -> Vec<[f32; 3]> {
+        let mesh = self.skipping_degenerate(skip_degenerate);
+        laplacian::mean_curvature_normals(&mesh)
+            .into_iter()
+            .map(|n| [n.x, n.y, n.z])
+            .collect()
+    }
+
+    //count area-uniform random surface points as (positions, face indices, barycentric
+    //coordinates), identical for the same seed; face and barycentrics can go to interpolate_at
+    //meshes without surface area give empty lists
+    #[pyo3(signature = (count, seed=0))]
+    fn sample_surface_indexed(&self, count: usize, seed: u64) -> (Vec<[f32; 3]>, Vec<usize>, Vec<[f32; 3]>) {
+        let samples = sampling::sample_surface(self, count, seed);
+
+        let positions = samples
+            .iter()
+            .map(|&(face, bary)| {
+                let [a, b, c] = self.faces[face].map(|v| self.vertices[v].coords);
+                let p = a * bary[0] + b * bary[1] + c * bary[2];
+                [p.x, p.y, p.z]
+            })
+            .collect();
+        let (faces, barycentrics) = samples.into_iter().unzip();
+
+        (positions, faces, barycentrics)
+    }
+
+    //copies the source's per-vertex attributes onto this mesh, each vertex taking the value
+    //interpolated at the closest point on the source surface, e.g. after remeshing a copy
+    //attributes the source doesn't have are skipped, returns the names of those transferred
+    //out of "normals", "colors" and "uvs"
+    fn transfer_attributes(&mut self, py: Python, source: &Mesh) -> PyResult<Vec<String>> {
+        if source.faces.is_empty() {
+            return Err(MeshGeometryError::new_err("source mesh has no faces"));
+        }
+
+        let (normals, colors, uvs) = py.allow_threads(|| {
+            let colors = source.colors.as_ref().and_then(|source_colors| {
+                self.transferred_from(source, |corners, bary| {
+                    [0, 1, 2].map(|c| (0..3).map(|i| source_colors[corners[i]][c] * bary[i]).sum())
+                })
+            });
+            let uvs = source.uvs.as_ref().and_then(|source_uvs| {
+                self.transferred_from(source, |corners, bary| {
+                    [0, 1].map(|c| (0..3).map(|i| source_uvs[corners[i]][c] * bary[i]).sum())
+                })
+            });
+            (self.normals_transferred_from(source), colors, uvs)
+        });
+
+        let mut transferred = Vec::new();
+        if let Some(normals) = normals {
+            self.normals = Some(normals);
+            transferred.push("normals".to_string());
+        }
+        if let Some(colors) = colors {
+            self.colors = Some(colors);
+            transferred.push("colors".to_string());
+        }
+        if let Some(uvs) = uvs {
+            self.uvs = Some(uvs);
+            transferred.push("uvs".to_string());
+        }
+
+        Ok(transferred)
+    }
+
+    //position and (if stored) unit normal at barycentric coordinates inside a face
+    fn interpolate_at(&self, face: usize, bary: [f32; 3]) -> PyResult<([f32; 3], Option<[f32; 3]>)> {
+        let indices = self.faces.get(face).ok_or_else(|| {
+            pyo3::exceptions::PyIndexError::new_err(format!(
+                "face index {} out of range for mesh with {} faces",
+                face,
+                self.faces.len()
+            ))
+        })?;
+
+        let total: f32 = bary.iter().sum();
+        if (total - 1.0).abs() > 1e-4 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "barycentric weights must sum to 1, got {}",
+                total
+            )));
+        }
+
+        let blend = |points: &[Point3<f32>]| -> Vector3<f32> {
+            points[indices[0]].coords * bary[0]
+                + points[indices[1]].coords * bary[1]
+                + points[indices[2]].coords * bary[2]
+        };
+
+        let position = blend(&self.vertices);
+        let normal = self.normals.as_ref().map(|normals| {
+            let n = blend(normals).try_normalize(0.0).unwrap_or_else(Vector3::zeros);
+            [n.x, n.y, n.z]
+        });
+
+        Ok(([position.x, position.y, position.z], normal))
+    }
+
+    //union of two watertight meshes
+    #[staticmethod]
+    fn union(a: &Mesh, b: &Mesh) -> PyResult<Mesh> {
+        boolean::union(a, b)
+    }
+
+    //part of two watertight meshes inside both of them
+    #[staticmethod]
+    fn intersection(a: &Mesh, b: &Mesh) -> PyResult<Mesh> {
+        boolean::intersection(a, b)
+    }
+
+    //watertight mesh a with the volume of b cut away
+    #[staticmethod]
+    fn difference(a: &Mesh, b: &Mesh) -> PyResult<Mesh> {
+        boolean::difference(a, b)
+    }
+
+    //reads the vertices/faces (and vertex_normals if present) arrays of a trimesh.Trimesh
+    //or any object with the same attributes, numpy arrays are copied through the buffer protocol
+    #[staticmethod]
+    fn from_trimesh(obj: &PyAny) -> PyResult<Mesh> {
+        use pyo3::exceptions::{PyIndexError, PyTypeError};
+
+        let attribute = |name: &str| {
+            obj.getattr(name).map_err(|_| {
+                PyTypeError::new_err(format!(
+                    "expected a trimesh.Trimesh-like object with a '{}' attribute, got '{}'",
+                    name,
+                    obj.get_type().name().unwrap_or("?")
+                ))
+            })
+        };
+        let shape_error = |name: &str| PyTypeError::new_err(format!("'{}' must be an (N, 3) array", name));
+
+        let vertices_attr = attribute("vertices")?;
+        let vertices: Vec<[f32; 3]> = match buffer_rows::<f64, f32>(vertices_attr, |x| x as f32)
+            .or_else(|| buffer_rows::<f32, f32>(vertices_attr, |x| x))
+        {
+            Some(rows) => rows,
+            None => vertices_attr.extract().map_err(|_| shape_error("vertices"))?,
+        };
+
+        let faces_attr = attribute("faces")?;
+        let faces: Vec<[i64; 3]> = match buffer_rows::<i64, i64>(faces_attr, |x| x)
+            .or_else(|| buffer_rows::<i32, i64>(faces_attr, |x| x as i64))
+        {
+            Some(rows) => rows,
+            None => faces_attr.extract().map_err(|_| shape_error("faces"))?,
+        };
+
+        let faces = faces
+            .into_iter()
+            .map(|face| {
+                if face.iter().any(|&i| i < 0 || i as usize >= vertices.len()) {
+                    return Err(PyIndexError::new_err(format!(
+                        "face {:?} references a vertex outside 0..{}",
+                        face,
+                        vertices.len()
+                    )));
+                }
+                Ok([face[0] as usize, face[1] as usize, face[2] as usize])
+            })
+            .collect::<PyResult<Vec<[usize; 3]>>>()?;
+
+        let normals = match obj.getattr("vertex_normals") {
+            Ok(attr) => buffer_rows::<f64, f32>(attr, |x| x as f32)
+                .or_else(|| buffer_rows::<f32, f32>(attr, |x| x))
+                .or_else(|| attr.extract::<Vec<[f32; 3]>>().ok())
+                .filter(|normals| normals.len() == vertices.len()),
+            Err(_) => None,
+        };
+
+        Mesh::new(vertices, faces, normals)
+    }
+
+    //builds a mesh from flat xyz positions and triangle indices, the glTF / GPU buffer layout
+    //normals are flat xyz too, one per position
+    #[staticmethod]
+    #[pyo3(signature = (positions, indices, normals=None))]
+    fn from_buffers(positions: Vec<f32>, indices: Vec<u32>, normals: Option<Vec<f32>>) -> PyResult<Mesh> {
+        if positions.len() % 3 != 0 || indices.len() % 3 != 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "buffer lengths must be multiples of 3, got {} positions and {} indices",
+                positions.len(),
+                indices.len()
+            )));
+        }
+
+        let vertex_count = positions.len() / 3;
+        if let Some(&index) = indices.iter().find(|&&i| i as usize >= vertex_count) {
+            return Err(pyo3::exceptions::PyIndexError::new_err(format!(
+                "index {} out of range for {} vertices",
+                index,
+                vertex_count
+            )));
+        }
+        if let Some(normals) = &normals {
+            if normals.len() != positions.len() {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "got {} normal components for {} position components",
+                    normals.len(),
+                    positions.len()
+                )));
+            }
+        }
+
+        let rows = |buffer: &[f32]| buffer.chunks_exact(3).map(|p| Point3::new(p[0], p[1], p[2])).collect();
+        Ok(Mesh {
+            vertices: rows(&positions),
+            faces: indices
+                .chunks_exact(3)
+                .map(|f| [f[0] as usize, f[1] as usize, f[2] as usize])
+                .collect(),
+            normals: normals.as_deref().map(rows),
+            ..Default::default()
+        })
+    }
+
+    //(positions, indices) as from_buffers takes them
+    fn to_buffers(&self) -> PyResult<(Vec<f32>, Vec<u32>)> {
+        if self.vertices.len() > u32::MAX as usize {
+            return Err(MeshError::new_err("buffers store 32-bit indices, mesh has too many vertices"));
+        }
+
+        let positions = self.vertices.iter().flat_map(|v| [v.x, v.y, v.z]).collect();
+        let indices = self.faces.iter().flatten().map(|&i| i as u32).collect();
+        Ok((positions, indices))
+    }
+
+    //converts to a trimesh.Trimesh without letting trimesh merge or reorder anything
+    fn to_trimesh(&self, py: Python) -> PyResult<PyObject> {
+        use pyo3::types::{PyBytes, PyDict};
+
+        let numpy = py.import("numpy")?;
+        let trimesh = py.import("trimesh")?;
+
+        let array = |bytes: Vec<u8>, dtype: &str| -> PyResult<&PyAny> {
+            numpy
+                .call_method1("frombuffer", (PyBytes::new(py, &bytes), dtype))?
+                .call_method1("reshape", (-1, 3))
+        };
+
+        let vertex_bytes: Vec<u8> = self.vertices
+            .iter()
+            .flat_map(|v| [v.x as f64, v.y as f64, v.z as f64])
+            .flat_map(f64::to_le_bytes)
+            .collect();
+        let face_bytes: Vec<u8> = self.faces
+            .iter()
+            .flat_map(|f| [f[0] as i64, f[1] as i64, f[2] as i64])
+            .flat_map(i64::to_le_bytes)
+            .collect();
+
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("vertices", array(vertex_bytes, "<f8")?)?;
+        kwargs.set_item("faces", array(face_bytes, "<i8")?)?;
+        if let Some(normals) = &self.normals {
+            let normal_bytes: Vec<u8> = normals
+                .iter()
+                .flat_map(|n| [n.x as f64, n.y as f64, n.z as f64])
+                .flat_map(f64::to_le_bytes)
+                .collect();
+            kwargs.set_item("vertex_normals", array(normal_bytes, "<f8")?)?;
+        }
+        kwargs.set_item("process", false)?;
+
+        Ok(trimesh.getattr("Trimesh")?.call((), Some(kwargs))?.into())
+    }
+
+    //double precision copy of the vertices and faces, see MeshF64
+    fn to_f64(&self) -> MeshF64 {
+        MeshF64 {
+            vertices: self.vertices.iter().map(|v| v.cast::<f64>()).collect(),
+            faces: self.faces.clone(),
+        }
+    }
+
+    //saves mesh to .obj file, precision fixes the number of decimals written for each coordinate,
+    //by default every value is written with as many digits as it takes to read back exactly
+    //like every save_* it writes gzip when the filename ends in .gz
+    //face groups are written as usemtl statements
+    #[pyo3(signature = (filename, precision=None))]
+    fn save_obj(&self, filename: &str, precision: Option<usize>) -> PyResult<()> {
+        use std::io::{BufWriter, Write};
+
+        text::check_finite(self)?;
+
+        let mut writer = BufWriter::new(gzip::create(filename)?);
+
+        for v in &self.vertices {
+            write!(writer, "v ")?;
+            text::write_floats(&mut writer, &[v.x, v.y, v.z], precision)?;
+            writeln!(writer)?;
+        }
+
+        if let Some(normals) = &self.normals {
+            for n in normals {
+                write!(writer, "vn ")?;
+                text::write_floats(&mut writer, &[n.x, n.y, n.z], precision)?;
+                writeln!(writer)?;
+            }
+        }
+
+        //a usemtl line wherever the group changes, so from_obj reads the same groups back
+        let mut current_group = None;
+        for (face_idx, face) in self.faces.iter().enumerate() {
+            if let Some(groups) = &self.face_groups {
+                let group = groups[face_idx];
+                if current_group != Some(group) {
+                    writeln!(writer, "usemtl {}", self.group_names[group as usize])?;
+                    current_group = Some(group);
+                }
+            }
+
+            if self.normals.is_some() {
+                writeln!(
+                    writer,
+                    "f {}//{} {}//{} {}//{}",
+                    face[0] + 1, face[0] + 1,
+                    face[1] + 1, face[1] + 1,
+                    face[2] + 1, face[2] + 1,
+                )?;
+            } else {
+                writeln!(
+                    writer,
+                    "f {} {} {}",
+                    face[0] + 1, face[1] + 1, face[2] + 1
+                )?;
+            }
+        }
+
+        gzip::finish(writer)?;
+        Ok(())
+    }
+
+    //saves the vertices and every edge once as an `l a b` line element, e.g. to inspect holes
+    //in a viewer; group_boundary puts the edges in groups "interior", "boundary" (one face)
+    //and "non_manifold" (more than two faces) so they can be shown separately
+    #[pyo3(signature = (filename, group_boundary=false, precision=None))]
+    fn save_wireframe_obj(&self, filename: &str, group_boundary: bool, precision: Option<usize>) -> PyResult<()> {
+        use std::io::{BufWriter, Write};
+
+        text::check_finite(self)?;
+
+        let topology = self.topology();
+        let mut edges: Vec<((usize, usize), usize)> = topology
+            .edge_to_faces
+            .iter()
+            .map(|(&edge, faces)| (edge, faces.len()))
+            .collect();
+        edges.sort_unstable();
+
+        let mut writer = BufWriter::new(gzip::create(filename)?);
+
+        for v in &self.vertices {
+            write!(writer, "v ")?;
+            text::write_floats(&mut writer, &[v.x, v.y, v.z], precision)?;
+            writeln!(writer)?;
+        }
+
+        if group_boundary {
+            let group = |faces: usize| match faces {
+                1 => "boundary",
+                2 => "interior",
+                _ => "non_manifold",
+            };
+            for name in ["interior", "boundary", "non_manifold"] {
+                let mut members = edges.iter().filter(|&&(_, faces)| group(faces) == name).peekable();
+                if members.peek().is_some() {
+                    writeln!(writer, "g {}", name)?;
+                }
+                for ((a, b), _) in members {
+                    writeln!(writer, "l {} {}", a + 1, b + 1)?;
+                }
+            }
+        } else {
+            for ((a, b), _) in &edges {
+                writeln!(writer, "l {} {}", a + 1, b + 1)?;
+            }
+        }
+
+        gzip::finish(writer)?;
+        Ok(())
+    }
+
+    //saves mesh to .ply file, binary little endian by default
+    //precision fixes the decimals of ascii files like it does for save_obj
+    #[pyo3(signature = (filename, binary=true, precision=None))]
+    fn save_ply(&self, filename: &str, binary: bool, precision: Option<usize>) -> PyResult<()> {
+        ply::save(self, filename, binary, precision)
+    }
+
+    //saves mesh as a legacy ASCII .vtk file for ParaView, with stored normals as point data
+    //precision fixes the decimals like it does for save_obj
+    #[pyo3(signature = (filename, precision=None))]
+    fn save_vtk(&self, filename: &str, precision: Option<usize>) -> PyResult<()> {
+        vtk::save(self, filename, None, precision)
+    }
+
+    //save_vtk with a named scalar field to color by, e.g. curvature or thickness; one value
+    //per vertex is written as point data, one per face as cell data
+    #[pyo3(signature = (filename, name, values, precision=None))]
+    fn save_vtk_with_scalars(&self, filename: &str, name: &str, values: Vec<f32>, precision: Option<usize>) -> PyResult<()> {
+        vtk::save(self, filename, Some((name, &values)), precision)
+    }
+
+    //saves mesh in the raw binary format, the fastest to reload and lossless
+    fn save_bin(&self, filename: &str) -> PyResult<()> {
+        bin::save(self, filename)
+    }
+
+    #[staticmethod]
+    fn load_bin(filename: &str) -> PyResult<Mesh> {
+        bin::load(filename)
+    }
+
+    //loads an .obj file, polygons are fan triangulated; gzip compressed files are decompressed
+    //reject_non_finite turns inf/nan coordinates into a MeshParseError instead of loading them
+    //`usemtl` statements become face_groups, the last one before a face names its group; `g` is skipped
+    #[staticmethod]
+    #[pyo3(signature = (filename, reject_non_finite=false))]
+    fn from_obj(filename: &str, reject_non_finite: bool) -> PyResult<Mesh> {
+        obj::load(filename, reject_non_finite)
+    }
+
+    //axis aligned cube centered at the origin, normals=True stores averaged vertex normals
+    #[staticmethod]
+    #[pyo3(signature = (size=1.0, normals=false))]
+    fn cube(size: f32, normals: bool) -> PyResult<Mesh> {
+        if size.is_nan() || size <= 0.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("size must be positive, got {}", size)));
+        }
+
+        let mut mesh = primitives::cube(size);
+        if normals {
+            mesh.normals = Some(mesh.averaged_vertex_normals());
+        }
+        Ok(mesh)
+    }
+
+    //icosphere centered at the origin, an icosahedron subdivided 1-to-4 `subdivisions` times
+    //normals=True stores the exact radial normals
+    #[staticmethod]
+    #[pyo3(signature = (radius=1.0, subdivisions=2, normals=false))]
+    fn sphere(radius: f32, subdivisions: u32, normals: bool) -> PyResult<Mesh> {
+        if radius.is_nan() || radius <= 0.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("radius must be positive, got {}", radius)));
+        }
+
+        let mut mesh = primitives::icosphere(radius, subdivisions);
+        if normals {
+            mesh.normals = Some(mesh.vertices.iter().map(|v| Point3::from(v.coords / radius)).collect());
+        }
+        Ok(mesh)
+    }
+
+    //closed cylinder along z centered at the origin, normals=True stores averaged vertex normals
+    #[staticmethod]
+    #[pyo3(signature = (radius=1.0, height=1.0, segments=32, normals=false))]
+    fn cylinder(radius: f32, height: f32, segments: usize, normals: bool) -> PyResult<Mesh> {
+        if radius.is_nan() || radius <= 0.0 || height.is_nan() || height <= 0.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "radius and height must be positive, got {} and {}",
+                radius, height
+            )));
+        }
+        if segments < 3 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "segments must be at least 3, got {}",
+                segments
+            )));
+        }
+
+        let mut mesh = primitives::cylinder(radius, height, segments);
+        if normals {
+            mesh.normals = Some(mesh.averaged_vertex_normals());
+        }
+        Ok(mesh)
+    }
+
+    //open grid in the xy plane centered at the origin and facing +z, with `divisions` quads
+    //along each side; normals=True stores +z for every vertex
+    #[staticmethod]
+    #[pyo3(signature = (width=1.0, height=1.0, divisions=1, normals=false))]
+    fn plane(width: f32, height: f32, divisions: usize, normals: bool) -> PyResult<Mesh> {
+        if width.is_nan() || width <= 0.0 || height.is_nan() || height <= 0.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "width and height must be positive, got {} and {}",
+                width, height
+            )));
+        }
+        if divisions < 1 {
+            return Err(pyo3::exceptions::PyValueError::new_err("divisions must be at least 1"));
+        }
+
+        let mut mesh = primitives::plane(width, height, divisions);
+        if normals {
+            mesh.normals = Some(vec![Point3::new(0.0, 0.0, 1.0); mesh.vertices.len()]);
+        }
+        Ok(mesh)
+    }
+
+    #[staticmethod]
+    fn merge(mesh1: &Mesh, mesh2: &Mesh) -> Mesh {
+        let mut merged = Mesh {
+            vertices: mesh1.vertices.clone(),
+            faces: mesh1.faces.clone(),
+            normals: mesh1.normals.clone(),
+            colors: mesh1.colors.clone(),
+            uvs: mesh1.uvs.clone(),
+            face_groups: mesh1.face_groups.clone(),
+            group_names: mesh1.group_names.clone(),
+            ..Default::default()
+        };
+        merged.append(mesh2);
+        merged
+    }
+
+    //merges two meshes and welds boundary vertices closer than tolerance, e.g. the seam of two halves
+    //interior vertices are never welded, returns the mesh and how many vertices were welded
+    //a negative tolerance is relative to the characteristic_length of the merged mesh
+    #[staticmethod]
+    fn merge_welded(mesh1: &Mesh, mesh2: &Mesh, tolerance: f32) -> (Mesh, usize) {
+        let mut merged = Mesh::merge(mesh1, mesh2);
+
+        let mut on_boundary = vec![false; merged.vertices.len()];
+        for (a, b) in merged.topology().boundary_half_edges(&merged.faces) {
+            on_boundary[a] = true;
+            on_boundary[b] = true;
+        }
+
+        let welded = merged.weld_vertices(&on_boundary, tolerance);
+        (merged, welded)
+    }
+}
+
+impl Mesh {
+    //per-vertex sum of the incident face normals, each weighted as weighting says, normalized
+    //sums too short to give a direction are kept as they are, so unused vertices get a zero normal
+    pub(crate) fn accumulate_vertex_normals(&self, weighting: NormalWeighting) -> Vec<Vector3<f32>> {
+        let mut normals = vec![Vector3::zeros(); self.vertices.len()];
+
+        for (f, face) in self.faces.iter().enumerate() {
+            let normal = match weighting {
+                NormalWeighting::Uniform => self.face_normal(f),
+                NormalWeighting::Area => {
+                    let edge1 = self.vertices[face[1]] - self.vertices[face[0]];
+                    let edge2 = self.vertices[face[2]] - self.vertices[face[0]];
+                    edge1.cross(&edge2)
+                }
+            };
+            for &vertex in face {
+                normals[vertex] += normal;
+            }
+        }
+
+        //area weighted sums scale with the mesh, so any nonzero one has a direction
+        let min_norm = match weighting {
+            NormalWeighting::Uniform => 1e-6,
+            NormalWeighting::Area => 0.0,
+        };
+        normals
+            .into_iter()
+            .map(|normal| normal.try_normalize(min_norm).unwrap_or(normal))
+            .collect()
+    }
+
+    //per-vertex mean of the unit normals of the incident faces, normalized
+    //degenerate faces don't contribute, vertices without a usable face keep a (near) zero normal
+    pub(crate) fn averaged_vertex_normals(&self) -> Vec<Point3<f32>> {
+        self.accumulate_vertex_normals(NormalWeighting::Uniform)
+            .into_iter()
+            .map(Point3::from)
+            .collect()
+    }
+
+    pub(crate) fn face_area(&self, face_idx: usize) -> f32 {
+        measure::face_area(&self.vertices, &self.faces[face_idx])
+    }
+
+    //hits of a ray along a unit direction as (distance, face index), sorted by distance
+    //hits within a millionth of each other are one crossing, as when the ray passes through an
+    //edge or vertex shared by several faces; the nearest of them is kept
+    pub(crate) fn ray_crossings(&self, origin: &Point3<f32>, direction: &Vector3<f32>) -> Vec<(f32, usize)> {
+        let mut crossings: Vec<(f32, usize)> = Vec::new();
+        for (t, face) in self.face_bvh().intersect_ray_all(origin, direction) {
+            match crossings.last() {
+                Some(&(last, _)) if t - last <= t.abs().max(1.0) * 1e-6 => {}
+                _ => crossings.push((t, face)),
+            }
+        }
+        crossings
+    }
+
+    pub(crate) fn face_normal(&self, face_idx: usize) -> Vector3<f32> {
+        measure::face_normal(&self.vertices, &self.faces[face_idx])
+    }
+
+    //kd-tree over the vertices, built lazily and reused until the geometry changes
+    pub(crate) fn vertex_tree(&self) -> PyResult<&KdTree> {
+        if self.vertices.is_empty() {
+            return Err(MeshGeometryError::new_err("mesh has no vertices"));
+        }
+
+        Ok(self.vertex_tree.get_or_init(|| KdTree::new(&self.vertices)))
+    }
+
+    //merges eligible vertices closer than tolerance into the first one seen, returns how many were merged
+    pub(crate) fn weld_vertices(&mut self, eligible: &[bool], tolerance: f32) -> usize {
+        let representative = self.weld_map(eligible, tolerance);
+        self.apply_weld_map(&representative)
+    }
+
+    //points faces at the representative of each vertex and drops the vertices that aren't
+    //their own representative, returns how many were dropped
+    //every representative must be its own representative; collapsed faces are left to the caller
+    pub(crate) fn apply_weld_map(&mut self, representative: &[usize]) -> usize {
+        let keep: Vec<bool> = representative.iter().enumerate().map(|(i, &r)| i == r).collect();
+        let merged = keep.iter().filter(|&&k| !k).count();
+        if merged == 0 {
+            return 0;
+        }
+
+        for face in &mut self.faces {
+            for index in face.iter_mut() {
+                *index = representative[*index];
+            }
+        }
+
+        self.compact_vertices(&keep);
+        merged
+    }
+
+    //for every vertex the first eligible vertex within tolerance of it (itself if there is none)
+    //a negative tolerance is relative to characteristic_length
+    pub(crate) fn weld_map(&self, eligible: &[bool], tolerance: f32) -> Vec<usize> {
+        use std::collections::HashMap;
+
+        let tolerance = self.absolute_tolerance(tolerance);
+        let mut representative: Vec<usize> = (0..self.vertices.len()).collect();
+        //non-finite positions are never within any tolerance of anything
+        let candidates = self
+            .vertices
+            .iter()
+            .enumerate()
+            .filter(|&(i, v)| eligible[i] && v.coords.iter().all(|c| c.is_finite()));
+
+        //only identical positions, by bits so it stays linear; + 0.0 turns -0.0 into 0.0
+        if tolerance == 0.0 {
+            let mut first: HashMap<[u32; 3], usize> = HashMap::new();
+            for (i, vertex) in candidates {
+                let key = [vertex.x, vertex.y, vertex.z].map(|c| (c + 0.0).to_bits());
+                representative[i] = *first.entry(key).or_insert(i);
+            }
+            return representative;
+        }
+
+        //cells no smaller than 2^-40 of the largest coordinate, so cell indices can't saturate
+        //and put everything in one cell
+        let extent = self
+            .vertices
+            .iter()
+            .flat_map(|v| v.coords.iter().copied())
+            .filter(|c| c.is_finite())
+            .fold(0.0f32, |m, c| m.max(c.abs()));
+        let cell_size = tolerance.max(extent * (-40.0f32).exp2()).max(f32::MIN_POSITIVE);
+        let cell = |p: &Point3<f32>| {
+            (
+                (p.x / cell_size).floor() as i64,
+                (p.y / cell_size).floor() as i64,
+                (p.z / cell_size).floor() as i64,
+            )
+        };
+
+        let mut grid: HashMap<(i64, i64, i64), Vec<usize>> = HashMap::new();
+
+        for (i, vertex) in candidates {
+            let (cx, cy, cz) = cell(vertex);
+            let mut found = None;
+
+            'search: for dx in -1..=1 {
+                for dy in -1..=1 {
+                    for dz in -1..=1 {
+                        if let Some(bucket) = grid.get(&(cx + dx, cy + dy, cz + dz)) {
+                            if let Some(&j) = bucket
+                                .iter()
+                                .find(|&&j| (self.vertices[j] - vertex).norm() <= tolerance)
+                            {
+                                found = Some(j);
+                                break 'search;
+                            }
+                        }
+                    }
+                }
+            }
+
+            match found {
+                Some(j) => representative[i] = j,
+                None => grid.entry((cx, cy, cz)).or_default().push(i),
+            }
+        }
+
+        representative
+    }
+
+    //(vertex, edge) pairs for find_t_junctions
+    pub(crate) fn t_junctions(&self, tolerance: f32) -> Vec<(usize, (usize, usize))> {
+        let tolerance = self.absolute_tolerance(tolerance);
+        let topology = self.topology();
+        let mut open_edges: Vec<((usize, usize), usize)> = topology
+            .edge_to_faces
+            .iter()
+            .filter(|(_, faces)| faces.len() == 1)
+            .map(|(&edge, faces)| (edge, faces[0]))
+            .collect();
+        open_edges.sort_unstable();
+
+        //only vertices on open edges can sit in a T, sorted by x so each edge looks at the
+        //ones in its own x range
+        let mut candidates: Vec<(f32, usize)> = open_edges
+            .iter()
+            .flat_map(|&((a, b), _)| [a, b])
+            .map(|v| (self.vertices[v].x, v))
+            .collect();
+        candidates.sort_by(|p, q| p.0.total_cmp(&q.0).then(p.1.cmp(&q.1)));
+        candidates.dedup();
+
+        let mut junctions = Vec::new();
+        for &((a, b), face) in &open_edges {
+            let (start, end) = (self.vertices[a], self.vertices[b]);
+            let edge = end - start;
+            let length_squared = edge.norm_squared();
+            if length_squared.is_nan() || length_squared <= 0.0 {
+                continue;
+            }
+
+            let (low, high) = (start.x.min(end.x) - tolerance, start.x.max(end.x) + tolerance);
+            let first = candidates.partition_point(|&(x, _)| x < low);
+            for &(_, v) in candidates[first..].iter().take_while(|&&(x, _)| x <= high) {
+                if self.faces[face].contains(&v) {
+                    continue;
+                }
+
+                let point = self.vertices[v];
+                let t = (point - start).dot(&edge) / length_squared;
+                let off_line = (point - (start + edge * t)).norm();
+                if t > 0.0
+                    && t < 1.0
+                    && off_line <= tolerance
+                    && (point - start).norm() > tolerance
+                    && (point - end).norm() > tolerance
+                {
+                    junctions.push((v, (a, b)));
+                }
+            }
+        }
+
+        junctions.sort_unstable();
+        junctions
+    }
+
+    //fix_t_junctions without the tolerance check
+    pub(crate) fn split_t_junctions(&mut self, tolerance: f32) -> usize {
+        use std::collections::BTreeMap;
+
+        let mut fixed = 0;
+        loop {
+            let junctions = self.t_junctions(tolerance);
+            if junctions.is_empty() {
+                return fixed;
+            }
+
+            let mut on_edge: BTreeMap<(usize, usize), Vec<usize>> = BTreeMap::new();
+            for (vertex, edge) in junctions {
+                on_edge.entry(edge).or_default().push(vertex);
+            }
+
+            //a face with junctions on more than one edge is split one edge per round
+            let topology = self.topology();
+            let mut split = vec![false; self.faces.len()];
+            let mut pieces = Vec::new();
+            for (edge, mut vertices) in on_edge {
+                let f = topology.edge_to_faces[&edge][0];
+                if split[f] {
+                    continue;
+                }
+                split[f] = true;
+
+                let face = self.faces[f];
+                let i = (0..3)
+                    .find(|&i| (face[i].min(face[(i + 1) % 3]), face[i].max(face[(i + 1) % 3])) == edge)
+                    .expect("face has the edge");
+                let (from, to, tip) = (face[i], face[(i + 1) % 3], face[(i + 2) % 3]);
+
+                let direction = self.vertices[to] - self.vertices[from];
+                let along = |v: usize| (self.vertices[v] - self.vertices[from]).dot(&direction);
+                vertices.sort_by(|&p, &q| along(p).total_cmp(&along(q)));
+
+                //fan from the tip over the edge's pieces, in the face's winding
+                let mut chain = vec![from];
+                chain.extend(&vertices);
+                chain.push(to);
+                self.faces[f] = [chain[0], chain[1], tip];
+                pieces.extend(chain[1..].windows(2).map(|pair| (f, [pair[0], pair[1], tip])));
+                fixed += vertices.len();
+            }
+
+            for (source, piece) in pieces {
+                self.faces.push(piece);
+                if let Some(groups) = &mut self.face_groups {
+                    groups.push(groups[source]);
+                }
+            }
+            self.geometry_changed();
+        }
+    }
+
+    //fan triangulates the given boundary loops, returns how many were closed
+    pub(crate) fn fill_hole_loops(&mut self, holes: &[Vec<usize>]) -> usize {
+        let mut filled = 0;
+
+        for hole in holes.iter().filter(|hole| hole.len() >= 3) {
+            for i in 1..hole.len() - 1 {
+                self.faces.push([hole[0], hole[i + 1], hole[i]]);
+            }
+            filled += 1;
+        }
+
+        if filled > 0 {
+            //the patches belong to no group
+            self.face_groups = None;
+            self.group_names.clear();
+            self.geometry_changed();
+        }
+        filled
+    }
+
+    //per-vertex normals from face corner references into a separate normal list
+    //a vertex using several distinct normals gets their normalized average, vertices without
+    //any (or whose normals cancel out) fall back to area-weighted face normals
+    pub(crate) fn resolve_referenced_normals(
+        &self,
+        normals: &[Point3<f32>],
+        mut refs: Vec<(usize, usize)>,
+    ) -> Vec<Point3<f32>> {
+        refs.sort();
+        refs.dedup();
+
+        let mut sums = vec![Vector3::zeros(); self.vertices.len()];
+        for (vertex, normal) in refs {
+            sums[vertex] += normals[normal].coords.try_normalize(0.0).unwrap_or_else(Vector3::zeros);
+        }
+
+        let fallback = self.accumulate_vertex_normals(NormalWeighting::Area);
+        sums.into_iter()
+            .zip(fallback)
+            .map(|(sum, fallback)| Point3::from(sum.try_normalize(1e-6).unwrap_or(fallback)))
+            .collect()
+    }
+
+    //vertices joined into one set per connected component
+    pub(crate) fn vertex_sets(&self) -> UnionFind {
+        let mut sets = UnionFind::new(self.vertices.len());
+        for face in &self.faces {
+            sets.union(face[0], face[1]);
+            sets.union(face[1], face[2]);
+        }
+        sets
+    }
+
+    pub(crate) fn check_vertex_index(&self, vertex: usize) -> PyResult<()> {
+        if vertex >= self.vertices.len() {
+            return Err(pyo3::exceptions::PyIndexError::new_err(format!(
+                "vertex {} out of range for mesh with {} vertices",
+                vertex,
+                self.vertices.len()
+            )));
+        }
+        Ok(())
+    }
+
+    //decimation state for collapsing the single edge (a, b), which must be in the mesh
+    pub(crate) fn edge_simplifier(
+        &self,
+        a: usize,
+        b: usize,
+        preserve_boundary: bool,
+    ) -> PyResult<simplification::Simplifier> {
+        self.check_vertex_index(a)?;
+        self.check_vertex_index(b)?;
+        if a == b || !self.topology().edge_to_faces.contains_key(&(a.min(b), a.max(b))) {
+            return Err(MeshTopologyError::new_err(format!("edge ({}, {}) is not in the mesh", a, b)));
+        }
+
+        let options = simplification::SimplifyOptions {
+            preserve_boundary,
+            feature_angle_deg: None,
+        };
+        Ok(simplification::Simplifier::around_edge(self, a, b, &options))
+    }
+
+    //faces using each vertex, built lazily and reused until the geometry changes
+    pub(crate) fn vertex_faces(&self) -> &[Vec<usize>] {
+        self.vertex_faces.get_or_init(|| {
+            let mut vertex_faces = vec![Vec::new(); self.vertices.len()];
+            for (face_idx, face) in self.faces.iter().enumerate() {
+                for &vertex in face {
+                    vertex_faces[vertex].push(face_idx);
+                }
+            }
+            vertex_faces
+        })
+    }
+
+    //bvh over the faces, built lazily and reused until the geometry changes
+    pub(crate) fn face_bvh(&self) -> &Bvh {
+        self.face_bvh.get_or_init(|| Bvh::new(&self.vertices, &self.faces))
+    }
+
+    //edge and vertex incidence, built lazily and reused until the geometry changes
+    pub(crate) fn topology(&self) -> Arc<Topology> {
+        self.topology.get_or_init(|| Arc::new(Topology::new(&self.faces))).clone()
+    }
+
+    //adds the vertices and faces of other after this mesh's own
+    //normals, colors, uvs and face groups are kept only if both meshes have them, groups are matched
+    //by name and other's new names are added after this mesh's
+    pub(crate) fn append(&mut self, other: &Mesh) {
+        let offset = self.vertices.len();
+
+        self.vertices.extend_from_slice(&other.vertices);
+        self.faces
+            .extend(other.faces.iter().map(|face| [face[0] + offset, face[1] + offset, face[2] + offset]));
+
+        self.normals = match (self.normals.take(), &other.normals) {
+            (Some(mut normals), Some(other_normals)) => {
+                normals.extend_from_slice(other_normals);
+                Some(normals)
+            }
+            _ => None,
+        };
+        self.colors = match (self.colors.take(), &other.colors) {
+            (Some(mut colors), Some(other_colors)) => {
+                colors.extend_from_slice(other_colors);
+                Some(colors)
+            }
+            _ => None,
+        };
+        self.uvs = match (self.uvs.take(), &other.uvs) {
+            (Some(mut uvs), Some(other_uvs)) => {
+                uvs.extend_from_slice(other_uvs);
+                Some(uvs)
+            }
+            _ => None,
+        };
+        self.face_groups = match (self.face_groups.take(), &other.face_groups) {
+            (Some(mut groups), Some(other_groups)) => {
+                let ids: Vec<u32> = other.group_names
+                    .iter()
+                    .map(|name| match self.group_names.iter().position(|n| n == name) {
+                        Some(id) => id as u32,
+                        None => {
+                            self.group_names.push(name.clone());
+                            self.group_names.len() as u32 - 1
+                        }
+                    })
+                    .collect();
+                groups.extend(other_groups.iter().map(|&group| ids[group as usize]));
+                Some(groups)
+            }
+            _ => None,
+        };
+        if self.face_groups.is_none() {
+            self.group_names.clear();
+        }
+
+        self.geometry_changed();
+    }
+
+    //this mesh, or with a tolerance a copy without the faces that have an edge no longer than it
+    pub(crate) fn skipping_degenerate(&self, tolerance: Option<f32>) -> std::borrow::Cow<'_, Mesh> {
+        use std::borrow::Cow;
+
+        let tolerance = match tolerance {
+            Some(tolerance) => self.absolute_tolerance(tolerance),
+            None => return Cow::Borrowed(self),
+        };
+        let degenerate = |a: usize, b: usize| (self.vertices[a] - self.vertices[b]).norm() <= tolerance;
+
+        Cow::Owned(Mesh {
+            vertices: self.vertices.clone(),
+            faces: self
+                .faces
+                .iter()
+                .filter(|f| !(degenerate(f[0], f[1]) || degenerate(f[1], f[2]) || degenerate(f[2], f[0])))
+                .copied()
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    //a distance tolerance argument in the mesh's units, see characteristic_length
+    pub(crate) fn absolute_tolerance(&self, tolerance: f32) -> f32 {
+        if tolerance < 0.0 {
+            -tolerance * self.characteristic_length()
+        } else {
+            tolerance
+        }
+    }
+
+    //drops cached data derived from vertex positions or faces, call after moving or
+    //removing vertices and after adding or removing faces
+    pub(crate) fn geometry_changed(&mut self) {
+        self.vertex_tree = OnceLock::new();
+        self.face_bvh = OnceLock::new();
+        self.bounds = OnceLock::new();
+        self.vertex_faces = OnceLock::new();
+        self.topology = OnceLock::new();
+        self.edge_counts = OnceLock::new();
+    }
+
+    //bounding box from all vertices, zeros for an empty mesh
+    fn scan_bounds(&self) -> ([f32; 3], [f32; 3]) {
+        measure::bounds(&self.vertices)
+    }
+
+    //cached bounds carried through a map whose output coordinates each depend monotonically
+    //on one input coordinate, such maps send the box to the box spanned by its mapped corners
+    fn mapped_bounds(&self, map: impl Fn(Vector3<f32>) -> Vector3<f32>) -> OnceLock<([f32; 3], [f32; 3])> {
+        let bounds = OnceLock::new();
+        if let Some(&(min, max)) = self.bounds.get() {
+            let (a, b) = (map(Vector3::from(min)), map(Vector3::from(max)));
+            let _ = bounds.set((a.inf(&b).into(), a.sup(&b).into()));
+        }
+        bounds
+    }
+
+    //keeps the flagged faces and their groups, returns how many were removed
+    //the caller decides whether that needs geometry_changed
+    pub(crate) fn retain_faces(&mut self, keep: &[bool]) -> usize {
+        fn kept<T: Copy>(items: &[T], keep: &[bool]) -> Vec<T> {
+            items.iter().zip(keep).filter(|(_, &k)| k).map(|(item, _)| *item).collect()
+        }
+
+        let before = self.faces.len();
+        self.faces = kept(&self.faces, keep);
+        if let Some(groups) = &self.face_groups {
+            self.face_groups = Some(kept(groups, keep));
+        }
+        before - self.faces.len()
+    }
+
+    //keeps the flagged vertices and their normals, colors and uvs, faces must only reference kept vertices
+    pub(crate) fn compact_vertices(&mut self, keep: &[bool]) {
+        let mut remap = vec![usize::MAX; self.vertices.len()];
+        let mut kept = 0;
+        for (i, new_index) in remap.iter_mut().enumerate() {
+            if keep[i] {
+                *new_index = kept;
+                kept += 1;
+            }
+        }
+
+        self.vertices = self.vertices
+            .iter()
+            .zip(keep)
+            .filter(|(_, &k)| k)
+            .map(|(v, _)| *v)
+            .collect();
+
+        if let Some(normals) = &self.normals {
+            self.normals = Some(normals
+                .iter()
+                .zip(keep)
+                .filter(|(_, &k)| k)
+                .map(|(n, _)| *n)
+                .collect());
+        }
+        if let Some(colors) = &self.colors {
+            self.colors = Some(colors
+                .iter()
+                .zip(keep)
+                .filter(|(_, &k)| k)
+                .map(|(c, _)| *c)
+                .collect());
+        }
+        if let Some(uvs) = &self.uvs {
+            self.uvs = Some(uvs
+                .iter()
+                .zip(keep)
+                .filter(|(_, &k)| k)
+                .map(|(uv, _)| *uv)
+                .collect());
+        }
+
+        for face in &mut self.faces {
+            for vertex in face.iter_mut() {
+                *vertex = remap[*vertex];
+            }
+        }
+
+        self.geometry_changed();
+    }
+
+    //1-ring neighbours of every vertex from the half-edge structure
+    pub(crate) fn vertex_rings(&self) -> PyResult<Vec<Vec<usize>>> {
+        let half_edges = HalfEdgeMesh::from_mesh(self)?;
+        Ok((0..self.vertices.len())
+            .map(|v| half_edges.vertex_neighbors(v))
+            .collect())
+    }
+
+    //detect_spikes vertices with the centroid of their 1-ring, in vertex order
+    pub(crate) fn spikes(&self, threshold: f32) -> PyResult<Vec<(usize, Point3<f32>)>> {
+        if threshold.is_nan() || threshold <= 0.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "threshold must be positive, got {}",
+                threshold
+            )));
+        }
+
+        let rings = self.vertex_rings()?;
+        let vertex_faces = self.vertex_faces();
+
+        Ok((0..self.vertices.len())
+            .filter_map(|v| {
+                let ring = &rings[v];
+                if ring.is_empty() {
+                    return None;
+                }
+                let centroid = ring.iter().fold(Vector3::zeros(), |acc, &n| acc + self.vertices[n].coords)
+                    / ring.len() as f32;
+
+                let opposite_lengths: Vec<f32> = vertex_faces[v]
+                    .iter()
+                    .map(|&f| {
+                        let others: Vec<usize> = self.faces[f].iter().copied().filter(|&u| u != v).collect();
+                        match others[..] {
+                            [a, b] => (self.vertices[a] - self.vertices[b]).norm(),
+                            _ => 0.0,
+                        }
+                    })
+                    .collect();
+                let mean_length = opposite_lengths.iter().sum::<f32>() / opposite_lengths.len().max(1) as f32;
+
+                let distance = (self.vertices[v].coords - centroid).norm();
+                (distance > threshold * mean_length).then(|| (v, Point3::from(centroid)))
+            })
+            .collect())
+    }
+
+    //unit normals interpolated at the closest point on the source surface for every vertex,
+    //None when the source has no normals or no faces
+    pub(crate) fn normals_transferred_from(&self, source: &Mesh) -> Option<Vec<Point3<f32>>> {
+        let source_normals = source.normals.as_ref()?;
+
+        self.transferred_from(source, |corners, bary| {
+            let normal = (0..3).fold(Vector3::zeros(), |sum, i| sum + source_normals[corners[i]].coords * bary[i]);
+            Point3::from(normal.try_normalize(0.0).unwrap_or_else(Vector3::zeros))
+        })
+    }
+
+    //interpolate(face vertices, barycentric coordinates) at the closest point on the source
+    //surface for every vertex, None when the source has no faces
+    pub(crate) fn transferred_from<T: Send>(
+        &self,
+        source: &Mesh,
+        interpolate: impl Fn([usize; 3], [f32; 3]) -> T + Sync,
+    ) -> Option<Vec<T>> {
+        if source.faces.is_empty() {
+            return None;
+        }
+        let bvh = source.face_bvh();
+
+        let transfer = |vertex: &Point3<f32>| {
+            let (face, _, bary, _) = bvh.closest_point(vertex).expect("source has faces");
+            interpolate(source.faces[face], bary)
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            Some(self.vertices.par_iter().map(transfer).collect())
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            Some(self.vertices.iter().map(transfer).collect())
+        }
+    }
+
+    //(neighbor, weight) 1-rings for laplacian_step, "uniform" weighs every neighbor the same
+    pub(crate) fn smoothing_rings(&self, weighting: &str) -> PyResult<Vec<Vec<(usize, f32)>>> {
+        match weighting {
+            "uniform" => Ok(self
+                .vertex_rings()?
+                .into_iter()
+                .map(|ring| ring.into_iter().map(|n| (n, 1.0)).collect())
+                .collect()),
+            "cotangent" => Ok(laplacian::cotangent_rings(self)),
+            _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "unknown weighting '{}', expected 'uniform' or 'cotangent'",
+                weighting
+            ))),
+        }
+    }
+
+    //moves every vertex by factor towards the weighted average of its 1-ring
+    //vertices without neighbors or with zero total weight stay put
+    pub(crate) fn laplacian_step(&mut self, rings: &[Vec<(usize, f32)>], factor: f32) {
+        let updated: Vec<Point3<f32>> = self.vertices
+            .iter()
+            .zip(rings)
+            .map(|(vertex, ring)| {
+                let total: f32 = ring.iter().map(|&(_, w)| w).sum();
+                if total.is_nan() || total <= 0.0 {
+                    return *vertex;
+                }
+
+                let average = ring
+                    .iter()
+                    .fold(Vector3::zeros(), |acc, &(n, w)| acc + self.vertices[n].coords * w)
+                    / total;
+
+                vertex + (average - vertex.coords) * factor
+            })
+            .collect();
+
+        self.vertices = updated;
+        self.geometry_changed();
+    }
+
+    //vertex mean and the covariance eigenvectors, largest variance first, right-handed
+    pub(crate) fn principal_axes(&self) -> (Vector3<f32>, [Vector3<f32>; 3]) {
+        let n = self.vertices.len().max(1) as f32;
+        let mean = self.vertices
+            .iter()
+            .fold(Vector3::zeros(), |acc, v| acc + v.coords) / n;
+
+        let covariance = self.vertices
+            .iter()
+            .fold(Matrix3::zeros(), |acc, v| {
+                let d = v.coords - mean;
+                acc + d * d.transpose()
+            }) / n;
+
+        let eigen = SymmetricEigen::new(covariance);
+
+        let mut order = [0, 1, 2];
+        order.sort_by(|&a, &b| eigen.eigenvalues[b].total_cmp(&eigen.eigenvalues[a]));
+
+        let first: Vector3<f32> = eigen.eigenvectors.column(order[0]).into();
+        let second: Vector3<f32> = eigen.eigenvectors.column(order[1]).into();
+        let third = first.cross(&second);
+
+        (mean, [first, second, third])
+    }
+}
+
+//((row, col) pairs, values) of the nonzero entries of a sparse matrix
+type SparseMatrix = (Vec<(usize, usize)>, Vec<f32>);
+
+//per-vertex tangents and bitangents
+type TangentFrames = (Vec<[f32; 3]>, Vec<[f32; 3]>);
+
+//(nodes, edges) of curve_skeleton
+type Skeleton = (Vec<[f32; 3]>, Vec<(usize, usize)>);
+
+//(face index, point, barycentric coordinates, distance) from closest_point
+type ClosestPoint = (usize, [f32; 3], [f32; 3], f32);
+
+//corner positions of a face
+type Triangle = ([f32; 3], [f32; 3], [f32; 3]);
+
+//how accumulate_vertex_normals weights the face normals around a vertex
+#[derive(Clone, Copy)]
+pub(crate) enum NormalWeighting {
+    //every face counts the same
+    Uniform,
+    //faces count by their area, so slivers barely tilt the normal
+    Area,
+}
+
+//Mesh.iter_triangles, yields the corners of the next face
+#[pyclass]
+pub struct TriangleIter {
+    mesh: Py<Mesh>,
+    next: usize,
+}
+
+#[pymethods]
+impl TriangleIter {
+    fn __iter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python) -> PyResult<Option<Triangle>> {
+        let mesh = self.mesh.try_borrow(py)?;
+        let face = match mesh.faces.get(self.next) {
+            Some(face) => *face,
+            None => return Ok(None),
+        };
+
+        for &vertex in &face {
+            mesh.check_vertex_index(vertex)?;
+        }
+        self.next += 1;
+
+        let [a, b, c] = face.map(|v| [mesh.vertices[v].x, mesh.vertices[v].y, mesh.vertices[v].z]);
+        Ok(Some((a, b, c)))
+    }
+}
+
+//bounds of several meshes together without merging them, the same as the bounds of their merge
+//meshes without vertices don't count, a zero box if none has any
+#[pyfunction]
+pub fn union_bounds(meshes: Vec<PyRef<Mesh>>) -> ([f32; 3], [f32; 3]) {
+    let mut union: Option<([f32; 3], [f32; 3])> = None;
+    for mesh in meshes.iter().filter(|mesh| !mesh.vertices.is_empty()) {
+        let (min, max) = mesh.compute_bounds();
+        union = Some(match union {
+            Some((union_min, union_max)) => (
+                [0, 1, 2].map(|i| union_min[i].min(min[i])),
+                [0, 1, 2].map(|i| union_max[i].max(max[i])),
+            ),
+            None => (min, max),
+        });
+    }
+
+    union.unwrap_or(([0.0; 3], [0.0; 3]))
+}
+
+//copies an (N, 3) array exposing the buffer protocol, None if the layout or dtype doesn't match
+fn buffer_rows<T, U>(value: &PyAny, convert: fn(T) -> U) -> Option<Vec<[U; 3]>>
+where
+    T: pyo3::buffer::Element + Copy,
+{
+    let buffer = pyo3::buffer::PyBuffer::<T>::get(value).ok()?;
+    if buffer.dimensions() != 2 || buffer.shape()[1] != 3 {
+        return None;
+    }
+
+    let flat = buffer.to_vec(value.py()).ok()?;
+    Some(flat
+        .chunks_exact(3)
+        .map(|row| [convert(row[0]), convert(row[1]), convert(row[2])])
+        .collect())
+}
+
+//normalizes a user supplied direction, rejecting the zero vector
+fn unit_direction(direction: [f32; 3]) -> PyResult<Vector3<f32>> {
+    Vector3::from(direction)
+        .try_normalize(f32::EPSILON)
+        .ok_or_else(|| pyo3::exceptions::PyValueError::new_err("direction must be non-zero"))
+}
+
+//normalized sum of unit face normals, sums too short to give a direction are kept as they are
+fn unit_or_zero_normal(sum: Vector3<f32>) -> Point3<f32> {
+    Point3::from(sum.try_normalize(1e-6).unwrap_or(sum))
 }
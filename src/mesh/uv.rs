@@ -0,0 +1,107 @@
+use crate::mesh::types::Mesh;
+use nalgebra::{Point3, Vector3};
+use std::collections::HashMap;
+use std::f32::consts::{PI, TAU};
+
+//Texture coordinates by projection for generate_uvs, one uv per vertex in [0, 1].
+//The wrapping projections measure u as the angle around the z axis, 0 along +x and growing
+//counterclockwise, so they have a seam on the +x side where u jumps from 1 back to 0.
+
+fn angle_around_z(offset: &Vector3<f32>) -> f32 {
+    (offset.y.atan2(offset.x) / TAU).rem_euclid(1.0)
+}
+
+//u around z, v from the south pole (0) to the north pole (1) as seen from center
+pub fn spherical(vertices: &[Point3<f32>], center: &Point3<f32>) -> Vec<[f32; 2]> {
+    vertices
+        .iter()
+        .map(|vertex| {
+            let offset = vertex - center;
+            let v = match offset.try_normalize(0.0) {
+                Some(direction) => 1.0 - direction.z.clamp(-1.0, 1.0).acos() / PI,
+                None => 0.5,
+            };
+            [angle_around_z(&offset), v]
+        })
+        .collect()
+}
+
+//u around the z axis through center, v along it from z_range's start (0) to its end (1)
+pub fn cylindrical(vertices: &[Point3<f32>], center: &Point3<f32>, z_range: (f32, f32)) -> Vec<[f32; 2]> {
+    let height = z_range.1 - z_range.0;
+    vertices
+        .iter()
+        .map(|vertex| {
+            let v = if height > 0.0 { (vertex.z - z_range.0) / height } else { 0.5 };
+            [angle_around_z(&(vertex - center)), v]
+        })
+        .collect()
+}
+
+//positions in the plane perpendicular to normal, scaled to fill the unit square
+pub fn planar(vertices: &[Point3<f32>], normal: &Vector3<f32>) -> Vec<[f32; 2]> {
+    //two directions spanning the plane, from an axis that isn't close to normal
+    let helper = if normal.x.abs() < 0.9 { Vector3::x() } else { Vector3::y() };
+    let u_axis = normal.cross(&helper).normalize();
+    let v_axis = normal.cross(&u_axis);
+
+    let projected: Vec<[f32; 2]> = vertices
+        .iter()
+        .map(|vertex| [vertex.coords.dot(&u_axis), vertex.coords.dot(&v_axis)])
+        .collect();
+
+    let range = |i: usize| {
+        let min = projected.iter().map(|p| p[i]).fold(f32::INFINITY, f32::min);
+        let max = projected.iter().map(|p| p[i]).fold(f32::NEG_INFINITY, f32::max);
+        (min, max - min)
+    };
+    let (range_u, range_v) = (range(0), range(1));
+    let scale = |value: f32, (min, extent): (f32, f32)| if extent > 0.0 { (value - min) / extent } else { 0.5 };
+
+    projected.iter().map(|p| [scale(p[0], range_u), scale(p[1], range_v)]).collect()
+}
+
+//gives the faces that straddle a wrapping seam their own copies of the corners on the low
+//side, with u + 1, so no face interpolates across the whole texture; the copies carry the
+//normals and colors of their originals, returns how many vertices were added
+pub fn split_seam(mesh: &mut Mesh, uvs: &mut Vec<[f32; 2]>) -> usize {
+    let mut copies: HashMap<usize, usize> = HashMap::new();
+
+    for f in 0..mesh.faces.len() {
+        let us = mesh.faces[f].map(|v| uvs[v][0]);
+        let low = us.iter().copied().fold(f32::INFINITY, f32::min);
+        let high = us.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        if high - low <= 0.5 {
+            continue;
+        }
+
+        for corner in 0..3 {
+            let vertex = mesh.faces[f][corner];
+            if uvs[vertex][0] >= 0.5 {
+                continue;
+            }
+
+            let copy = match copies.get(&vertex) {
+                Some(&copy) => copy,
+                None => {
+                    mesh.vertices.push(mesh.vertices[vertex]);
+                    if let Some(normals) = &mut mesh.normals {
+                        normals.push(normals[vertex]);
+                    }
+                    if let Some(colors) = &mut mesh.colors {
+                        colors.push(colors[vertex]);
+                    }
+                    uvs.push([uvs[vertex][0] + 1.0, uvs[vertex][1]]);
+                    copies.insert(vertex, mesh.vertices.len() - 1);
+                    mesh.vertices.len() - 1
+                }
+            };
+            mesh.faces[f][corner] = copy;
+        }
+    }
+
+    if !copies.is_empty() {
+        mesh.geometry_changed();
+    }
+    copies.len()
+}
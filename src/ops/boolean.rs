@@ -0,0 +1,404 @@
+use crate::mesh::types::Mesh;
+use nalgebra::{Point3, Vector3};
+use crate::errors::MeshTopologyError;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+//Constructive solid geometry on closed meshes.
+//Both operands are turned into BSP trees over their faces; each tree clips away
+//the other operand's faces that lie inside (or outside) of it, which splits faces
+//along the intersection curve and classifies the pieces in one pass. The kept
+//convex pieces are fan triangulated and welded back into an indexed mesh.
+//A cut splits the faces on one side of it without splitting the matching faces on the other,
+//so the pieces are stitched to them at the T-junctions this leaves, which makes the result
+//watertight again and lets it be the operand of another operation.
+
+const EPSILON: f64 = 1e-5;
+
+//how far apart, relative to characteristic_length, points of the result can be and still
+//be joined when stitching, cut points found from different polygons differ in the last bits
+const SEAM_TOLERANCE: f32 = 1e-5;
+
+const COPLANAR: u8 = 0;
+const FRONT: u8 = 1;
+const BACK: u8 = 2;
+const SPANNING: u8 = 3;
+
+#[derive(Clone)]
+struct Plane {
+    normal: Vector3<f64>,
+    w: f64,
+}
+
+impl Plane {
+    fn from_points(a: &Vector3<f64>, b: &Vector3<f64>, c: &Vector3<f64>) -> Option<Plane> {
+        let normal = (b - a).cross(&(c - a));
+        let length = normal.norm();
+        if length < EPSILON * EPSILON {
+            return None;
+        }
+
+        let normal = normal / length;
+        Some(Plane { normal, w: normal.dot(a) })
+    }
+
+    fn flip(&mut self) {
+        self.normal = -self.normal;
+        self.w = -self.w;
+    }
+
+    //sorts the polygon into the matching lists, splitting it when it straddles the plane
+    fn split_polygon(
+        &self,
+        polygon: Polygon,
+        coplanar_front: &mut Vec<Polygon>,
+        coplanar_back: &mut Vec<Polygon>,
+        front: &mut Vec<Polygon>,
+        back: &mut Vec<Polygon>,
+    ) {
+        let mut polygon_type = COPLANAR;
+        let types: Vec<u8> = polygon.vertices
+            .iter()
+            .map(|v| {
+                let t = self.normal.dot(v) - self.w;
+                let vertex_type = if t < -EPSILON {
+                    BACK
+                } else if t > EPSILON {
+                    FRONT
+                } else {
+                    COPLANAR
+                };
+                polygon_type |= vertex_type;
+                vertex_type
+            })
+            .collect();
+
+        match polygon_type {
+            COPLANAR => {
+                if self.normal.dot(&polygon.plane.normal) > 0.0 {
+                    coplanar_front.push(polygon);
+                } else {
+                    coplanar_back.push(polygon);
+                }
+            }
+            FRONT => front.push(polygon),
+            BACK => back.push(polygon),
+            _ => {
+                let mut f = Vec::new();
+                let mut b = Vec::new();
+                let n = polygon.vertices.len();
+
+                for i in 0..n {
+                    let j = (i + 1) % n;
+                    let (ti, tj) = (types[i], types[j]);
+                    let (vi, vj) = (polygon.vertices[i], polygon.vertices[j]);
+
+                    if ti != BACK {
+                        f.push(vi);
+                    }
+                    if ti != FRONT {
+                        b.push(vi);
+                    }
+                    if (ti | tj) == SPANNING {
+                        let t = (self.w - self.normal.dot(&vi)) / self.normal.dot(&(vj - vi));
+                        let v = vi + (vj - vi) * t;
+                        f.push(v);
+                        b.push(v);
+                    }
+                }
+
+                if f.len() >= 3 {
+                    front.push(Polygon { vertices: f, plane: polygon.plane.clone() });
+                }
+                if b.len() >= 3 {
+                    back.push(Polygon { vertices: b, plane: polygon.plane });
+                }
+            }
+        }
+    }
+}
+
+//convex planar polygon
+#[derive(Clone)]
+struct Polygon {
+    vertices: Vec<Vector3<f64>>,
+    plane: Plane,
+}
+
+impl Polygon {
+    fn flip(&mut self) {
+        self.vertices.reverse();
+        self.plane.flip();
+    }
+}
+
+struct Node {
+    plane: Option<Plane>,
+    front: Option<usize>,
+    back: Option<usize>,
+    polygons: Vec<Polygon>,
+}
+
+impl Node {
+    fn empty() -> Self {
+        Node {
+            plane: None,
+            front: None,
+            back: None,
+            polygons: Vec::new(),
+        }
+    }
+}
+
+//BSP tree stored as an arena, node 0 is the root
+//all traversals use explicit work lists since the tree can get as deep as the face count
+struct Bsp {
+    nodes: Vec<Node>,
+}
+
+impl Bsp {
+    fn new(polygons: Vec<Polygon>) -> Self {
+        let mut bsp = Bsp { nodes: vec![Node::empty()] };
+        bsp.build(polygons);
+        bsp
+    }
+
+    fn from_mesh(mesh: &Mesh) -> Self {
+        let polygons = mesh.faces
+            .iter()
+            .filter_map(|face| {
+                let vertices: Vec<Vector3<f64>> = face
+                    .iter()
+                    .map(|&i| mesh.vertices[i].coords.cast::<f64>())
+                    .collect();
+                let plane = Plane::from_points(&vertices[0], &vertices[1], &vertices[2])?;
+                Some(Polygon { vertices, plane })
+            })
+            .collect();
+
+        Bsp::new(polygons)
+    }
+
+    //converts space inside the solid to outside and vice versa
+    fn invert(&mut self) {
+        for node in &mut self.nodes {
+            for polygon in &mut node.polygons {
+                polygon.flip();
+            }
+            if let Some(plane) = &mut node.plane {
+                plane.flip();
+            }
+            std::mem::swap(&mut node.front, &mut node.back);
+        }
+    }
+
+    //removes the parts of the polygons that are inside this solid
+    fn clip_polygons(&self, polygons: Vec<Polygon>) -> Vec<Polygon> {
+        let mut result = Vec::new();
+        let mut work = vec![(0, polygons)];
+
+        while let Some((node_idx, polygons)) = work.pop() {
+            let node = &self.nodes[node_idx];
+            let plane = match &node.plane {
+                Some(plane) => plane,
+                None => {
+                    result.extend(polygons);
+                    continue;
+                }
+            };
+
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            for polygon in polygons {
+                let mut coplanar_front = Vec::new();
+                let mut coplanar_back = Vec::new();
+                plane.split_polygon(polygon, &mut coplanar_front, &mut coplanar_back, &mut front, &mut back);
+                front.extend(coplanar_front);
+                back.extend(coplanar_back);
+            }
+
+            match node.front {
+                Some(child) => work.push((child, front)),
+                None => result.extend(front),
+            }
+            if let Some(child) = node.back {
+                work.push((child, back));
+            }
+        }
+
+        result
+    }
+
+    //removes every polygon of this tree that is inside the other tree
+    fn clip_to(&mut self, other: &Bsp) {
+        for node in &mut self.nodes {
+            let polygons = std::mem::take(&mut node.polygons);
+            node.polygons = other.clip_polygons(polygons);
+        }
+    }
+
+    fn all_polygons(&self) -> Vec<Polygon> {
+        self.nodes
+            .iter()
+            .flat_map(|node| node.polygons.iter().cloned())
+            .collect()
+    }
+
+    fn build(&mut self, polygons: Vec<Polygon>) {
+        let mut work = vec![(0, polygons)];
+
+        while let Some((node_idx, polygons)) = work.pop() {
+            if polygons.is_empty() {
+                continue;
+            }
+
+            if self.nodes[node_idx].plane.is_none() {
+                self.nodes[node_idx].plane = Some(polygons[0].plane.clone());
+            }
+            let plane = self.nodes[node_idx].plane.clone().unwrap();
+
+            let mut coplanar = Vec::new();
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            for polygon in polygons {
+                let mut coplanar_back = Vec::new();
+                plane.split_polygon(polygon, &mut coplanar, &mut coplanar_back, &mut front, &mut back);
+                coplanar.extend(coplanar_back);
+            }
+            self.nodes[node_idx].polygons.extend(coplanar);
+
+            if !front.is_empty() {
+                let child = self.child(node_idx, true);
+                work.push((child, front));
+            }
+            if !back.is_empty() {
+                let child = self.child(node_idx, false);
+                work.push((child, back));
+            }
+        }
+    }
+
+    fn child(&mut self, node_idx: usize, front: bool) -> usize {
+        let existing = if front { self.nodes[node_idx].front } else { self.nodes[node_idx].back };
+        if let Some(child) = existing {
+            return child;
+        }
+
+        let child = self.nodes.len();
+        self.nodes.push(Node::empty());
+        if front {
+            self.nodes[node_idx].front = Some(child);
+        } else {
+            self.nodes[node_idx].back = Some(child);
+        }
+        child
+    }
+
+    //fan triangulates the convex polygons and welds bit-identical vertices
+    fn to_mesh(&self) -> Mesh {
+        let mut vertices: Vec<Point3<f32>> = Vec::new();
+        let mut faces: Vec<[usize; 3]> = Vec::new();
+        let mut index: HashMap<[u32; 3], usize> = HashMap::new();
+
+        for polygon in self.all_polygons() {
+            let indices: Vec<usize> = polygon.vertices
+                .iter()
+                .map(|v| {
+                    let p = Point3::new(v.x as f32, v.y as f32, v.z as f32);
+                    *index.entry([p.x.to_bits(), p.y.to_bits(), p.z.to_bits()]).or_insert_with(|| {
+                        vertices.push(p);
+                        vertices.len() - 1
+                    })
+                })
+                .collect();
+
+            for i in 1..indices.len() - 1 {
+                let face = [indices[0], indices[i], indices[i + 1]];
+                if face[0] != face[1] && face[1] != face[2] && face[0] != face[2] {
+                    faces.push(face);
+                }
+            }
+        }
+
+        let mut mesh = Mesh {
+            vertices,
+            faces,
+            normals: None,
+            ..Default::default()
+        };
+        stitch(&mut mesh);
+        mesh
+    }
+}
+
+//welds the points of the cut and splits faces at the T-junctions along it
+fn stitch(mesh: &mut Mesh) {
+    let eligible = vec![true; mesh.vertices.len()];
+    if mesh.weld_vertices(&eligible, -SEAM_TOLERANCE) > 0 {
+        let keep: Vec<bool> = mesh.faces
+            .iter()
+            .map(|face| face[0] != face[1] && face[1] != face[2] && face[0] != face[2])
+            .collect();
+        mesh.retain_faces(&keep);
+        mesh.geometry_changed();
+    }
+    mesh.split_t_junctions(-SEAM_TOLERANCE);
+}
+
+fn check_watertight(mesh: &Mesh, name: &str) -> PyResult<()> {
+    if !mesh.topology().is_watertight() {
+        return Err(MeshTopologyError::new_err(format!(
+            "boolean operations require watertight meshes, {} is not watertight",
+            name
+        )));
+    }
+    Ok(())
+}
+
+fn operands(a: &Mesh, b: &Mesh) -> PyResult<(Bsp, Bsp)> {
+    check_watertight(a, "a")?;
+    check_watertight(b, "b")?;
+    Ok((Bsp::from_mesh(a), Bsp::from_mesh(b)))
+}
+
+pub fn union(a: &Mesh, b: &Mesh) -> PyResult<Mesh> {
+    let (mut a, mut b) = operands(a, b)?;
+
+    a.clip_to(&b);
+    b.clip_to(&a);
+    b.invert();
+    b.clip_to(&a);
+    b.invert();
+    a.build(b.all_polygons());
+
+    Ok(a.to_mesh())
+}
+
+pub fn intersection(a: &Mesh, b: &Mesh) -> PyResult<Mesh> {
+    let (mut a, mut b) = operands(a, b)?;
+
+    a.invert();
+    b.clip_to(&a);
+    b.invert();
+    a.clip_to(&b);
+    b.clip_to(&a);
+    a.build(b.all_polygons());
+    a.invert();
+
+    Ok(a.to_mesh())
+}
+
+pub fn difference(a: &Mesh, b: &Mesh) -> PyResult<Mesh> {
+    let (mut a, mut b) = operands(a, b)?;
+
+    a.invert();
+    a.clip_to(&b);
+    b.clip_to(&a);
+    b.invert();
+    b.clip_to(&a);
+    b.invert();
+    a.build(b.all_polygons());
+    a.invert();
+
+    Ok(a.to_mesh())
+}
@@ -0,0 +1,274 @@
+use crate::mesh::types::Mesh;
+use crate::ops::hull::Hull;
+use crate::progress::Progress;
+use nalgebra::{Point3, Vector3};
+use pyo3::PyResult;
+
+//Approximate convex decomposition in the spirit of V-HACD.
+//The solid is voxelized by casting rays down grid columns to estimate volumes, then pieces are
+//split in two by the axis-aligned plane that leaves the least volume between each half and its
+//convex hull, always working on the piece whose hull overshoots it the most. A piece keeps the
+//part of the surface on its side, clipped exactly at the cuts, and its hull is the hull of
+//those polygons: the cross sections are bounded by the clipped edges, so they are enclosed too.
+
+//voxels along the longest side of the bounding box
+const RESOLUTION: usize = 32;
+//split positions tried per axis
+const CANDIDATE_PLANES: usize = 12;
+
+struct Piece {
+    //voxel centers inside the solid, each standing for one cell of volume
+    voxels: Vec<Vector3<f64>>,
+    //convex polygons of the surface on this side of every cut so far
+    surface: Vec<Vec<Vector3<f64>>>,
+    hull: Option<Hull>,
+    //volume of the hull not covered by the voxels
+    concave_volume: f64,
+    splittable: bool,
+}
+
+impl Piece {
+    fn new(voxels: Vec<Vector3<f64>>, surface: Vec<Vec<Vector3<f64>>>, cell_volume: f64) -> Self {
+        let points: Vec<Vector3<f64>> = surface.iter().flatten().copied().collect();
+        let hull = Hull::new(&points);
+        let concave_volume = match &hull {
+            Some(hull) => (hull.volume() - voxels.len() as f64 * cell_volume).max(0.0),
+            None => 0.0,
+        };
+
+        Piece {
+            voxels,
+            surface,
+            hull,
+            concave_volume,
+            splittable: true,
+        }
+    }
+
+    //the two halves on either side of position along axis, None if one would be empty
+    fn split(&self, axis: usize, position: f64, cell_volume: f64) -> Option<(Piece, Piece)> {
+        let (below, above): (Vec<Vector3<f64>>, Vec<Vector3<f64>>) =
+            self.voxels.iter().partition(|v| v[axis] < position);
+        if below.is_empty() || above.is_empty() {
+            return None;
+        }
+
+        let mut surface_below = Vec::new();
+        let mut surface_above = Vec::new();
+        for polygon in &self.surface {
+            surface_below.extend(clip(polygon, axis, position, -1.0));
+            surface_above.extend(clip(polygon, axis, position, 1.0));
+        }
+
+        Some((
+            Piece::new(below, surface_below, cell_volume),
+            Piece::new(above, surface_above, cell_volume),
+        ))
+    }
+}
+
+//the part of a convex polygon on the side of the plane given by the sign, None if nothing is left
+//Sutherland-Hodgman against a single axis-aligned plane
+fn clip(polygon: &[Vector3<f64>], axis: usize, position: f64, side: f64) -> Option<Vec<Vector3<f64>>> {
+    let distance = |p: &Vector3<f64>| (p[axis] - position) * side;
+
+    //a polygon in the plane bounds the solid on the side its outward normal points away from
+    if polygon.iter().all(|p| distance(p) == 0.0) {
+        let normal = (polygon[1] - polygon[0]).cross(&(polygon[2] - polygon[0]));
+        return if normal[axis] * side < 0.0 { Some(polygon.to_vec()) } else { None };
+    }
+
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+
+    for (i, current) in polygon.iter().enumerate() {
+        let next = &polygon[(i + 1) % polygon.len()];
+        let (d_current, d_next) = (distance(current), distance(next));
+
+        if d_current >= 0.0 {
+            clipped.push(*current);
+        }
+        if (d_current > 0.0 && d_next < 0.0) || (d_current < 0.0 && d_next > 0.0) {
+            clipped.push(crossing(current, next, axis, position));
+        }
+    }
+
+    if clipped.len() < 3 { None } else { Some(clipped) }
+}
+
+//where the segment crosses the plane, the same bits for both directions of an edge so the
+//faces sharing it don't leave near duplicate points for the hull
+fn crossing(a: &Vector3<f64>, b: &Vector3<f64>, axis: usize, position: f64) -> Vector3<f64> {
+    let (a, b) = if a.as_slice() < b.as_slice() { (a, b) } else { (b, a) };
+    let mut point = a + (b - a) * ((position - a[axis]) / (b[axis] - a[axis]));
+    point[axis] = position;
+    point
+}
+
+//convex pieces whose hulls overshoot the solid by at most concavity times its volume,
+//or as many as max_hulls allows; expects a closed, consistently wound mesh
+pub fn convex_decomposition(mesh: &Mesh, max_hulls: usize, concavity: f32) -> PyResult<Vec<Mesh>> {
+    let (min, max) = mesh.compute_bounds();
+    let (min, max) = (Vector3::from(min).cast::<f64>(), Vector3::from(max).cast::<f64>());
+    let extent = max - min;
+    let cell = extent.max() / RESOLUTION as f64;
+    if cell <= 0.0 || !cell.is_finite() {
+        return Ok(Vec::new());
+    }
+    let cell_volume = cell * cell * cell;
+
+    let voxels = voxelize(mesh, &min, &extent, cell, &mut Progress::new(None))?;
+    if voxels.is_empty() {
+        return Ok(Vec::new());
+    }
+    let solid_volume = voxels.len() as f64 * cell_volume;
+
+    let surface = mesh
+        .faces
+        .iter()
+        .map(|face| face.iter().map(|&v| mesh.vertices[v].coords.cast::<f64>()).collect())
+        .collect();
+
+    let mut pieces = vec![Piece::new(voxels, surface, cell_volume)];
+    let tolerance = concavity as f64 * solid_volume;
+
+    while pieces.len() < max_hulls {
+        //the piece whose hull overshoots it the most, first one on ties
+        let worst = (0..pieces.len())
+            .filter(|&i| pieces[i].splittable && pieces[i].concave_volume > tolerance)
+            .fold(None, |best: Option<usize>, i| match best {
+                Some(b) if pieces[b].concave_volume >= pieces[i].concave_volume => Some(b),
+                _ => Some(i),
+            });
+        let worst = match worst {
+            Some(worst) => worst,
+            None => break,
+        };
+
+        match best_split(&pieces[worst], cell, cell_volume) {
+            Some((below, above)) => {
+                pieces[worst] = below;
+                pieces.push(above);
+            }
+            None => pieces[worst].splittable = false,
+        }
+    }
+
+    Ok(pieces.iter().filter_map(|piece| piece.hull.as_ref().map(Hull::to_mesh)).collect())
+}
+
+//split with the least concave volume left in both halves, ties go to the lower axis and position
+//a coarse pass over every axis is refined with every plane around its best candidate
+fn best_split(piece: &Piece, cell: f64, cell_volume: f64) -> Option<(Piece, Piece)> {
+    struct Candidate {
+        cost: f64,
+        axis: usize,
+        gap: usize,
+        halves: (Piece, Piece),
+    }
+
+    //planes sit in the gaps between voxel layers, gap i just above the i-th layer from the bottom
+    let range = |axis: usize| {
+        let lo = piece.voxels.iter().map(|v| v[axis]).fold(f64::INFINITY, f64::min);
+        let hi = piece.voxels.iter().map(|v| v[axis]).fold(f64::NEG_INFINITY, f64::max);
+        (lo, ((hi - lo) / cell).round() as usize)
+    };
+
+    let try_gap = |best: &mut Option<Candidate>, axis: usize, gap: usize, lo: f64| {
+        if let Some(halves) = piece.split(axis, lo + (gap as f64 + 0.5) * cell, cell_volume) {
+            let cost = halves.0.concave_volume + halves.1.concave_volume;
+            let better = match best {
+                Some(b) => cost < b.cost,
+                None => true,
+            };
+            if better {
+                *best = Some(Candidate { cost, axis, gap, halves });
+            }
+        }
+    };
+
+    let mut best = None;
+    let mut step = 1;
+    for axis in 0..3 {
+        let (lo, gaps) = range(axis);
+        let axis_step = gaps.div_ceil(CANDIDATE_PLANES).max(1);
+        step = step.max(axis_step);
+        for gap in (axis_step / 2..gaps).step_by(axis_step) {
+            try_gap(&mut best, axis, gap, lo);
+        }
+    }
+
+    let (axis, gap) = best.as_ref().map(|b| (b.axis, b.gap))?;
+    let (lo, gaps) = range(axis);
+    for neighbor in gap.saturating_sub(step)..(gap + step).min(gaps) {
+        if neighbor != gap {
+            try_gap(&mut best, axis, neighbor, lo);
+        }
+    }
+
+    best.map(|b| b.halves)
+}
+
+//centers of the grid cells inside the mesh
+//each column of cells is classified by the parity of the surface crossings along it
+//progress is reported after every slab of columns along y
+pub(crate) fn voxelize(
+    mesh: &Mesh,
+    min: &Vector3<f64>,
+    extent: &Vector3<f64>,
+    cell: f64,
+    progress: &mut Progress,
+) -> PyResult<Vec<Vector3<f64>>> {
+    let counts = extent.map(|e| ((e / cell).ceil() as usize).max(1));
+    //center the grid on the bounding box
+    let origin = min - (counts.cast::<f64>() * cell - extent) / 2.0;
+    let center = |i: usize, j: usize, k: usize| {
+        origin + Vector3::new(i as f64 + 0.5, j as f64 + 0.5, k as f64 + 0.5) * cell
+    };
+
+    let bvh = mesh.face_bvh();
+    let direction = Vector3::z();
+    let index = |i: usize, j: usize, k: usize| (k * counts.y + j) * counts.x + i;
+    let mut inside = vec![false; counts.x * counts.y * counts.z];
+
+    for j in 0..counts.y {
+        for i in 0..counts.x {
+            //nudged off the cell center so columns don't run exactly along grid aligned edges
+            let start = center(i, j, 0) + Vector3::new(0.0013, 0.0007, -1.0) * cell;
+            let start = Point3::from(start.cast::<f32>());
+
+            let mut crossings: Vec<f64> = Vec::new();
+            for (t, _) in bvh.intersect_ray_all(&start, &direction) {
+                let z = start.z as f64 + t as f64;
+                //a column through an edge hits both of its faces at the same height
+                let distinct = match crossings.last() {
+                    Some(&last) => z - last > cell * 1e-6,
+                    None => true,
+                };
+                if distinct {
+                    crossings.push(z);
+                }
+            }
+
+            for k in 0..counts.z {
+                let z = center(i, j, k).z;
+                let below = crossings.iter().filter(|&&c| c < z).count();
+                inside[index(i, j, k)] = below % 2 == 1;
+            }
+        }
+        progress.report((j + 1) as f32 / counts.y as f32)?;
+    }
+    progress.finish()?;
+
+    let mut voxels = Vec::new();
+    for k in 0..counts.z {
+        for j in 0..counts.y {
+            for i in 0..counts.x {
+                if inside[index(i, j, k)] {
+                    voxels.push(center(i, j, k));
+                }
+            }
+        }
+    }
+
+    Ok(voxels)
+}
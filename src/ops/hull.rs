@@ -0,0 +1,295 @@
+use crate::mesh::types::Mesh;
+use nalgebra::{Point3, Vector3};
+use std::collections::{HashMap, HashSet};
+
+//Quickhull (Barber, Dobkin & Huhdanpaa 1996) with exact orientation tests.
+//Points are snapped to a 2^20 grid over their bounding box so the side of a plane a point is on
+//can be decided exactly in integer arithmetic; floating point tests misjudge nearly coplanar
+//points, which breaks the horizon and flips faces. The hull is grown from a tetrahedron by
+//repeatedly adding the point farthest outside a face: the faces it sees are removed and the
+//hole is closed with a fan of new faces, which take over the outside points of the old ones.
+
+//grid steps along the diagonal of the bounding box
+const GRID: f64 = (1u64 << 20) as f64;
+
+//points closer to a face than this many grid steps count as on it; rounding to the grid turns
+//points on a common line or plane into tiny bumps that would otherwise become sliver faces
+const TOLERANCE: f64 = 4.0;
+
+type GridPoint = [i64; 3];
+
+//six times the signed volume of the tetrahedron, positive when d is on the side of abc its
+//counterclockwise normal points to; grid coordinates stay below 2^21 so this fits easily
+fn orient(a: &GridPoint, b: &GridPoint, c: &GridPoint, d: &GridPoint) -> i128 {
+    let u = [0, 1, 2].map(|i| (b[i] - a[i]) as i128);
+    let v = [0, 1, 2].map(|i| (c[i] - a[i]) as i128);
+    let w = [0, 1, 2].map(|i| (d[i] - a[i]) as i128);
+
+    u[0] * (v[1] * w[2] - v[2] * w[1]) - u[1] * (v[0] * w[2] - v[2] * w[0]) + u[2] * (v[0] * w[1] - v[1] * w[0])
+}
+
+struct HullFace {
+    vertices: [usize; 3],
+    //points outside this face that no other face has claimed
+    outside: Vec<usize>,
+    alive: bool,
+}
+
+impl HullFace {
+    fn new(vertices: [usize; 3]) -> Self {
+        HullFace {
+            vertices,
+            outside: Vec::new(),
+            alive: true,
+        }
+    }
+
+    //how far outside the face a point is, scaled by the face's doubled area
+    fn height(&self, grid: &[GridPoint], point: usize) -> i128 {
+        let [a, b, c] = self.vertices.map(|v| &grid[v]);
+        orient(a, b, c, &grid[point])
+    }
+
+    //whether the point is more than TOLERANCE grid steps outside the face
+    fn sees(&self, grid: &[GridPoint], point: usize) -> bool {
+        let height = self.height(grid, point);
+        if height <= 0 {
+            return false;
+        }
+
+        let [a, b, c] = self.vertices.map(|v| &grid[v]);
+        let u = [0, 1, 2].map(|i| (b[i] - a[i]) as f64);
+        let v = [0, 1, 2].map(|i| (c[i] - a[i]) as f64);
+        let normal = Vector3::new(u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]);
+        height as f64 > TOLERANCE * normal.norm()
+    }
+}
+
+//closed convex polytope with outward wound faces over a subset of the input points
+pub struct Hull {
+    pub points: Vec<Vector3<f64>>,
+    pub faces: Vec<[usize; 3]>,
+}
+
+impl Hull {
+    //None when the points don't span a volume
+    pub fn new(points: &[Vector3<f64>]) -> Option<Self> {
+        let mut min = Vector3::repeat(f64::INFINITY);
+        let mut max = Vector3::repeat(f64::NEG_INFINITY);
+        for p in points {
+            min = min.inf(p);
+            max = max.sup(p);
+        }
+        let scale = (max - min).norm();
+        if scale <= 0.0 || !scale.is_finite() {
+            return None;
+        }
+
+        let grid: Vec<GridPoint> = points
+            .iter()
+            .map(|p| ((p - min) / scale * GRID).map(|x| x.round() as i64).into())
+            .collect();
+
+        let initial = initial_tetrahedron(&grid)?;
+        let mut faces: Vec<HullFace> = Vec::new();
+        for [a, b, c, opposite] in [[0, 1, 2, 3], [0, 3, 1, 2], [1, 3, 2, 0], [0, 2, 3, 1]] {
+            let mut face = HullFace::new([initial[a], initial[b], initial[c]]);
+            if face.height(&grid, initial[opposite]) > 0 {
+                face = HullFace::new([initial[a], initial[c], initial[b]]);
+            }
+            faces.push(face);
+        }
+
+        //directed edge -> face holding it, each hull edge appears once per direction
+        let mut edge_face: HashMap<(usize, usize), usize> = HashMap::new();
+        for (f, face) in faces.iter().enumerate() {
+            for i in 0..3 {
+                edge_face.insert((face.vertices[i], face.vertices[(i + 1) % 3]), f);
+            }
+        }
+
+        let unclaimed: Vec<usize> = (0..points.len()).filter(|p| !initial.contains(p)).collect();
+        assign_outside(&mut faces, 0, unclaimed, &grid);
+
+        let mut pending: Vec<usize> = (0..faces.len()).rev().collect();
+        while let Some(f) = pending.pop() {
+            if !faces[f].alive || faces[f].outside.is_empty() {
+                continue;
+            }
+
+            //farthest point outside the face, first one on ties
+            let p = faces[f].outside.iter().copied().fold(faces[f].outside[0], |best, q| {
+                if faces[f].height(&grid, q) > faces[f].height(&grid, best) { q } else { best }
+            });
+
+            //faces the point sees, a connected patch around f
+            let mut visible = vec![f];
+            let mut is_visible: HashSet<usize> = HashSet::from([f]);
+            let mut next = 0;
+            while next < visible.len() {
+                let vertices = faces[visible[next]].vertices;
+                next += 1;
+                for i in 0..3 {
+                    let neighbor = edge_face[&(vertices[(i + 1) % 3], vertices[i])];
+                    if !is_visible.contains(&neighbor) && faces[neighbor].sees(&grid, p) {
+                        is_visible.insert(neighbor);
+                        visible.push(neighbor);
+                    }
+                }
+            }
+
+            //edges of visible faces whose other side stays are the horizon
+            let mut horizon = Vec::new();
+            for &g in &visible {
+                let vertices = faces[g].vertices;
+                for i in 0..3 {
+                    let (a, b) = (vertices[i], vertices[(i + 1) % 3]);
+                    if !is_visible.contains(&edge_face[&(b, a)]) {
+                        horizon.push((a, b));
+                    }
+                }
+            }
+
+            //with the tolerance the visible faces can touch at a single vertex or surround one
+            //that stays, leaving a horizon a single fan can't close; the point is then barely
+            //outside and is dropped instead
+            if !is_single_loop(&horizon) {
+                faces[f].outside.retain(|&q| q != p);
+                pending.push(f);
+                continue;
+            }
+
+            let mut orphans = Vec::new();
+            for &g in &visible {
+                orphans.extend(faces[g].outside.drain(..).filter(|&q| q != p));
+            }
+
+            for &g in &visible {
+                faces[g].alive = false;
+                let vertices = faces[g].vertices;
+                for i in 0..3 {
+                    edge_face.remove(&(vertices[i], vertices[(i + 1) % 3]));
+                }
+            }
+
+            let first_new = faces.len();
+            for (a, b) in horizon {
+                let g = faces.len();
+                faces.push(HullFace::new([a, b, p]));
+                edge_face.insert((a, b), g);
+                edge_face.insert((b, p), g);
+                edge_face.insert((p, a), g);
+            }
+
+            assign_outside(&mut faces, first_new, orphans, &grid);
+            pending.extend((first_new..faces.len()).rev());
+        }
+
+        //keep only the points the hull uses, in input order
+        let faces: Vec<[usize; 3]> = faces.iter().filter(|f| f.alive).map(|f| f.vertices).collect();
+        let mut remap = vec![usize::MAX; points.len()];
+        for face in &faces {
+            for &v in face {
+                remap[v] = 0;
+            }
+        }
+        let mut used = Vec::new();
+        for (v, index) in remap.iter_mut().enumerate() {
+            if *index == 0 {
+                *index = used.len();
+                used.push(points[v]);
+            }
+        }
+
+        Some(Hull {
+            faces: faces.into_iter().map(|face| face.map(|v| remap[v])).collect(),
+            points: used,
+        })
+    }
+
+    pub fn volume(&self) -> f64 {
+        self.faces
+            .iter()
+            .map(|face| self.points[face[0]].dot(&self.points[face[1]].cross(&self.points[face[2]])))
+            .sum::<f64>()
+            / 6.0
+    }
+
+    pub fn to_mesh(&self) -> Mesh {
+        Mesh {
+            vertices: self.points.iter().map(|p| Point3::from(p.cast::<f32>())).collect(),
+            faces: self.faces.clone(),
+            normals: None,
+            ..Default::default()
+        }
+    }
+}
+
+//four points spanning a tetrahedron as large as cheaply possible: the extremes along x,
+//the point farthest from their line and the point farthest from that plane
+fn initial_tetrahedron(grid: &[GridPoint]) -> Option<[usize; 4]> {
+    let farthest = |score: &dyn Fn(&GridPoint) -> i128| {
+        (0..grid.len())
+            .map(|i| (score(&grid[i]), i))
+            .fold((0, 0), |best, candidate| if candidate.0 > best.0 { candidate } else { best })
+    };
+    let squared = |v: [i128; 3]| v.iter().map(|x| x * x).sum::<i128>();
+    let delta = |p: &GridPoint, q: &GridPoint| [0, 1, 2].map(|i| (p[i] - q[i]) as i128);
+
+    let a = (0..grid.len()).min_by_key(|&i| (grid[i][0], i))?;
+    let (_, b) = farthest(&|p| squared(delta(p, &grid[a])));
+    let (area, c) = farthest(&|p| {
+        let (u, v) = (delta(&grid[b], &grid[a]), delta(p, &grid[a]));
+        squared([u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]])
+    });
+    if area == 0 {
+        return None;
+    }
+
+    let (volume, d) = farthest(&|p| orient(&grid[a], &grid[b], &grid[c], p).abs());
+    if volume == 0 {
+        return None;
+    }
+
+    Some([a, b, c, d])
+}
+
+//whether the directed edges chain into one closed loop through distinct vertices
+fn is_single_loop(edges: &[(usize, usize)]) -> bool {
+    let mut next = HashMap::new();
+    for &(a, b) in edges {
+        if next.insert(a, b).is_some() {
+            return false;
+        }
+    }
+
+    let start = match edges.first() {
+        Some(&(start, _)) => start,
+        None => return false,
+    };
+    let mut vertex = start;
+    for steps in 1..=edges.len() {
+        vertex = match next.get(&vertex) {
+            Some(&to) => to,
+            None => return false,
+        };
+        if vertex == start {
+            return steps == edges.len();
+        }
+    }
+    false
+}
+
+//gives each point to the first face from `first` on that it lies outside of, falling back to
+//any live face so no outside point is lost; points inside every face are dropped
+fn assign_outside(faces: &mut [HullFace], first: usize, unclaimed: Vec<usize>, grid: &[GridPoint]) {
+    for q in unclaimed {
+        let outside_of = |f: &HullFace| f.alive && f.sees(grid, q);
+        let owner = (first..faces.len())
+            .find(|&f| outside_of(&faces[f]))
+            .or_else(|| (0..first).find(|&f| outside_of(&faces[f])));
+        if let Some(f) = owner {
+            faces[f].outside.push(q);
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod boolean;
+pub mod decomposition;
+pub mod hull;
+pub mod remesh;
@@ -0,0 +1,541 @@
+use crate::mesh::types::{Mesh, NormalWeighting};
+use nalgebra::{Point3, Vector3};
+use std::collections::HashMap;
+
+//Local changes to the triangulation that keep the surface (roughly) where it is.
+
+//cosine of the smallest angle of a triangle, larger means worse
+fn worst_angle_cos(p: [Point3<f32>; 3]) -> f32 {
+    (0..3)
+        .map(|i| {
+            let u = (p[(i + 1) % 3] - p[i]).normalize();
+            let v = (p[(i + 2) % 3] - p[i]).normalize();
+            u.dot(&v)
+        })
+        .fold(f32::NEG_INFINITY, f32::max)
+}
+
+fn cross(p: [Point3<f32>; 3]) -> Vector3<f32> {
+    (p[1] - p[0]).cross(&(p[2] - p[0]))
+}
+
+//faces on every undirected edge, keyed by (min vertex, max vertex)
+fn edge_face_map(faces: &[[usize; 3]]) -> HashMap<(usize, usize), Vec<usize>> {
+    let mut edge_faces: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for (face_idx, face) in faces.iter().enumerate() {
+        for i in 0..3 {
+            let (a, b) = (face[i], face[(i + 1) % 3]);
+            edge_faces.entry((a.min(b), a.max(b))).or_default().push(face_idx);
+        }
+    }
+    edge_faces
+}
+
+//vertices on boundary or non-manifold edges
+fn constrained_vertices(vertex_count: usize, edge_faces: &HashMap<(usize, usize), Vec<usize>>) -> Vec<bool> {
+    let mut constrained = vec![false; vertex_count];
+    for (&(a, b), faces) in edge_faces {
+        if faces.len() != 2 {
+            constrained[a] = true;
+            constrained[b] = true;
+        }
+    }
+    constrained
+}
+
+//flips interior edges whose opposite angles sum to more than 180 degrees (the Delaunay
+//criterion) when that also raises the smaller minimum angle of the two faces
+//at most `iterations` passes over the edges, returns the number of flips
+pub fn improve_by_edge_flips(mesh: &mut Mesh, iterations: usize) -> usize {
+    let mut edge_faces = edge_face_map(&mesh.faces);
+    let mut flips = 0;
+
+    for _ in 0..iterations {
+        let mut edges: Vec<(usize, usize)> = edge_faces.keys().copied().collect();
+        edges.sort();
+
+        let mut pass_flips = 0;
+        for edge in edges {
+            if try_flip(mesh, &mut edge_faces, edge) {
+                pass_flips += 1;
+            }
+        }
+
+        flips += pass_flips;
+        if pass_flips == 0 {
+            break;
+        }
+    }
+
+    if flips > 0 {
+        mesh.geometry_changed();
+    }
+    flips
+}
+
+//the two faces of an interior edge as f1 = [a, b, c] and f2 = [b, a, d], up to rotation
+struct Flip {
+    a: usize,
+    b: usize,
+    c: usize,
+    d: usize,
+    f1: usize,
+    f2: usize,
+}
+
+impl Flip {
+    fn old_faces(&self, v: &[Point3<f32>]) -> [[Point3<f32>; 3]; 2] {
+        [[v[self.a], v[self.b], v[self.c]], [v[self.b], v[self.a], v[self.d]]]
+    }
+
+    fn new_faces(&self, v: &[Point3<f32>]) -> [[Point3<f32>; 3]; 2] {
+        [[v[self.c], v[self.a], v[self.d]], [v[self.d], v[self.b], v[self.c]]]
+    }
+
+    //the flipped faces must face the same way as the pair they replace
+    fn keeps_orientation(&self, v: &[Point3<f32>]) -> bool {
+        let old = self.old_faces(v);
+        let reference = cross(old[0]) + cross(old[1]);
+        self.new_faces(v).iter().all(|&t| cross(t).dot(&reference) > 0.0)
+    }
+}
+
+//the flip of a manifold edge with consistently wound faces, unless edge c-d already exists
+fn flip_candidate(mesh: &Mesh, edge_faces: &HashMap<(usize, usize), Vec<usize>>, edge: (usize, usize)) -> Option<Flip> {
+    let (f1, f2) = match edge_faces.get(&edge).map(|faces| faces.as_slice()) {
+        Some(&[f1, f2]) => (f1, f2),
+        _ => return None,
+    };
+
+    //orient the edge as a -> b in f1, f2 must then run b -> a
+    let face1 = mesh.faces[f1];
+    let i = (0..3).find(|&i| {
+        let (x, y) = (face1[i], face1[(i + 1) % 3]);
+        (x.min(y), x.max(y)) == edge
+    })?;
+    let (a, b, c) = (face1[i], face1[(i + 1) % 3], face1[(i + 2) % 3]);
+
+    let face2 = mesh.faces[f2];
+    let j = (0..3).find(|&j| face2[j] == b && face2[(j + 1) % 3] == a)?;
+    let d = face2[(j + 2) % 3];
+
+    if c == d || edge_faces.contains_key(&(c.min(d), c.max(d))) {
+        return None;
+    }
+
+    Some(Flip { a, b, c, d, f1, f2 })
+}
+
+fn apply_flip(mesh: &mut Mesh, edge_faces: &mut HashMap<(usize, usize), Vec<usize>>, flip: &Flip) {
+    let Flip { a, b, c, d, f1, f2 } = *flip;
+
+    mesh.faces[f1] = [c, a, d];
+    mesh.faces[f2] = [d, b, c];
+
+    edge_faces.remove(&(a.min(b), a.max(b)));
+    edge_faces.insert((c.min(d), c.max(d)), vec![f1, f2]);
+    //a-d moved from f2 to f1 and b-c from f1 to f2
+    for (key, from, to) in [((a.min(d), a.max(d)), f2, f1), ((b.min(c), b.max(c)), f1, f2)] {
+        if let Some(faces) = edge_faces.get_mut(&key) {
+            for face in faces.iter_mut().filter(|face| **face == from) {
+                *face = to;
+            }
+        }
+    }
+}
+
+fn try_flip(mesh: &mut Mesh, edge_faces: &mut HashMap<(usize, usize), Vec<usize>>, edge: (usize, usize)) -> bool {
+    let flip = match flip_candidate(mesh, edge_faces, edge) {
+        Some(flip) => flip,
+        None => return false,
+    };
+    let Flip { a, b, c, d, .. } = flip;
+    let v = &mesh.vertices;
+
+    //Delaunay: angles at c and d opposite the edge must not sum past 180 degrees
+    let cot = |corner: Point3<f32>, p: Point3<f32>, q: Point3<f32>| {
+        let (u, w) = (p - corner, q - corner);
+        u.dot(&w) / u.cross(&w).norm().max(f32::MIN_POSITIVE)
+    };
+    if cot(v[c], v[a], v[b]) + cot(v[d], v[a], v[b]) >= -1e-6 {
+        return false;
+    }
+
+    //the new faces must face the same way as the old pair and be better shaped
+    if !flip.keeps_orientation(v) {
+        return false;
+    }
+
+    let (old, new) = (flip.old_faces(v), flip.new_faces(v));
+    let old_worst = worst_angle_cos(old[0]).max(worst_angle_cos(old[1]));
+    let new_worst = worst_angle_cos(new[0]).max(worst_angle_cos(new[1]));
+    if new_worst >= old_worst {
+        return false;
+    }
+
+    apply_flip(mesh, edge_faces, &flip);
+    true
+}
+
+//isotropic remeshing after Botsch & Kobbelt, "A Remeshing Approach to Multiresolution
+//Modeling" (2004): every iteration splits edges longer than 4/3 of the target length,
+//collapses edges shorter than 4/5 of it, flips edges towards valence 6 (4 on the boundary)
+//and relaxes vertices in their tangent plane before projecting them back onto the input
+//vertices on boundary and non-manifold edges never move, so open boundaries keep their shape
+pub fn isotropic_remesh(mesh: &Mesh, target_edge_length: f32, iterations: usize) -> Mesh {
+    let max_length = target_edge_length * 4.0 / 3.0;
+    let min_length = target_edge_length * 4.0 / 5.0;
+
+    let mut remeshed = Mesh {
+        vertices: mesh.vertices.clone(),
+        faces: mesh.faces.clone(),
+        normals: None,
+        ..Default::default()
+    };
+
+    for _ in 0..iterations {
+        split_long_edges(&mut remeshed, max_length);
+        collapse_short_edges(&mut remeshed, min_length, max_length);
+        flip_towards_regular_valence(&mut remeshed);
+        relax_tangentially(&mut remeshed, mesh);
+    }
+
+    remeshed
+}
+
+//splits edges at their midpoint until none is longer than max_length
+//each pass splits every long edge whose faces weren't split already in that pass
+fn split_long_edges(mesh: &mut Mesh, max_length: f32) {
+    loop {
+        let edge_faces = edge_face_map(&mesh.faces);
+        let mut long_edges: Vec<(usize, usize)> = edge_faces
+            .keys()
+            .copied()
+            .filter(|&(a, b)| (mesh.vertices[b] - mesh.vertices[a]).norm() > max_length)
+            .collect();
+        if long_edges.is_empty() {
+            break;
+        }
+        long_edges.sort();
+
+        let mut split_faces = vec![false; mesh.faces.len()];
+        for (a, b) in long_edges {
+            let faces = &edge_faces[&(a, b)];
+            if faces.iter().any(|&f| split_faces[f]) {
+                continue;
+            }
+
+            let midpoint = mesh.vertices.len();
+            mesh.vertices.push(nalgebra::center(&mesh.vertices[a], &mesh.vertices[b]));
+
+            //each face keeps its a half and gets a new face for its b half, same winding
+            for &f in faces {
+                split_faces[f] = true;
+                let face = mesh.faces[f];
+                mesh.faces[f] = face.map(|v| if v == b { midpoint } else { v });
+                mesh.faces.push(face.map(|v| if v == a { midpoint } else { v }));
+            }
+        }
+    }
+
+    mesh.geometry_changed();
+}
+
+//splits faces larger than max_area 1-to-4 at their edge midpoints until none is left
+//faces next to a split edge are split along it too, in two or three, so no vertex ends up in
+//the middle of another face's edge; returns the number of faces added
+//midpoints get the average normal, color and uv of their edge's ends, and every piece of a
+//face keeps its group
+pub fn split_large_faces(mesh: &mut Mesh, max_area: f32) -> usize {
+    let before = mesh.faces.len();
+
+    loop {
+        let large: Vec<usize> = (0..mesh.faces.len()).filter(|&f| mesh.face_area(f) > max_area).collect();
+        if large.is_empty() {
+            break;
+        }
+
+        //one midpoint per split edge, shared by the faces on both sides
+        let mut midpoints: HashMap<(usize, usize), usize> = HashMap::new();
+        for f in large {
+            let face = mesh.faces[f];
+            for i in 0..3 {
+                let (a, b) = (face[i], face[(i + 1) % 3]);
+                midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| push_midpoint(mesh, a, b));
+            }
+        }
+
+        let groups = mesh.face_groups.take();
+        let mut split_groups = groups.as_ref().map(|groups| Vec::with_capacity(groups.len()));
+        for (f, face) in std::mem::take(&mut mesh.faces).into_iter().enumerate() {
+            let split = [0, 1, 2].map(|i| {
+                let (a, b) = (face[i], face[(i + 1) % 3]);
+                midpoints.get(&(a.min(b), a.max(b))).copied()
+            });
+            let pieces = split_face(&mesh.vertices, face, split);
+            if let (Some(split_groups), Some(groups)) = (&mut split_groups, &groups) {
+                split_groups.extend(std::iter::repeat(groups[f]).take(pieces.len()));
+            }
+            mesh.faces.extend(pieces);
+        }
+        mesh.face_groups = split_groups;
+    }
+
+    mesh.geometry_changed();
+    mesh.faces.len() - before
+}
+
+//adds the midpoint of edge (a, b) with the averages of the ends' attributes, returns its index
+fn push_midpoint(mesh: &mut Mesh, a: usize, b: usize) -> usize {
+    mesh.vertices.push(nalgebra::center(&mesh.vertices[a], &mesh.vertices[b]));
+    if let Some(normals) = &mut mesh.normals {
+        let sum = normals[a].coords + normals[b].coords;
+        normals.push(Point3::from(sum.try_normalize(0.0).unwrap_or(sum)));
+    }
+    if let Some(colors) = &mut mesh.colors {
+        colors.push([0, 1, 2].map(|i| 0.5 * (colors[a][i] + colors[b][i])));
+    }
+    if let Some(uvs) = &mut mesh.uvs {
+        uvs.push([0.5 * (uvs[a][0] + uvs[b][0]), 0.5 * (uvs[a][1] + uvs[b][1])]);
+    }
+    mesh.vertices.len() - 1
+}
+
+//the triangles replacing a face whose edges i -> i + 1 have the given midpoints, same winding
+fn split_face(vertices: &[Point3<f32>], face: [usize; 3], split: [Option<usize>; 3]) -> Vec<[usize; 3]> {
+    let [a, b, c] = face;
+
+    match split {
+        [None, None, None] => vec![face],
+        [Some(ab), None, None] => vec![[a, ab, c], [ab, b, c]],
+        [Some(ab), Some(bc), None] => {
+            //the quad a, ab, bc, c left next to the corner triangle is cut along its shorter diagonal
+            if (vertices[bc] - vertices[a]).norm() <= (vertices[c] - vertices[ab]).norm() {
+                vec![[ab, b, bc], [a, ab, bc], [a, bc, c]]
+            } else {
+                vec![[ab, b, bc], [a, ab, c], [ab, bc, c]]
+            }
+        }
+        [Some(ab), Some(bc), Some(ca)] => vec![[a, ab, ca], [ab, b, bc], [ca, bc, c], [ab, bc, ca]],
+        //every other pattern is a rotation of one of the above
+        _ => split_face(vertices, [b, c, a], [split[1], split[2], split[0]]),
+    }
+}
+
+//collapses interior edges shorter than min_length, shortest first, unless that would break
+//the manifold, turn a face over or create an edge longer than max_length
+//constrained vertices stay where they are and two of them are never merged
+fn collapse_short_edges(mesh: &mut Mesh, min_length: f32, max_length: f32) {
+    loop {
+        let edge_faces = edge_face_map(&mesh.faces);
+        let constrained = constrained_vertices(mesh.vertices.len(), &edge_faces);
+
+        let mut vertex_faces = vec![Vec::new(); mesh.vertices.len()];
+        for (face_idx, face) in mesh.faces.iter().enumerate() {
+            for &v in face {
+                vertex_faces[v].push(face_idx);
+            }
+        }
+
+        let mut short_edges: Vec<(f32, usize, usize)> = edge_faces
+            .iter()
+            .filter(|(_, faces)| faces.len() == 2)
+            .map(|(&(a, b), _)| ((mesh.vertices[b] - mesh.vertices[a]).norm(), a, b))
+            .filter(|&(length, _, _)| length < min_length)
+            .collect();
+        short_edges.sort_by(|x, y| x.0.total_cmp(&y.0).then((x.1, x.2).cmp(&(y.1, y.2))));
+
+        let mut alive = vec![true; mesh.faces.len()];
+        //vertices whose neighbourhood changed in this pass, their edges wait for the next one
+        let mut touched = vec![false; mesh.vertices.len()];
+        let mut collapses = 0;
+
+        for (_, a, b) in short_edges {
+            if touched[a] || touched[b] {
+                continue;
+            }
+
+            let (keep, remove, position) = match (constrained[a], constrained[b]) {
+                (true, true) => continue,
+                (true, false) => (a, b, mesh.vertices[a]),
+                (false, true) => (b, a, mesh.vertices[b]),
+                (false, false) => (a, b, nalgebra::center(&mesh.vertices[a], &mesh.vertices[b])),
+            };
+
+            let live_faces = |v: usize| vertex_faces[v].iter().copied().filter(|&f| alive[f]);
+            let neighbors = |v: usize| {
+                let mut neighbors: Vec<usize> = live_faces(v)
+                    .flat_map(|f| mesh.faces[f])
+                    .filter(|&u| u != v)
+                    .collect();
+                neighbors.sort();
+                neighbors.dedup();
+                neighbors
+            };
+
+            //link condition: the only common neighbours are the tips of the two faces on the edge
+            let (neighbors_keep, neighbors_remove) = (neighbors(keep), neighbors(remove));
+            let common = neighbors_keep
+                .iter()
+                .filter(|v| neighbors_remove.binary_search(v).is_ok())
+                .count();
+            if common != 2 || neighbors_keep.len() + neighbors_remove.len() - common < 5 {
+                continue;
+            }
+
+            let too_long = neighbors_keep
+                .iter()
+                .chain(&neighbors_remove)
+                .filter(|&&v| v != keep && v != remove)
+                .any(|&v| (mesh.vertices[v] - position).norm() > max_length);
+            if too_long {
+                continue;
+            }
+
+            let turns_over = [keep, remove].iter().any(|&moved| {
+                live_faces(moved)
+                    .filter(|&f| !(mesh.faces[f].contains(&keep) && mesh.faces[f].contains(&remove)))
+                    .any(|f| {
+                        let face = mesh.faces[f];
+                        let before = face.map(|v| mesh.vertices[v]);
+                        let after = face.map(|v| if v == moved { position } else { mesh.vertices[v] });
+                        cross(after).dot(&cross(before)) <= 0.0
+                    })
+            });
+            if turns_over {
+                continue;
+            }
+
+            let faces_remove: Vec<usize> = live_faces(remove).collect();
+            for f in faces_remove {
+                if mesh.faces[f].contains(&keep) {
+                    alive[f] = false;
+                    continue;
+                }
+                for v in mesh.faces[f].iter_mut() {
+                    if *v == remove {
+                        *v = keep;
+                    }
+                }
+                vertex_faces[keep].push(f);
+            }
+            mesh.vertices[keep] = position;
+
+            for v in neighbors_keep.into_iter().chain(neighbors_remove) {
+                touched[v] = true;
+            }
+            touched[keep] = true;
+            touched[remove] = true;
+            collapses += 1;
+        }
+
+        let mut face_idx = 0;
+        mesh.faces.retain(|_| {
+            face_idx += 1;
+            alive[face_idx - 1]
+        });
+
+        if collapses == 0 {
+            break;
+        }
+    }
+
+    let mut used = vec![false; mesh.vertices.len()];
+    for face in &mesh.faces {
+        for &v in face {
+            used[v] = true;
+        }
+    }
+    mesh.compact_vertices(&used);
+}
+
+//flips interior edges when that brings the four vertices involved closer to the regular
+//valence, 6 inside and 4 on the boundary
+fn flip_towards_regular_valence(mesh: &mut Mesh) {
+    let mut edge_faces = edge_face_map(&mesh.faces);
+    let constrained = constrained_vertices(mesh.vertices.len(), &edge_faces);
+
+    let mut valence = vec![0i32; mesh.vertices.len()];
+    for &(a, b) in edge_faces.keys() {
+        valence[a] += 1;
+        valence[b] += 1;
+    }
+    let deviation = |v: usize, valence: i32| (valence - if constrained[v] { 4 } else { 6 }).abs();
+
+    let mut edges: Vec<(usize, usize)> = edge_faces.keys().copied().collect();
+    edges.sort();
+
+    for edge in edges {
+        let flip = match flip_candidate(mesh, &edge_faces, edge) {
+            Some(flip) => flip,
+            None => continue,
+        };
+        let Flip { a, b, c, d, .. } = flip;
+
+        if valence[a] <= 3 || valence[b] <= 3 {
+            continue;
+        }
+
+        let before = deviation(a, valence[a]) + deviation(b, valence[b]) + deviation(c, valence[c]) + deviation(d, valence[d]);
+        let after = deviation(a, valence[a] - 1)
+            + deviation(b, valence[b] - 1)
+            + deviation(c, valence[c] + 1)
+            + deviation(d, valence[d] + 1);
+        if after >= before || !flip.keeps_orientation(&mesh.vertices) {
+            continue;
+        }
+
+        apply_flip(mesh, &mut edge_faces, &flip);
+        valence[a] -= 1;
+        valence[b] -= 1;
+        valence[c] += 1;
+        valence[d] += 1;
+    }
+
+    mesh.geometry_changed();
+}
+
+//moves unconstrained vertices towards the centroid of their neighbours within their tangent
+//plane, then onto the closest point of the reference surface
+fn relax_tangentially(mesh: &mut Mesh, reference: &Mesh) {
+    let edge_faces = edge_face_map(&mesh.faces);
+    let constrained = constrained_vertices(mesh.vertices.len(), &edge_faces);
+
+    //sorted so the centroid sums don't depend on hash order
+    let mut edges: Vec<(usize, usize)> = edge_faces.keys().copied().collect();
+    edges.sort();
+
+    let mut neighbor_sums = vec![(Vector3::zeros(), 0usize); mesh.vertices.len()];
+    for (a, b) in edges {
+        neighbor_sums[a].0 += mesh.vertices[b].coords;
+        neighbor_sums[a].1 += 1;
+        neighbor_sums[b].0 += mesh.vertices[a].coords;
+        neighbor_sums[b].1 += 1;
+    }
+
+    let normals = mesh.accumulate_vertex_normals(NormalWeighting::Area);
+    let surface = reference.face_bvh();
+
+    let relaxed: Vec<Point3<f32>> = mesh.vertices
+        .iter()
+        .enumerate()
+        .map(|(v, &position)| {
+            let (sum, count) = neighbor_sums[v];
+            if constrained[v] || count == 0 {
+                return position;
+            }
+
+            let offset = sum / count as f32 - position.coords;
+            let tangential = offset - normals[v] * normals[v].dot(&offset);
+            let moved = position + tangential;
+
+            match surface.closest_point(&moved) {
+                Some((_, point, _, _)) => Point3::from(point),
+                None => moved,
+            }
+        })
+        .collect();
+
+    mesh.vertices = relaxed;
+    mesh.geometry_changed();
+}
@@ -0,0 +1,55 @@
+use pyo3::exceptions::PyKeyboardInterrupt;
+use pyo3::prelude::*;
+
+//minimum advance between two callback invocations
+const REPORT_STEP: f32 = 0.01;
+
+//wraps the optional Python progress callback of long running operations
+//safe to use inside `py.allow_threads`, the GIL is only taken while reporting
+pub struct Progress {
+    callback: Option<PyObject>,
+    last_reported: f32,
+}
+
+impl Progress {
+    pub fn new(callback: Option<PyObject>) -> Self {
+        Progress {
+            callback,
+            last_reported: f32::NEG_INFINITY,
+        }
+    }
+
+    //calls the callback with a fraction in [0, 1], at most once per REPORT_STEP and never
+    //twice with the same fraction, so finish after a last step of 1.0 doesn't repeat it
+    //a callback returning False cancels the operation
+    pub fn report(&mut self, fraction: f32) -> PyResult<()> {
+        let fraction = fraction.clamp(0.0, 1.0);
+
+        if fraction <= self.last_reported
+            || (fraction < 1.0 && fraction - self.last_reported < REPORT_STEP)
+        {
+            return Ok(());
+        }
+
+        let callback = match &self.callback {
+            Some(callback) => callback,
+            None => return Ok(()),
+        };
+
+        self.last_reported = fraction;
+
+        Python::with_gil(|py| {
+            let result = callback.call1(py, (fraction,))?;
+
+            if let Ok(false) = result.extract::<bool>(py) {
+                return Err(PyKeyboardInterrupt::new_err("operation cancelled by progress callback"));
+            }
+
+            Ok(())
+        })
+    }
+
+    pub fn finish(&mut self) -> PyResult<()> {
+        self.report(1.0)
+    }
+}
@@ -0,0 +1,138 @@
+import meshalyzer
+
+CUBE_VERTICES = [[x, y, z] for z in (0.0, 1.0) for y in (0.0, 1.0) for x in (0.0, 1.0)]
+CUBE_FACES = [
+    [0, 2, 1], [1, 2, 3], [4, 5, 6], [5, 7, 6], [0, 1, 4], [1, 5, 4],
+    [2, 6, 3], [3, 6, 7], [0, 4, 2], [2, 4, 6], [1, 3, 5], [3, 7, 5],
+]
+
+
+def unit_cube():
+    return meshalyzer.Mesh(CUBE_VERTICES, CUBE_FACES)
+
+
+def uv_sphere(rings=12, segments=24, radius=1.0):
+    import math
+
+    vertices = [[0.0, 0.0, radius]]
+    for i in range(1, rings):
+        theta = math.pi * i / rings
+        for j in range(segments):
+            phi = 2.0 * math.pi * j / segments
+            vertices.append([
+                radius * math.sin(theta) * math.cos(phi),
+                radius * math.sin(theta) * math.sin(phi),
+                radius * math.cos(theta),
+            ])
+    vertices.append([0.0, 0.0, -radius])
+
+    def ring(i, j):
+        return 1 + (i - 1) * segments + j % segments
+
+    bottom = len(vertices) - 1
+    faces = []
+    for j in range(segments):
+        faces.append([0, ring(1, j), ring(1, j + 1)])
+        faces.append([bottom, ring(rings - 1, j + 1), ring(rings - 1, j)])
+    for i in range(1, rings - 1):
+        for j in range(segments):
+            a, b = ring(i, j), ring(i, j + 1)
+            c, d = ring(i + 1, j), ring(i + 1, j + 1)
+            faces += [[a, c, d], [a, d, b]]
+
+    return meshalyzer.Mesh(vertices, faces)
+
+
+def subdivided_cube(n=8):
+    # unit cube whose sides are n x n grids of quads, each split into two triangles
+    vertices = []
+    index = {}
+
+    def vertex(p):
+        key = tuple(round(c * n) for c in p)
+        if key not in index:
+            index[key] = len(vertices)
+            vertices.append(list(p))
+        return index[key]
+
+    faces = []
+    for axis in range(3):
+        u_axis, v_axis = (axis + 1) % 3, (axis + 2) % 3
+        for side in (0.0, 1.0):
+            for i in range(n):
+                for j in range(n):
+                    corners = []
+                    for du, dv in ((0, 0), (1, 0), (1, 1), (0, 1)):
+                        p = [0.0, 0.0, 0.0]
+                        p[axis] = side
+                        p[u_axis] = (i + du) / n
+                        p[v_axis] = (j + dv) / n
+                        corners.append(vertex(p))
+                    a, b, c, d = corners if side == 1.0 else corners[::-1]
+                    faces += [[a, b, c], [a, c, d]]
+
+    return meshalyzer.Mesh(vertices, faces)
+
+
+def torus(rings=16, segments=32, major=2.0, minor=0.5):
+    import math
+
+    vertices = []
+    for i in range(segments):
+        phi = 2.0 * math.pi * i / segments
+        for j in range(rings):
+            theta = 2.0 * math.pi * j / rings
+            r = major + minor * math.cos(theta)
+            vertices.append([r * math.cos(phi), r * math.sin(phi), minor * math.sin(theta)])
+
+    def index(i, j):
+        return (i % segments) * rings + j % rings
+
+    faces = []
+    for i in range(segments):
+        for j in range(rings):
+            a, b = index(i, j), index(i + 1, j)
+            c, d = index(i + 1, j + 1), index(i, j + 1)
+            faces += [[a, b, c], [a, c, d]]
+
+    return meshalyzer.Mesh(vertices, faces)
+
+
+# closed tube along z from 0 to length, with a fan cap on each end
+def capped_tube(rings=21, segments=16, radius=0.3, length=4.0):
+    import math
+
+    vertices = []
+    for i in range(rings):
+        for j in range(segments):
+            phi = 2.0 * math.pi * j / segments
+            vertices.append([radius * math.cos(phi), radius * math.sin(phi), length * i / (rings - 1)])
+    bottom, top = len(vertices), len(vertices) + 1
+    vertices += [[0.0, 0.0, 0.0], [0.0, 0.0, length]]
+
+    faces = []
+    for i in range(rings - 1):
+        for j in range(segments):
+            a, b = i * segments + j, i * segments + (j + 1) % segments
+            faces += [[a, b, b + segments], [a, b + segments, a + segments]]
+    last = (rings - 1) * segments
+    for j in range(segments):
+        faces.append([bottom, (j + 1) % segments, j])
+        faces.append([top, last + j, last + (j + 1) % segments])
+
+    return meshalyzer.Mesh(vertices, faces)
+
+
+def l_prism():
+    # L shaped outline in the xy plane extruded one unit along z, volume 3
+    outline = [[0.0, 0.0], [2.0, 0.0], [2.0, 1.0], [1.0, 1.0], [1.0, 2.0], [0.0, 2.0]]
+    n = len(outline)
+    vertices = [[x, y, 0.0] for x, y in outline] + [[x, y, 1.0] for x, y in outline]
+
+    bottom = [[0, 3, 1], [1, 3, 2], [0, 5, 3], [3, 5, 4]]
+    faces = bottom + [[a + n, c + n, b + n] for a, b, c in bottom]
+    for i in range(n):
+        j = (i + 1) % n
+        faces += [[i, j, j + n], [i, j + n, i + n]]
+
+    return meshalyzer.Mesh(vertices, faces)
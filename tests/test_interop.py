@@ -0,0 +1,59 @@
+import pytest
+
+import meshalyzer
+
+
+class _MeshLike:
+    def __init__(self, vertices, faces):
+        self.vertices = vertices
+        self.faces = faces
+
+
+def test_from_trimesh_duck_typed():
+    mesh = meshalyzer.Mesh.from_trimesh(_MeshLike([[0, 0, 0], [1, 0, 0], [0, 1, 0]], [[0, 1, 2]]))
+
+    assert mesh.vertex_count() == 3
+    assert mesh.faces == [[0, 1, 2]]
+
+
+def test_from_trimesh_rejects_other_objects():
+    with pytest.raises(TypeError):
+        meshalyzer.Mesh.from_trimesh(object())
+
+
+def test_trimesh_round_trip():
+    trimesh = pytest.importorskip("trimesh")
+
+    original = trimesh.creation.icosphere(subdivisions=2)
+    mesh = meshalyzer.Mesh.from_trimesh(original)
+    back = mesh.to_trimesh()
+
+    assert mesh.face_count() == len(original.faces)
+    assert (back.faces == original.faces).all()
+    assert abs(back.vertices - original.vertices).max() < 1e-6
+
+
+def test_buffers_round_trip_exactly():
+    from meshes import uv_sphere
+
+    positions, indices = uv_sphere().to_buffers()
+    assert len(positions) == 3 * uv_sphere().vertex_count()
+    assert len(indices) == 3 * uv_sphere().face_count()
+
+    mesh = meshalyzer.Mesh.from_buffers(positions, indices)
+    assert mesh.to_buffers() == (positions, indices)
+    assert mesh.faces == uv_sphere().faces
+
+    with pytest.raises(ValueError):
+        meshalyzer.Mesh.from_buffers([0.0, 0.0, 0.0, 1.0], [0, 0, 0])
+    with pytest.raises(ValueError):
+        meshalyzer.Mesh.from_buffers([0.0] * 9, [0, 1])
+    with pytest.raises(IndexError):
+        meshalyzer.Mesh.from_buffers([0.0] * 9, [0, 1, 3])
+
+    # flat normals ride along, one xyz per position
+    normals = [0.0, 0.0, 1.0] * 3
+    flat = meshalyzer.Mesh.from_buffers([0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0], [0, 1, 2], normals)
+    assert flat.interpolate_at(0, [0.2, 0.3, 0.5])[1] == [0.0, 0.0, 1.0]
+    with pytest.raises(ValueError, match="normal"):
+        meshalyzer.Mesh.from_buffers([0.0] * 9, [0, 1, 2], [0.0] * 6)
@@ -0,0 +1,500 @@
+import meshalyzer
+
+
+def test_from_obj_triangulates_quads_with_mixed_references(tmp_path):
+    path = tmp_path / "cube.obj"
+    path.write_text(
+        "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\n"
+        "v 0 0 1\nv 1 0 1\nv 1 1 1\nv 0 1 1\n"
+        "vt 0 0\nvn 0 0 1\n"
+        "f 1 4 3 2\n"
+        "f 5/1 6/1 7/1 8/1\n"
+        "f 1//1 2//1 6//1 5//1\n"
+        "f 2/1/1 3/1/1 7/1/1 6/1/1\n"
+        "f -5 -1 -2 -6\n"
+        "f 4 1 5 8\n"
+    )
+
+    mesh = meshalyzer.Mesh.from_obj(str(path))
+
+    assert mesh.vertex_count() == 8
+    assert mesh.face_count() == 12
+    assert abs(mesh.compute_surface_area() - 6.0) < 1e-5
+
+
+def test_from_obj_rejects_bad_face_references(tmp_path):
+    import pytest
+
+    for face in ["f 1 2 x", "f 1 2 0", "f 1 2 4", "f 1 2", "f 1/1/1/1 2 3"]:
+        path = tmp_path / "bad.obj"
+        path.write_text("v 0 0 0\nv 1 0 0\nv 0 1 0\n" + face + "\n")
+
+        with pytest.raises(ValueError, match=":4:"):
+            meshalyzer.Mesh.from_obj(str(path))
+
+
+def test_save_ply_binary_layout(tmp_path):
+    import struct
+
+    mesh = meshalyzer.Mesh([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+                           [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]])
+    path = tmp_path / "tetra.ply"
+    mesh.save_ply(str(path))
+
+    data = path.read_bytes()
+    header_end = data.index(b"end_header\n") + len(b"end_header\n")
+    header = data[:header_end].decode()
+    body = data[header_end:]
+
+    assert "format binary_little_endian 1.0" in header
+    assert "element vertex 4" in header
+    assert "element face 4" in header
+    assert len(body) == 4 * 12 + 4 * 13
+    assert struct.unpack_from("<3f", body, 12) == (1.0, 0.0, 0.0)
+    assert struct.unpack_from("<B3i", body, 48 + 13) == (3, 0, 1, 3)
+
+
+def test_save_ply_ascii(tmp_path):
+    mesh = meshalyzer.Mesh([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]], [[0, 1, 2]])
+    path = tmp_path / "triangle.ply"
+    mesh.save_ply(str(path), binary=False)
+
+    lines = path.read_text().splitlines()
+    assert lines[1] == "format ascii 1.0"
+    assert lines[-4:] == ["0 0 0", "1 0 0", "0 1 0", "3 0 1 2"]
+
+
+def test_save_vtk_with_point_and_cell_scalars(tmp_path):
+    import pytest
+
+    mesh = meshalyzer.Mesh([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [1.0, 1.0, 0.0]], [[0, 1, 2], [1, 3, 2]])
+    path = tmp_path / "quad.vtk"
+    mesh.save_vtk(str(path))
+    lines = path.read_text().splitlines()
+    assert lines[:5] == ["# vtk DataFile Version 3.0", "meshalyzer mesh", "ASCII", "DATASET POLYDATA", "POINTS 4 float"]
+    assert lines[9:] == ["POLYGONS 2 8", "3 0 1 2", "3 1 3 2"]
+
+    mesh.compute_normals()
+    mesh.save_vtk_with_scalars(str(path), "height", [0.0, 0.5, 1.0, 1.5])
+    lines = path.read_text().splitlines()
+    assert lines[12:14] == ["POINT_DATA 4", "NORMALS normals float"]
+    assert lines[18:] == ["SCALARS height float 1", "LOOKUP_TABLE default", "0", "0.5", "1", "1.5"]
+
+    mesh.save_vtk_with_scalars(str(path), "quality", [0.25, 0.75])
+    lines = path.read_text().splitlines()
+    assert lines[-5:] == ["CELL_DATA 2", "SCALARS quality float 1", "LOOKUP_TABLE default", "0.25", "0.75"]
+
+    with pytest.raises(ValueError, match="3 values"):
+        mesh.save_vtk_with_scalars(str(path), "bad", [0.0, 1.0, 2.0])
+    with pytest.raises(ValueError, match="single word"):
+        mesh.save_vtk_with_scalars(str(path), "two words", [0.25, 0.75])
+
+
+def test_binary_round_trip_is_exact(tmp_path):
+    import pytest
+    from meshes import uv_sphere
+
+    mesh = uv_sphere().translated(0.1, 0.2, 0.3)
+    mesh.compute_normals()
+    first, second = tmp_path / "a.bin", tmp_path / "b.bin"
+
+    mesh.save_bin(str(first))
+    loaded = meshalyzer.Mesh.load_bin(str(first))
+    loaded.save_bin(str(second))
+
+    assert loaded.faces == mesh.faces
+    assert loaded.compute_face_normals() == mesh.compute_face_normals()
+    assert first.read_bytes() == second.read_bytes()
+
+    corrupt = tmp_path / "corrupt.bin"
+    corrupt.write_bytes(b"MSHB" + (99).to_bytes(4, "little") + first.read_bytes()[8:])
+    with pytest.raises(ValueError, match="version"):
+        meshalyzer.Mesh.load_bin(str(corrupt))
+
+    corrupt.write_bytes(b"PLY?" + first.read_bytes()[4:])
+    with pytest.raises(ValueError):
+        meshalyzer.Mesh.load_bin(str(corrupt))
+
+    # counts far beyond what the file holds fail instead of allocating them
+    header = first.read_bytes()[:12]
+    for vertex_count in (2**40, 2**62):
+        corrupt.write_bytes(header + vertex_count.to_bytes(8, "little") + (1).to_bytes(8, "little"))
+        with pytest.raises(ValueError, match="truncated"):
+            meshalyzer.Mesh.load_bin(str(corrupt))
+    corrupt.write_bytes(first.read_bytes()[:-1])
+    with pytest.raises(ValueError, match="truncated"):
+        meshalyzer.Mesh.load_bin(str(corrupt))
+
+
+def test_binary_round_trip_keeps_colors(tmp_path):
+    from meshes import uv_sphere
+
+    mesh = uv_sphere()
+    mesh.colorize_from_scalars([float(i) for i in range(mesh.vertex_count())], "grayscale")
+    path = tmp_path / "colored.bin"
+
+    mesh.save_bin(str(path))
+    loaded = meshalyzer.Mesh.load_bin(str(path))
+    assert loaded.colors == mesh.colors
+    assert loaded.faces == mesh.faces
+
+    # files from before colors were stored still load, without colors
+    plain = uv_sphere()
+    plain.save_bin(str(path))
+    path.write_bytes(b"MSHB" + (1).to_bytes(4, "little") + path.read_bytes()[8:])
+    old = meshalyzer.Mesh.load_bin(str(path))
+    assert old.colors is None
+    assert old.faces == plain.faces
+
+
+def test_binary_round_trip_keeps_face_groups(tmp_path):
+    obj = tmp_path / "two_materials.obj"
+    obj.write_text(
+        "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nv 2 0 0\nv 2 1 0\n"
+        "usemtl r\u00f8d\n"
+        "f 1 2 3 4\n"
+        "usemtl blue\n"
+        "f 2 5 6\n",
+        encoding="utf-8",
+    )
+    mesh = meshalyzer.Mesh.from_obj(str(obj))
+    path = tmp_path / "grouped.bin"
+
+    mesh.save_bin(str(path))
+    loaded = meshalyzer.Mesh.load_bin(str(path))
+    assert loaded.group_names == ["r\u00f8d", "blue"]
+    assert loaded.face_groups == mesh.face_groups == [0, 0, 1]
+    assert loaded.faces_in_group(1) == [2]
+
+    obj.write_text("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n")
+    meshalyzer.Mesh.from_obj(str(obj)).save_bin(str(path))
+    assert meshalyzer.Mesh.load_bin(str(path)).face_groups is None
+
+
+def test_binary_round_trip_keeps_uvs(tmp_path):
+    from meshes import uv_sphere
+
+    mesh = uv_sphere()
+    mesh.generate_uvs("spherical")
+    path = tmp_path / "textured.bin"
+
+    mesh.save_bin(str(path))
+    loaded = meshalyzer.Mesh.load_bin(str(path))
+    assert loaded.uvs == mesh.uvs
+    assert loaded.faces == mesh.faces
+    assert loaded.colors is None and loaded.face_groups is None
+
+
+def test_from_obj_resolves_face_referenced_normals(tmp_path):
+    import pytest
+
+    path = tmp_path / "cube.obj"
+    path.write_text(
+        "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\n"
+        "v 0 0 1\nv 1 0 1\nv 1 1 1\nv 0 1 1\n"
+        "vn 0 0 -1\nvn 0 0 1\nvn 0 -1 0\nvn 1 0 0\nvn 0 1 0\nvn -1 0 0\n"
+        "f 1//1 4//1 3//1 2//1\n"
+        "f 5//2 6//2 7//2 8//2\n"
+        "f 1//3 2//3 6//3 5//3\n"
+        "f 2//4 3//4 7//4 6//4\n"
+        "f 4//5 8//5 7//5 3//5\n"
+        "f 4//6 1//6 5//6 8//6\n"
+    )
+
+    mesh = meshalyzer.Mesh.from_obj(str(path))
+
+    assert mesh.faces[0][0] == 0
+    _, normal = mesh.interpolate_at(0, [1.0, 0.0, 0.0])
+    assert normal is not None
+    assert all(abs(c + 3 ** -0.5) < 1e-6 for c in normal)
+
+    path.write_text("v 0 0 0\nv 1 0 0\nv 0 1 0\nvn 0 0 1\nf 1//1 2//1 3//2\n")
+    with pytest.raises(ValueError, match="missing normal"):
+        meshalyzer.Mesh.from_obj(str(path))
+
+
+def test_from_obj_can_reject_non_finite_coordinates(tmp_path):
+    import pytest
+
+    path = tmp_path / "corrupt.obj"
+    path.write_text("v 0 0 0\nv 1 0 0\nv 0 inf 0\nf 1 2 3\n")
+
+    assert meshalyzer.Mesh.from_obj(str(path)).has_non_finite()
+    with pytest.raises(ValueError, match=":3:"):
+        meshalyzer.Mesh.from_obj(str(path), reject_non_finite=True)
+
+
+def test_save_with_fixed_precision(tmp_path):
+    import pytest
+    from meshes import uv_sphere
+
+    mesh = uv_sphere().translated(0.1, 0.2, 0.3)
+    full, short = tmp_path / "full.obj", tmp_path / "short.obj"
+    mesh.save_obj(str(full))
+    mesh.save_obj(str(short), precision=6)
+
+    assert short.stat().st_size < full.stat().st_size
+    assert all(len(token.split(".")[1]) == 6 for line in short.read_text().splitlines()
+               if line.startswith("v ") for token in line.split()[1:])
+
+    loaded = meshalyzer.Mesh.from_obj(str(short))
+    assert loaded.faces == mesh.faces
+    for f in range(mesh.face_count()):
+        for corner in ([1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]):
+            original = mesh.interpolate_at(f, corner)[0]
+            reloaded = loaded.interpolate_at(f, corner)[0]
+            assert max(abs(a - b) for a, b in zip(original, reloaded)) <= 1e-6
+
+    path = tmp_path / "triangle.ply"
+    triangle = meshalyzer.Mesh([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]], [[0, 1, 2]])
+    triangle.save_ply(str(path), binary=False, precision=2)
+    assert path.read_text().splitlines()[-4:-1] == ["0.00 0.00 0.00", "1.00 0.00 0.00", "0.00 1.00 0.00"]
+
+    broken = meshalyzer.Mesh([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, float("nan"), 0.0]], [[0, 1, 2]])
+    with pytest.raises(ValueError, match="sanitize"):
+        broken.save_obj(str(tmp_path / "broken.obj"))
+    with pytest.raises(ValueError, match="sanitize"):
+        broken.save_ply(str(tmp_path / "broken.ply"), binary=False)
+    broken.save_ply(str(tmp_path / "broken.ply"))
+
+
+def test_mesh_f64_keeps_utm_coordinates_through_obj(tmp_path):
+    from meshes import CUBE_FACES, CUBE_VERTICES
+
+    # a 1 m cube at UTM-like coordinates, where f32 steps are half a meter apart
+    easting, northing = 500123.25, 5412345.75
+    vertices = [[easting + x, northing + y, 100.0 + z] for x, y, z in CUBE_VERTICES]
+    mesh = meshalyzer.MeshF64(vertices, CUBE_FACES)
+    assert abs(mesh.compute_volume() - 1.0) < 1e-6
+    assert abs(mesh.compute_surface_area() - 6.0) < 1e-6
+
+    path = tmp_path / "site.obj"
+    mesh.save_obj(str(path))
+    loaded = meshalyzer.MeshF64.from_obj(str(path))
+    assert loaded.vertices == vertices
+    assert loaded.faces == mesh.faces
+    assert loaded.compute_bounds() == ([easting, northing, 100.0], [easting + 1.0, northing + 1.0, 101.0])
+
+    # single precision loses the centimeters, unless the data is moved to the origin first
+    assert meshalyzer.Mesh.from_obj(str(path)).compute_bounds()[0] != [easting, northing, 100.0]
+    local = loaded.to_f32(origin=[easting, northing, 100.0])
+    assert local.compute_bounds() == ([0.0, 0.0, 0.0], [1.0, 1.0, 1.0])
+    assert local.to_f64().translated(easting, northing, 100.0).vertices == vertices
+
+
+def test_mesh_f64_measures_and_transforms_like_mesh():
+    import pytest
+    from meshes import uv_sphere
+
+    # near the origin both precisions agree on everything they measure
+    sphere = uv_sphere()
+    double = sphere.to_f64()
+    assert double.compute_surface_area() == pytest.approx(sphere.compute_surface_area(), rel=1e-5)
+    assert double.compute_volume() == pytest.approx(sphere.compute_volume(), rel=1e-5)
+    assert all(abs(a - b) < 1e-6 for a, b in zip(double.face_areas(), sphere.face_areas()))
+    for a, b in zip(double.compute_face_normals(), sphere.compute_face_normals()):
+        assert all(abs(x - y) < 1e-5 for x, y in zip(a, b))
+    assert all(abs(a - b) < 1e-6 for a, b in zip(double.centroid(), sphere.centroid()))
+    assert double.characteristic_length() == pytest.approx(sphere.characteristic_length())
+    assert double.is_watertight() and sphere.is_watertight()
+    assert double.winding_number([0.0, 0.0, 0.0]) == pytest.approx(sphere.winding_number([0.0, 0.0, 0.0]))
+    assert double.contains_point_robust([0.1, 0.0, 0.0]) and not double.contains_point_robust([5.0, 0.0, 0.0])
+
+    matrix = [[0.0, 2.0, 0.0, 1.0], [-2.0, 0.0, 0.0, 2.0], [0.0, 0.0, 2.0, 3.0], [0.0, 0.0, 0.0, 1.0]]
+    sphere.transform(matrix)
+    double.transform(matrix)
+    for a, b in zip(double.compute_bounds(), sphere.compute_bounds()):
+        assert all(abs(x - y) < 1e-5 for x, y in zip(a, b))
+    assert double.scaled(0.5).compute_volume() == pytest.approx(sphere.scaled(0.5).compute_volume(), rel=1e-5)
+    with pytest.raises(ValueError, match="affine"):
+        double.transform([[1.0, 0.0, 0.0, 0.0]] * 4)
+
+    # far from it only double precision still resolves a centimeter sized offset
+    far = double.translated(6378137.0, 0.0, 0.0)
+    centroid = far.centroid()
+    assert centroid[0] - 6378137.0 == pytest.approx(double.centroid()[0], abs=1e-6)
+    assert far.winding_number([6378137.0 + 1.0, 2.0, 3.0]) == pytest.approx(1.0)
+
+
+def test_mesh_f64_from_obj_reads_like_mesh_from_obj(tmp_path):
+    import pytest
+
+    # a quad with relative indices and normals, which MeshF64 skips
+    path = tmp_path / "quad.obj"
+    path.write_text(
+        "v 500000.125 0 0\nv 500001.125 0 0\nv 500001.125 1 0\nv 500000.125 1 0\n"
+        "vn 0 0 1\n"
+        "usemtl ground\n"
+        "f -4//1 -3//1 -2//1 -1//1\n"
+    )
+    loaded = meshalyzer.MeshF64.from_obj(str(path))
+    assert loaded.faces == meshalyzer.Mesh.from_obj(str(path)).faces == [[0, 1, 2], [0, 2, 3]]
+    assert loaded.vertices[0] == [500000.125, 0.0, 0.0]
+
+    path.write_text("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 4\n")
+    with pytest.raises(ValueError, match=":4: face vertex '4' refers to a missing vertex"):
+        meshalyzer.MeshF64.from_obj(str(path))
+    path.write_text("v 0 0 0\nv inf 0 0\nv 0 1 0\nf 1 2 3\n")
+    assert meshalyzer.MeshF64.from_obj(str(path)).vertex_count() == 3
+    with pytest.raises(ValueError, match="non-finite"):
+        meshalyzer.MeshF64.from_obj(str(path), reject_non_finite=True)
+
+
+def test_from_obj_resolves_indices_across_large_files(tmp_path):
+    import pytest
+
+    # a few MB so the file is parsed in several chunks, faces alternate absolute and relative
+    # indices and carry normals, which all have to line up across the chunk boundaries
+    lines = []
+    expected = []
+    vertex_count = 0
+    for i in range(60000):
+        lines += ["v %d 0 0" % i, "v %d 1 0" % i, "v %d 0 1" % i, "vn 0 0 1", "# strip %d" % i]
+        vertex_count += 3
+        if i % 2:
+            lines.append("f -3//-1 -2//-1 -1//-1")
+        else:
+            lines.append("f %d %d %d" % (vertex_count - 2, vertex_count - 1, vertex_count))
+        expected.append([vertex_count - 3, vertex_count - 2, vertex_count - 1])
+
+    path = tmp_path / "large.obj"
+    path.write_text("\n".join(lines) + "\n")
+    assert path.stat().st_size > 3 << 20
+
+    mesh = meshalyzer.Mesh.from_obj(str(path))
+    assert mesh.vertex_count() == vertex_count
+    assert mesh.faces == expected
+    assert "has_normals=true" in repr(mesh)
+
+    # the first bad line is reported with its number in the whole file
+    lines[-7] = "f 1 2 %d" % (vertex_count + 1)
+    lines[-1] = "f 1 2 x"
+    path.write_text("\n".join(lines) + "\n")
+    with pytest.raises(ValueError, match=":%d: face vertex" % (len(lines) - 6)):
+        meshalyzer.Mesh.from_obj(str(path))
+
+
+def test_errors_are_typed(tmp_path):
+    import pytest
+    from meshes import CUBE_FACES, CUBE_VERTICES
+
+    path = tmp_path / "broken.obj"
+    path.write_text("v 0 0 0\nv 1 0 0\nf 1 2 3\n")
+    with pytest.raises(meshalyzer.MeshParseError, match=":3:"):
+        meshalyzer.Mesh.from_obj(str(path))
+
+    open_box = meshalyzer.Mesh(CUBE_VERTICES, CUBE_FACES[:-1])
+    with pytest.raises(meshalyzer.MeshTopologyError, match="watertight"):
+        open_box.signed_distance([[0.5, 0.5, 0.5]])
+    with pytest.raises(meshalyzer.MeshGeometryError):
+        meshalyzer.Mesh([], []).closest_point([0.0, 0.0, 0.0])
+
+    for error in (meshalyzer.MeshParseError, meshalyzer.MeshTopologyError, meshalyzer.MeshGeometryError):
+        assert issubclass(error, meshalyzer.MeshError)
+        assert issubclass(error, ValueError)
+
+
+def test_save_wireframe_obj_writes_each_edge_once(tmp_path):
+    from meshes import CUBE_FACES, CUBE_VERTICES
+
+    path = tmp_path / "wire.obj"
+    meshalyzer.Mesh(CUBE_VERTICES, CUBE_FACES).save_wireframe_obj(str(path))
+    lines = path.read_text().splitlines()
+    assert sum(line.startswith("v ") for line in lines) == 8
+    edges = [tuple(sorted(map(int, line.split()[1:]))) for line in lines if line.startswith("l ")]
+    assert len(edges) == len(set(edges)) == 18
+
+    open_box = meshalyzer.Mesh(CUBE_VERTICES, CUBE_FACES[:-2])
+    open_box.save_wireframe_obj(str(path), group_boundary=True)
+    groups = {}
+    for line in path.read_text().splitlines():
+        if line.startswith("g "):
+            current = line.split()[1]
+        elif line.startswith("l "):
+            groups[current] = groups.get(current, 0) + 1
+    assert groups == {"interior": 13, "boundary": 4}
+
+
+def test_gzip_files_round_trip(tmp_path):
+    import gzip
+    from meshes import uv_sphere
+
+    sphere = uv_sphere()
+    sphere.compute_normals()
+    sphere.save_obj(str(tmp_path / "sphere.obj"))
+    sphere.save_obj(str(tmp_path / "sphere.obj.gz"))
+    assert (tmp_path / "sphere.obj.gz").read_bytes()[:2] == b"\x1f\x8b"
+
+    plain = meshalyzer.Mesh.from_obj(str(tmp_path / "sphere.obj"))
+    packed = meshalyzer.Mesh.from_obj(str(tmp_path / "sphere.obj.gz"))
+    assert packed.faces == plain.faces
+    for f in range(0, plain.face_count(), 7):
+        assert packed.interpolate_at(f, [0.2, 0.3, 0.5]) == plain.interpolate_at(f, [0.2, 0.3, 0.5])
+
+    # detected by content, not by name
+    (tmp_path / "renamed.obj").write_bytes((tmp_path / "sphere.obj.gz").read_bytes())
+    assert meshalyzer.Mesh.from_obj(str(tmp_path / "renamed.obj")).faces == plain.faces
+
+    sphere.save_ply(str(tmp_path / "sphere.ply.gz"), binary=False)
+    assert gzip.decompress((tmp_path / "sphere.ply.gz").read_bytes()).startswith(b"ply\n")
+
+    sphere.save_bin(str(tmp_path / "sphere.bin.gz"))
+    assert meshalyzer.Mesh.load_bin(str(tmp_path / "sphere.bin.gz")).faces == plain.faces
+
+
+def test_load_dir_collects_meshes_and_errors(tmp_path):
+    from meshes import unit_cube, uv_sphere
+
+    unit_cube().save_obj(str(tmp_path / "a.obj"))
+    uv_sphere().save_obj(str(tmp_path / "b.obj.gz"))
+    unit_cube().save_bin(str(tmp_path / "c.bin"))
+    (tmp_path / "broken.obj").write_text("v 0 0 0\nf 1 2 3\n")
+    (tmp_path / "notes.txt").write_text("not a mesh\n")
+    (tmp_path / "sub.obj").mkdir()
+
+    meshes, errors = meshalyzer.load_dir(str(tmp_path), "*.obj*")
+    assert [name.split("/")[-1] for name, _ in meshes] == ["a.obj", "b.obj.gz"]
+    assert meshes[0][1].face_count() == 12
+    assert meshes[1][1].face_count() == uv_sphere().face_count()
+    assert [name.split("/")[-1] for name, _ in errors] == ["broken.obj"]
+    assert "MeshParseError" in errors[0][1] and ":2:" in errors[0][1]
+
+    meshes, errors = meshalyzer.load_dir(str(tmp_path), "?.*")
+    assert [name.split("/")[-1] for name, _ in meshes] == ["a.obj", "b.obj.gz", "c.bin"]
+    assert errors == []
+
+    meshes, errors = meshalyzer.load_dir(str(tmp_path), "*.txt")
+    assert meshes == [] and "unsupported" in errors[0][1]
+
+
+def test_from_obj_reads_material_groups(tmp_path):
+    path = tmp_path / "two_materials.obj"
+    path.write_text(
+        "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nv 2 0 0\nv 2 1 0\n"
+        "g panel\n"
+        "usemtl red\n"
+        "f 1 2 3 4\n"
+        "usemtl blue\n"
+        "g trim\n"
+        "f 2 5 6\n"
+        "usemtl red\n"
+        "f 2 6 3\n"
+    )
+
+    mesh = meshalyzer.Mesh.from_obj(str(path))
+    assert mesh.group_names == ["red", "blue"]
+    assert mesh.face_groups == [0, 0, 1, 0]
+    assert mesh.faces_in_group(0) == [0, 1, 3]
+    assert mesh.faces_in_group(1) == [2]
+    assert mesh.submesh(mesh.faces_in_group(1)).face_groups == [1]
+
+    mesh.save_obj(str(tmp_path / "saved.obj"))
+    loaded = meshalyzer.Mesh.from_obj(str(tmp_path / "saved.obj"))
+    assert loaded.group_names == ["red", "blue"]
+    assert loaded.face_groups == mesh.face_groups
+
+    path.write_text("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n")
+    assert meshalyzer.Mesh.from_obj(str(path)).face_groups is None
+    # object groups alone don't make face groups
+    path.write_text("v 0 0 0\nv 1 0 0\nv 0 1 0\ng panel\nf 1 2 3\n")
+    assert meshalyzer.Mesh.from_obj(str(path)).face_groups is None
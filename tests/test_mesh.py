@@ -0,0 +1,1892 @@
+import meshalyzer
+
+
+def test_remove_isolated_vertices():
+    vertices = [[float(i), float(i % 3), 0.0] for i in range(10)]
+    faces = [[0, 2, 4], [4, 6, 8], [8, 9, 0]]
+    mesh = meshalyzer.Mesh(vertices, faces)
+
+    analyzer = meshalyzer.PyTopologyAnalyzer(mesh)
+    assert analyzer.isolated_vertices() == [1, 3, 5, 7]
+
+    assert mesh.remove_isolated_vertices() == 4
+    assert mesh.vertex_count() == 6
+    assert mesh.find_isolated_vertices() == []
+    assert mesh.faces == [[0, 1, 2], [2, 3, 4], [4, 5, 0]]
+
+
+def test_obb_of_rotated_box():
+    import math
+
+    angle = math.radians(30.0)
+    c, s = math.cos(angle), math.sin(angle)
+    corners = [[x, y, z] for x in (-4.0, 4.0) for y in (-1.0, 1.0) for z in (-0.5, 0.5)]
+    rotated = [[c * x - s * y, s * x + c * y, z] for x, y, z in corners]
+    mesh = meshalyzer.Mesh(rotated, [[0, 1, 2]])
+
+    center, axes, half_extents = mesh.compute_obb()
+
+    assert all(abs(v) < 1e-4 for v in center)
+    assert abs(abs(axes[0][0] * c + axes[0][1] * s) - 1.0) < 1e-4
+    assert all(abs(a - b) < 1e-3 for a, b in zip(half_extents, [4.0, 1.0, 0.5]))
+
+
+def test_repair_closes_open_cube():
+    from meshes import CUBE_FACES, CUBE_VERTICES
+
+    # a duplicated corner, a reversed face and a missing face
+    vertices = CUBE_VERTICES + [[1.0, 1.0, 1.0]]
+    faces = CUBE_FACES[:11]
+    faces[3] = [5, 6, 8]
+    mesh = meshalyzer.Mesh(vertices, faces)
+
+    report = mesh.repair(True, 1e-6)
+
+    assert report["vertices_merged"] == 1
+    assert report["holes_filled"] == 1
+    assert report["watertight"]
+    assert report["orientation_consistent"]
+    assert mesh.vertex_count() == 8
+    assert mesh.face_count() == 12
+    assert meshalyzer.PyTopologyAnalyzer(mesh).is_watertight()
+
+
+def test_repair_keeps_closest_point_in_step_with_reoriented_faces():
+    from meshes import uv_sphere
+
+    # every third face wound inward, nothing to merge or remove
+    positions, indices = uv_sphere().to_buffers()
+    for f in range(0, len(indices) // 3, 3):
+        indices[3 * f + 1], indices[3 * f + 2] = indices[3 * f + 2], indices[3 * f + 1]
+    mesh = meshalyzer.Mesh.from_buffers(positions, indices)
+    query = [0.4, -0.1, 1.2]
+    mesh.closest_point(query)
+
+    report = mesh.repair(False, 0.0)
+    assert report["orientation_consistent"]
+    assert report["vertices_merged"] == report["degenerate_faces_removed"] == 0
+
+    positions, indices = mesh.to_buffers()
+    fresh = meshalyzer.Mesh.from_buffers(positions, indices)
+    face, point, bary, _ = mesh.closest_point(query)
+    expected = fresh.closest_point(query)
+    assert face == expected[0]
+    assert all(abs(b - e) < 1e-6 for b, e in zip(bary, expected[2]))
+    assert all(abs(p - q) < 1e-5 for p, q in zip(mesh.interpolate_at(face, bary)[0], point))
+
+
+def test_taubin_smooth_validates_parameters():
+    import pytest
+
+    mesh = meshalyzer.Mesh([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]], [[0, 1, 2]])
+
+    with pytest.raises(ValueError):
+        mesh.taubin_smooth(1, 0.5, -0.4)
+    with pytest.raises(ValueError):
+        mesh.taubin_smooth(1, 0.0, -0.5)
+
+    mesh.taubin_smooth(3, 0.5, -0.53)
+    (_, _, min_z), (_, _, max_z) = mesh.compute_bounds()
+    assert min_z == 0.0 and max_z == 0.0
+
+
+def test_taubin_smooth_removes_noise_without_shrinking():
+    import math
+    import random
+    from meshes import uv_sphere
+
+    def noisy_sphere():
+        positions, indices = uv_sphere(rings=24, segments=48).to_buffers()
+        rng = random.Random(3)
+        for i in range(0, len(positions), 3):
+            scale = 1.0 + rng.uniform(-0.03, 0.03)
+            positions[i:i + 3] = [c * scale for c in positions[i:i + 3]]
+        return meshalyzer.Mesh.from_buffers(positions, indices)
+
+    def radii(mesh):
+        positions, _ = mesh.to_buffers()
+        radii = [math.sqrt(sum(c * c for c in positions[i:i + 3])) for i in range(0, len(positions), 3)]
+        mean = sum(radii) / len(radii)
+        return mean, math.sqrt(sum((r - mean) ** 2 for r in radii) / len(radii))
+
+    _, noise = radii(noisy_sphere())
+
+    taubin = noisy_sphere()
+    taubin.taubin_smooth(10, 0.5, -0.53)
+    radius, smoothed_noise = radii(taubin)
+    assert abs(radius - 1.0) < 0.01
+    assert smoothed_noise < 0.5 * noise
+
+    # as many plain Laplacian steps as taubin took in total lose several percent of the radius
+    laplacian = noisy_sphere()
+    laplacian.laplacian_smooth(20, 0.5)
+    assert radii(laplacian)[0] < 0.95
+
+
+def test_flip_normals_reverses_winding_and_normals():
+    from meshes import unit_cube
+
+    cube = unit_cube()
+    cube.compute_normals()
+    faces = cube.faces
+    volume = cube.compute_volume()
+    _, normal = cube.interpolate_at(0, [1.0, 0.0, 0.0])
+
+    cube.flip_normals()
+    assert cube.faces == [[a, c, b] for a, b, c in faces]
+    assert abs(cube.compute_volume() + volume) < 1e-6
+    assert cube.interpolate_at(0, [1.0, 0.0, 0.0])[1] == [-c for c in normal]
+
+    cube.flip_normals()
+    assert cube.faces == faces
+    assert cube.compute_volume() == volume
+
+
+def test_flip_normals_keeps_closest_point_barycentrics_in_face_order():
+    from meshes import uv_sphere
+
+    sphere = uv_sphere()
+    query = [0.3, 0.2, 1.5]
+    # built before the flip, so a stale BVH would report the old corner order
+    sphere.closest_point(query)
+    sphere.flip_normals()
+
+    positions, indices = sphere.to_buffers()
+    fresh = meshalyzer.Mesh.from_buffers(positions, indices)
+    face, point, bary, distance = sphere.closest_point(query)
+    expected = fresh.closest_point(query)
+    assert face == expected[0]
+    assert all(abs(b - e) < 1e-6 for b, e in zip(bary, expected[2]))
+    assert all(abs(p - q) < 1e-5 for p, q in zip(sphere.interpolate_at(face, bary)[0], point))
+
+
+def test_nearest_vertices_match_brute_force():
+    import math
+    import random
+
+    import pytest
+
+    rng = random.Random(7)
+    vertices = [[rng.uniform(-1, 1), rng.uniform(-1, 1), rng.uniform(-1, 1)] for _ in range(200)]
+    mesh = meshalyzer.Mesh(vertices, [])
+
+    for _ in range(20):
+        query = [rng.uniform(-1.5, 1.5) for _ in range(3)]
+        expected = sorted((math.dist(v, query), i) for i, v in enumerate(vertices))
+
+        index, distance = mesh.nearest_vertex(query)
+        assert index == expected[0][1]
+        assert abs(distance - expected[0][0]) < 1e-5
+
+        nearest = mesh.k_nearest_vertices(query, 5)
+        assert [i for i, _ in nearest] == [i for _, i in expected[:5]]
+
+    with pytest.raises(ValueError):
+        meshalyzer.Mesh([], []).nearest_vertex([0.0, 0.0, 0.0])
+
+
+def test_submesh_compacts_vertices():
+    import pytest
+
+    vertices = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0], [2.0, 0.0, 0.0]]
+    mesh = meshalyzer.Mesh(vertices, [[0, 1, 2], [0, 2, 3], [1, 4, 2]])
+
+    part = mesh.submesh([2, 2, 0])
+
+    assert part.faces == [[0, 1, 2], [3, 0, 2]]
+    assert part.vertex_count() == 4
+    with pytest.raises(IndexError):
+        mesh.submesh([3])
+
+
+def test_projected_area_of_square():
+    square = meshalyzer.Mesh(
+        [[0.0, 0.0, 0.0], [2.0, 0.0, 0.0], [2.0, 2.0, 0.0], [0.0, 2.0, 0.0]],
+        [[0, 1, 2], [0, 2, 3]],
+    )
+
+    assert square.compute_surface_area() == 4.0
+    assert square.projected_area([0.0, 0.0, 5.0]) == 4.0
+    assert square.projected_area([0.0, 0.0, -1.0]) == 0.0
+    assert square.projected_area([1.0, 0.0, 0.0]) == 0.0
+    assert square.silhouette_area([0.0, 0.0, -1.0]) == 4.0
+
+
+def test_cotangent_laplacian_is_symmetric_with_zero_row_sums():
+    octahedron = meshalyzer.Mesh(
+        [[1, 0, 0], [-1, 0, 0], [0, 1, 0], [0, -1, 0], [0, 0, 1], [0, 0, -1]],
+        [[0, 2, 4], [2, 1, 4], [1, 3, 4], [3, 0, 4], [2, 0, 5], [1, 2, 5], [3, 1, 5], [0, 3, 5]],
+    )
+
+    indices, values = octahedron.cotangent_laplacian()
+    matrix = dict(zip(indices, values))
+
+    row_sums = [0.0] * 6
+    for (row, col), value in matrix.items():
+        assert abs(value - matrix[(col, row)]) < 1e-6
+        row_sums[row] += value
+    assert all(abs(total) < 1e-5 for total in row_sums)
+
+    assert abs(sum(octahedron.mass_matrix()) - octahedron.compute_surface_area()) < 1e-5
+
+
+def test_flipped_faces_finds_reversed_triangle():
+    from meshes import CUBE_FACES, CUBE_VERTICES, unit_cube
+
+    assert unit_cube().flipped_faces() == []
+
+    faces = [list(face) for face in CUBE_FACES]
+    faces[4].reverse()
+
+    assert meshalyzer.Mesh(CUBE_VERTICES, faces).flipped_faces() == [4]
+
+
+def test_interpolate_at_barycentric_coordinates():
+    import pytest
+
+    mesh = meshalyzer.Mesh([[1.0, 2.0, 3.0], [4.0, 2.0, 3.0], [1.0, 5.0, 3.0]], [[0, 1, 2]])
+
+    assert mesh.interpolate_at(0, [1.0, 0.0, 0.0]) == ([1.0, 2.0, 3.0], None)
+    position, _ = mesh.interpolate_at(0, [0.0, 0.5, 0.5])
+    assert position == [2.5, 3.5, 3.0]
+
+    with pytest.raises(ValueError):
+        mesh.interpolate_at(0, [0.5, 0.5, 0.5])
+    with pytest.raises(IndexError):
+        mesh.interpolate_at(1, [1.0, 0.0, 0.0])
+
+
+def test_normalize_unit_sphere_and_cube():
+    import pytest
+
+    vertices = [[2.0, 1.0, 1.0], [6.0, 1.0, 1.0], [2.0, 3.0, 1.0], [2.0, 1.0, 2.0]]
+    faces = [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]]
+
+    mesh = meshalyzer.Mesh(vertices, faces)
+    mesh.normalize("unit_sphere")
+    assert all(abs(c) < 1e-6 for c in mesh.centroid())
+    _, max_radius = mesh.k_nearest_vertices([0.0, 0.0, 0.0], 4)[-1]
+    assert abs(max_radius - 1.0) < 1e-6
+
+    mesh = meshalyzer.Mesh(vertices, faces)
+    mesh.normalize("unit_cube")
+    low, high = mesh.compute_bounds()
+    for a, b in zip(low + high, [-0.5, -0.25, -0.125, 0.5, 0.25, 0.125]):
+        assert abs(a - b) < 1e-6
+
+    with pytest.raises(ValueError):
+        mesh.normalize("unit_ball")
+    with pytest.raises(ValueError):
+        meshalyzer.Mesh([[1.0, 1.0, 1.0]] * 3, [[0, 1, 2]]).normalize("unit_sphere")
+
+
+def test_ray_intersect_batch_hits_cube_faces():
+    import pytest
+    from meshes import unit_cube
+
+    mesh = unit_cube()
+    origins = [[0.5, 0.5, -1.0], [0.3, 0.6, 0.5], [2.0, 0.5, 0.5], [0.5, 0.5, 2.0], [0.0, 0.0, -1.0]]
+    directions = [[0.0, 0.0, 2.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [0.0, 0.0, 1.0]]
+
+    hits = mesh.ray_intersect_batch(origins, directions)
+
+    distance, face = hits[0]
+    assert abs(distance - 1.0) < 1e-6 and face in (0, 1)
+    distance, face = hits[1]
+    assert abs(distance - 0.7) < 1e-6 and face in (10, 11)
+    assert hits[2] is None
+    assert hits[3] is None
+    assert abs(hits[4][0] - 1.0) < 1e-6
+
+    distance, face = mesh.ray_intersect_batch([[-1.0, 0.5, 1.0]], [[1.0, 0.0, 0.0]])[0]
+    assert abs(distance - 1.0) < 1e-6 and face in (8, 9)
+
+    with pytest.raises(ValueError):
+        mesh.ray_intersect_batch(origins, directions[:2])
+
+
+def test_align_principal_axes_puts_the_long_side_on_x():
+    import math
+
+    from meshes import CUBE_FACES, CUBE_VERTICES
+
+    # a 4 x 2 x 1 box with an extra vertex off one end so the axis signs are well defined
+    vertices = [[4.0 * x, 2.0 * y, z] for x, y, z in CUBE_VERTICES] + [[6.0, 1.5, 0.8]]
+    c, s = math.cos(0.7), math.sin(0.7)
+    posed = [[c * x - s * y + 3.0, s * x + c * y - 1.0, z] for x, y, z in vertices]
+    turned = [[z, -y, x] for x, y, z in posed]
+
+    def aligned_corners(vertices):
+        mesh = meshalyzer.Mesh(vertices, CUBE_FACES)
+        mesh.align_principal_axes()
+        (min_x, min_y, min_z), (max_x, max_y, max_z) = mesh.compute_bounds()
+        assert max_x - min_x > max_y - min_y > max_z - min_z
+
+        corners = [mesh.interpolate_at(f, [1.0, 0.0, 0.0])[0] for f in range(len(CUBE_FACES))]
+        return [[p[0] - min_x, p[1] - min_y, p[2] - min_z] for p in corners]
+
+    # the same shape in two poses ends up in the same orientation
+    for p, q in zip(aligned_corners(posed), aligned_corners(turned)):
+        assert all(abs(a - b) < 1e-4 for a, b in zip(p, q))
+
+
+def test_detect_symmetry_plane_of_a_pyramid():
+    import pytest
+    from meshes import uv_sphere
+
+    def pyramid(apex):
+        vertices = [[-2.0, -1.0, 0.0], [2.0, -1.0, 0.0], [2.0, 1.0, 0.0], [-2.0, 1.0, 0.0], apex]
+        faces = [[0, 2, 1], [0, 3, 2], [0, 1, 4], [1, 2, 4], [2, 3, 4], [3, 0, 4]]
+        return meshalyzer.Mesh(vertices, faces)
+
+    # with the apex off center along x only the y = 0 plane is left
+    point, normal = pyramid([1.0, 0.0, 3.0]).detect_symmetry_plane(1e-4)
+    assert all(abs(a - b) < 1e-5 for a, b in zip(normal, [0.0, 1.0, 0.0]))
+    assert abs(point[1]) < 1e-5
+
+    assert pyramid([1.0, 0.7, 3.0]).detect_symmetry_plane(0.05) is None
+    assert uv_sphere().detect_symmetry_plane(1e-4) is not None
+    assert meshalyzer.Mesh([], []).detect_symmetry_plane(0.1) is None
+    with pytest.raises(ValueError):
+        uv_sphere().detect_symmetry_plane(float("nan"))
+
+
+def test_len_and_truthiness():
+    from meshes import CUBE_VERTICES, unit_cube
+
+    cube = unit_cube()
+    assert len(cube) == 12
+    assert not cube.is_empty()
+    assert cube
+
+    points = meshalyzer.Mesh(CUBE_VERTICES, [])
+    assert len(points) == 0
+    assert points.is_empty()
+    assert not points
+
+    assert not meshalyzer.Mesh([], [])
+
+
+def test_bounds_follow_transforms():
+    import math
+    from meshes import unit_cube
+
+    cube = unit_cube()
+    assert cube.compute_bounds() == ([0.0, 0.0, 0.0], [1.0, 1.0, 1.0])
+    assert cube.translated(1.0, 2.0, 3.0).compute_bounds() == ([1.0, 2.0, 3.0], [2.0, 3.0, 4.0])
+    assert cube.scaled(-2.0).compute_bounds() == ([-2.0, -2.0, -2.0], [0.0, 0.0, 0.0])
+
+    # swap x and y, stretch z, then move
+    cube.transform([[0.0, 1.0, 0.0, 0.0], [1.0, 0.0, 0.0, 0.0], [0.0, 0.0, 3.0, -1.0], [0.0, 0.0, 0.0, 1.0]])
+    assert cube.compute_bounds() == ([0.0, 0.0, -1.0], [1.0, 1.0, 2.0])
+
+    # a rotation about z by 45 degrees gives the exact box, not the rotated old one
+    c = math.cos(math.radians(45.0))
+    cube = unit_cube()
+    cube.transform([[c, -c, 0.0, 0.0], [c, c, 0.0, 0.0], [0.0, 0.0, 1.0, 0.0], [0.0, 0.0, 0.0, 1.0]])
+    (min_x, min_y, _), (max_x, max_y, _) = cube.compute_bounds()
+    assert abs(min_x + c) < 1e-6 and abs(max_x - c) < 1e-6
+    assert abs(min_y) < 1e-6 and abs(max_y - 2.0 * c) < 1e-6
+
+    # smoothing drops the cached box by itself
+    cube.taubin_smooth(3, 0.5, -0.53)
+    smoothed = cube.compute_bounds()
+    assert smoothed[1][2] < 1.0
+    cube.invalidate_bounds()
+    assert cube.compute_bounds() == smoothed
+
+
+def test_union_bounds_matches_merged_bounds():
+    from meshes import unit_cube, uv_sphere
+
+    meshes = [unit_cube().translated(2.0, -1.0, 0.5), uv_sphere(radius=0.5), meshalyzer.Mesh([], [])]
+    merged = meshalyzer.Mesh.merge(meshalyzer.Mesh.merge(meshes[0], meshes[1]), meshes[2])
+    assert meshalyzer.union_bounds(meshes) == merged.compute_bounds()
+    assert meshalyzer.union_bounds(meshes[1:]) == meshes[1].compute_bounds()
+    assert meshalyzer.union_bounds([]) == ([0.0, 0.0, 0.0], [0.0, 0.0, 0.0])
+
+
+def test_transform_rejects_projective_matrices_and_singular_normal_maps():
+    import pytest
+    from meshes import unit_cube
+
+    cube = unit_cube()
+    with pytest.raises(ValueError, match="affine"):
+        cube.transform([[1.0, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0, 0.0], [0.0, 0.0, 1.0, 0.0], [0.0, 0.0, 1.0, 1.0]])
+
+    cube.compute_normals()
+    with pytest.raises(ValueError, match="singular"):
+        cube.transform([[1.0, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0, 0.0], [0.0, 0.0, 0.0, 0.0], [0.0, 0.0, 0.0, 1.0]])
+
+
+def test_vertex_normals_match_compute_normals_without_storing_them():
+    from meshes import uv_sphere
+
+    sphere = uv_sphere()
+    normals = sphere.vertex_normals()
+    assert len(normals) == sphere.vertex_count()
+    assert not sphere.stats()["has_normals"]
+
+    # the north pole and a vertex on the equator ring
+    assert all(abs(a - b) < 1e-6 for a, b in zip(normals[0], [0.0, 0.0, 1.0]))
+    assert abs(sum(c * c for c in normals[5 * 24 + 1]) - 1.0) < 1e-5
+
+    sphere.compute_normals()
+    for face in (0, 100, 300):
+        for corner in range(3):
+            bary = [0.0, 0.0, 0.0]
+            bary[corner] = 1.0
+            _, stored = sphere.interpolate_at(face, bary)
+            expected = normals[sphere.faces[face][corner]]
+            assert all(abs(a - b) < 1e-6 for a, b in zip(stored, expected))
+
+
+def test_dominant_normal_and_orientation_coverage():
+    import pytest
+    from meshes import unit_cube, uv_sphere
+
+    plane = meshalyzer.Mesh.plane(2.0, 1.0, 3)
+    assert all(abs(a - b) < 1e-6 for a, b in zip(plane.dominant_normal(), [0.0, 0.0, 1.0]))
+    assert abs(plane.orientation_coverage([0.0, 0.2, 1.0]) - 1.0) < 1e-6
+    assert plane.orientation_coverage([0.0, 0.0, -1.0]) == 0.0
+
+    # the normals of closed surfaces cancel out
+    assert all(abs(c) < 1e-6 for c in unit_cube().dominant_normal())
+    assert all(abs(c) < 1e-4 for c in uv_sphere().dominant_normal())
+
+    assert abs(unit_cube().orientation_coverage([0.0, 0.0, 1.0]) - 1.0 / 6.0) < 1e-6
+    assert abs(unit_cube().orientation_coverage([1.0, 1.0, 1.0]) - 0.5) < 1e-6
+    assert abs(uv_sphere().orientation_coverage([0.0, 0.0, 1.0]) - 0.5) < 1e-4
+
+    with pytest.raises(ValueError):
+        plane.orientation_coverage([0.0, 0.0, 0.0])
+
+
+def test_silhouette_edges():
+    import pytest
+    from meshes import unit_cube, uv_sphere
+
+    # the sides are edge-on, so the outline runs around the top and skips its diagonal
+    assert unit_cube().silhouette([0.0, 0.0, 1.0]) == [(4, 5), (4, 6), (5, 7), (6, 7)]
+    assert unit_cube().silhouette([0.0, 0.0, -2.0]) == [(0, 1), (0, 2), (1, 3), (2, 3)]
+
+    # seen from above, a sphere's outline is its equator
+    sphere = uv_sphere(rings=12, segments=24)
+    edges = sphere.silhouette([0.0, 0.0, 1.0])
+    assert len(edges) == 24
+    equator = {v for edge in edges for v in edge}
+    positions, _ = sphere.to_buffers()
+    assert len(equator) == 24
+    assert all(abs(positions[3 * v + 2]) < 1e-6 for v in equator)
+
+    # open surfaces keep their boundary whatever the direction
+    plane = meshalyzer.Mesh.plane(2.0, 1.0, 3)
+    assert len(plane.silhouette([0.0, 0.0, 1.0])) == 12
+    assert plane.silhouette([1.0, 0.0, 0.0]) == plane.silhouette([0.0, 0.0, 1.0])
+
+    with pytest.raises(ValueError):
+        plane.silhouette([0.0, 0.0, 0.0])
+
+
+def test_recompute_normals_for_updates_only_the_listed_vertices():
+    import pytest
+    from meshes import uv_sphere
+
+    sphere = uv_sphere()
+    with pytest.raises(ValueError, match="compute_normals"):
+        sphere.recompute_normals_for([0])
+
+    sphere.compute_normals()
+    # a non-uniform scale maps the stored normals differently from recomputing them
+    sphere.transform([[1.0, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0, 0.0], [0.0, 0.0, 3.0, 0.0], [0.0, 0.0, 0.0, 1.0]])
+    expected = sphere.vertex_normals()
+
+    corner_of = {}
+    for face, indices in enumerate(sphere.faces):
+        for corner, vertex in enumerate(indices):
+            corner_of[vertex] = (face, corner)
+
+    def stored(vertex):
+        face, corner = corner_of[vertex]
+        bary = [0.0, 0.0, 0.0]
+        bary[corner] = 1.0
+        return sphere.interpolate_at(face, bary)[1]
+
+    untouched = {v: stored(v) for v in range(sphere.vertex_count())}
+    sphere.recompute_normals_for([30, 31, 100])
+
+    for v in range(sphere.vertex_count()):
+        if v in (30, 31, 100):
+            assert all(abs(a - b) < 1e-6 for a, b in zip(stored(v), expected[v]))
+        else:
+            assert stored(v) == untouched[v]
+    assert any(abs(a - b) > 1e-3 for a, b in zip(untouched[30], expected[30]))
+
+    with pytest.raises(IndexError):
+        sphere.recompute_normals_for([1, sphere.vertex_count()])
+
+
+def test_spike_on_a_grid_is_found_and_flattened():
+    import pytest
+    from meshes import uv_sphere
+
+    vertices = [[float(x), float(y), 0.0] for y in range(7) for x in range(7)]
+    faces = []
+    for y in range(6):
+        for x in range(6):
+            a, b, c, d = y * 7 + x, y * 7 + x + 1, (y + 1) * 7 + x, (y + 1) * 7 + x + 1
+            faces += [[a, b, d], [a, d, c]]
+    vertices[24][2] = 5.0
+    mesh = meshalyzer.Mesh(vertices, faces)
+
+    assert mesh.detect_spikes(2.0) == [24]
+    assert mesh.remove_spikes(2.0) == 1
+    assert mesh.compute_bounds() == ([0.0, 0.0, 0.0], [6.0, 6.0, 0.0])
+    assert mesh.detect_spikes(2.0) == []
+    assert mesh.remove_spikes(2.0) == 0
+
+    assert uv_sphere().detect_spikes(1.0) == []
+    with pytest.raises(ValueError):
+        mesh.detect_spikes(0.0)
+
+
+def test_fix_normal_orientation_flips_only_the_inverted_normals(tmp_path):
+    from meshes import uv_sphere
+
+    sphere = uv_sphere()
+    normals = sphere.vertex_normals()
+    positions = {}
+    for f, face in enumerate(sphere.faces):
+        for corner in range(3):
+            positions[face[corner]] = sphere.interpolate_at(f, [1.0 if i == corner else 0.0 for i in range(3)])[0]
+
+    # every other vn line points inward
+    lines = ["v %r %r %r" % tuple(positions[v]) for v in range(len(normals))]
+    lines += ["vn %r %r %r" % tuple(-c if v % 2 else c for c in n) for v, n in enumerate(normals)]
+    lines += ["f %d %d %d" % tuple(v + 1 for v in face) for face in sphere.faces]
+    path = tmp_path / "inverted.obj"
+    path.write_text("\n".join(lines) + "\n")
+
+    mesh = meshalyzer.Mesh.from_obj(str(path))
+    assert mesh.fix_normal_orientation() == len(normals) // 2
+    assert mesh.fix_normal_orientation() == 0
+    for f in range(mesh.face_count()):
+        position, normal = mesh.interpolate_at(f, [1.0, 0.0, 0.0])
+        assert sum(p * n for p, n in zip(position, normal)) > 0.9
+
+    assert uv_sphere().fix_normal_orientation() == 0
+
+
+def test_iter_triangles_streams_face_corners():
+    import pytest
+    from meshes import CUBE_FACES, CUBE_VERTICES, unit_cube
+
+    triangles = unit_cube().iter_triangles()
+    assert iter(triangles) is triangles
+    assert list(triangles) == [tuple(CUBE_VERTICES[v] for v in face) for face in CUBE_FACES]
+    assert next(triangles, None) is None
+
+    area = sum(0.5 for a, b, c in unit_cube().iter_triangles())
+    assert area == 6.0
+
+    broken = meshalyzer.Mesh(CUBE_VERTICES, [[0, 1, 2], [0, 1, 8]])
+    triangles = broken.iter_triangles()
+    next(triangles)
+    with pytest.raises(IndexError):
+        next(triangles)
+
+
+def test_stats_of_closed_and_open_cube():
+    import json
+    from meshes import CUBE_FACES, CUBE_VERTICES, unit_cube
+
+    stats = unit_cube().stats()
+    assert stats["vertex_count"] == 8
+    assert stats["face_count"] == 12
+    assert abs(stats["surface_area"] - 6.0) < 1e-5
+    assert abs(stats["volume"] - 1.0) < 1e-5
+    assert stats["bounds"] == ([0.0, 0.0, 0.0], [1.0, 1.0, 1.0])
+    assert stats["centroid"] == [0.5, 0.5, 0.5]
+    assert stats["has_normals"] is False
+    assert stats["watertight"] is True
+    json.dumps(stats)
+
+    stats = meshalyzer.Mesh(CUBE_VERTICES, CUBE_FACES[:-1]).stats()
+    assert stats["volume"] is None
+    assert stats["watertight"] is False
+
+
+def test_diff_reports_what_a_step_changed():
+    from meshes import unit_cube, uv_sphere
+
+    sphere = uv_sphere()
+    same = meshalyzer.Mesh.diff(sphere, sphere)
+    assert same["vertex_count_delta"] == 0 and same["face_count_delta"] == 0
+    assert same["bounds_min_delta"] == [0.0, 0.0, 0.0] and same["bounds_max_delta"] == [0.0, 0.0, 0.0]
+    assert same["volume_delta"] == 0.0 and same["volume_relative_delta"] == 0.0
+    assert same["max_displacement"] == 0.0
+    assert same["euler_characteristic_delta"] == 0 and same["component_count_delta"] == 0
+    assert not same["topology_changed"]
+
+    moved = meshalyzer.Mesh.diff(unit_cube(), unit_cube().translated(0.0, 0.0, 0.5))
+    assert moved["bounds_min_delta"] == [0.0, 0.0, 0.5]
+    assert abs(moved["max_displacement"] - 0.5) < 1e-6
+    assert abs(moved["volume_delta"]) < 1e-6
+    assert not moved["topology_changed"]
+
+    simplified = sphere.simplify(100)
+    decimated = meshalyzer.Mesh.diff(sphere, simplified)
+    assert decimated["face_count_delta"] == simplified.face_count() - sphere.face_count()
+    assert abs(decimated["volume_relative_delta"]) < 0.1
+    assert not decimated["topology_changed"]
+
+    opened = meshalyzer.Mesh.diff(sphere, sphere.submesh(list(range(1, sphere.face_count()))))
+    assert opened["volume_delta"] is None
+    assert opened["euler_characteristic_delta"] == -1 and opened["topology_changed"]
+
+    two = meshalyzer.Mesh.diff(unit_cube(), meshalyzer.Mesh.merge(unit_cube(), uv_sphere().translated(3.0, 0.0, 0.0)))
+    assert two["component_count_delta"] == 1 and two["euler_characteristic_delta"] == 2
+
+
+def test_simplify_is_deterministic_and_keeps_the_surface_closed():
+    from meshes import uv_sphere
+
+    sphere = uv_sphere()
+    first = sphere.simplify(100)
+    second = sphere.simplify(100)
+
+    assert first.face_count() <= 100
+    assert first.faces == second.faces
+    assert first.compute_face_normals() == second.compute_face_normals()
+    assert first.face_areas() == second.face_areas()
+    assert first.stats()["watertight"]
+    assert abs(first.compute_volume() - sphere.compute_volume()) < 0.1 * sphere.compute_volume()
+
+
+def test_simplify_reports_progress_and_can_be_cancelled():
+    import pytest
+    from meshes import uv_sphere
+
+    reported = []
+    uv_sphere().simplify(100, progress=reported.append)
+    assert reported[-1] == 1.0
+    assert reported == sorted(reported)
+
+    with pytest.raises(KeyboardInterrupt):
+        uv_sphere().simplify(100, progress=lambda fraction: fraction < 0.5)
+
+
+def test_subdivide_loop_reports_progress_and_can_be_cancelled():
+    import pytest
+    from meshes import unit_cube
+
+    reported = []
+    cube = unit_cube()
+    cube.subdivide_loop(4, progress=reported.append)
+    assert reported == [0.25, 0.5, 0.75, 1.0]
+    assert cube.face_count() == 12 * 4 ** 4
+
+    def interrupt(fraction):
+        raise KeyboardInterrupt
+
+    cube = unit_cube()
+    cube.colorize_from_scalars([float(i) for i in range(8)], "viridis")
+    before = (cube.to_buffers(), cube.colors)
+    for callback in [lambda fraction: fraction < 0.5, interrupt]:
+        with pytest.raises(KeyboardInterrupt):
+            cube.subdivide_loop(3, progress=callback)
+        assert (cube.to_buffers(), cube.colors) == before
+
+
+def test_voxelize_reports_progress_and_can_be_cancelled():
+    import pytest
+    from meshes import unit_cube
+
+    reported = []
+    centers = unit_cube().voxelize(0.25, progress=reported.append)
+    assert sorted(centers) == sorted(
+        [[x * 0.25 + 0.125, y * 0.25 + 0.125, z * 0.25 + 0.125]
+         for x in range(4) for y in range(4) for z in range(4)]
+    )
+    assert reported == [0.25, 0.5, 0.75, 1.0]
+
+    # one report per slab of cells, even when there are more slabs than percent
+    reported = []
+    sphere = meshalyzer.Mesh.sphere(1.0, 3)
+    sphere.voxelize(0.01, progress=reported.append)
+    assert reported == sorted(reported) and reported[-1] == 1.0
+    assert len(reported) <= 101
+
+    def interrupt(fraction):
+        raise KeyboardInterrupt
+
+    for callback in [lambda fraction: fraction < 0.5, interrupt]:
+        with pytest.raises(KeyboardInterrupt):
+            sphere.voxelize(0.05, progress=callback)
+
+    assert meshalyzer.Mesh([], []).voxelize(1.0) == []
+    with pytest.raises(ValueError):
+        sphere.voxelize(0.0)
+
+
+def test_simplify_preserving_features_keeps_cube_corners():
+    from meshes import CUBE_VERTICES, subdivided_cube
+
+    cube = subdivided_cube()
+    simplified = cube.simplify(24, preserve_features=True)
+
+    assert simplified.face_count() <= 24
+    assert simplified.stats()["watertight"]
+    for corner in CUBE_VERTICES:
+        assert simplified.nearest_vertex(corner)[1] < 1e-4
+    assert abs(simplified.compute_volume() - 1.0) < 1e-3
+
+
+def test_simplify_preserving_boundary_keeps_the_open_rim():
+    from meshes import CUBE_VERTICES, subdivided_cube
+
+    cube = subdivided_cube()
+    # drop the top side so the box has a square rim at z = 1
+    box = cube.submesh([f for f in range(cube.face_count()) if cube.interpolate_at(f, [1 / 3, 1 / 3, 1 / 3])[0][2] < 1.0])
+    simplified = box.simplify(100, preserve_boundary=True, preserve_features=True)
+
+    assert simplified.face_count() <= 100
+    assert simplified.compute_bounds() == box.compute_bounds()
+    for corner in CUBE_VERTICES:
+        assert simplified.nearest_vertex(corner)[1] < 1e-4
+
+    assert len(meshalyzer.PyTopologyAnalyzer(simplified).find_holes()) == 1
+
+
+def test_collapse_edge_previews_and_applies_one_collapse():
+    import pytest
+    from meshes import uv_sphere
+
+    sphere = uv_sphere()
+    a, b = sphere.faces[100][0], sphere.faces[100][1]
+    cost, position = sphere.collapse_cost(a, b)
+    assert cost >= 0.0
+    assert sphere.face_count() == uv_sphere().face_count()
+
+    sphere.collapse_edge(a, b)
+    assert sphere.face_count() == uv_sphere().face_count() - 2
+    assert sphere.vertex_count() == uv_sphere().vertex_count()
+    assert sphere.find_isolated_vertices() == [b]
+    assert sphere.is_watertight()
+
+    face = next(f for f, corners in enumerate(sphere.faces) if a in corners)
+    bary = [1.0 if v == a else 0.0 for v in sphere.faces[face]]
+    moved = sphere.interpolate_at(face, bary)[0]
+    assert all(abs(p - q) < 1e-6 for p, q in zip(moved, position))
+
+    # stored normals are updated on every vertex whose faces moved, not only on a
+    shaded = uv_sphere()
+    shaded.compute_normals()
+    shaded.collapse_edge(a, b)
+    positions, indices = shaded.to_buffers()
+    fresh = meshalyzer.Mesh.from_buffers(positions, indices)
+    fresh.compute_normals()
+    for f in range(shaded.face_count()):
+        for bary in ([1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]):
+            stored, expected = shaded.interpolate_at(f, bary)[1], fresh.interpolate_at(f, bary)[1]
+            assert all(abs(p - q) < 1e-5 for p, q in zip(stored, expected))
+
+    with pytest.raises(meshalyzer.MeshTopologyError, match="not in the mesh"):
+        sphere.collapse_cost(a, b)
+    with pytest.raises(IndexError):
+        sphere.collapse_edge(a, sphere.vertex_count())
+
+    # the diagonal of a single quad joins two rim corners through the inside
+    plane = meshalyzer.Mesh.plane(1.0, 1.0, 1)
+    with pytest.raises(meshalyzer.MeshTopologyError, match="preserved boundary"):
+        plane.collapse_edge(0, 3, preserve_boundary=True)
+    assert plane.face_count() == 2
+
+
+def test_merge_welded_stitches_two_hemispheres():
+    from meshes import uv_sphere
+
+    sphere = uv_sphere()
+    top = [f for f in range(sphere.face_count()) if sphere.interpolate_at(f, [1 / 3, 1 / 3, 1 / 3])[0][2] > 0.0]
+    bottom = [f for f in range(sphere.face_count()) if f not in top]
+
+    merged = meshalyzer.Mesh.merge(sphere.submesh(top), sphere.submesh(bottom))
+    assert not merged.stats()["watertight"]
+
+    welded, count = meshalyzer.Mesh.merge_welded(sphere.submesh(top), sphere.submesh(bottom), 1e-5)
+    assert count == 24
+    assert welded.vertex_count() == sphere.vertex_count()
+    assert welded.stats()["watertight"]
+
+
+def test_weld_by_map_applies_a_given_correspondence():
+    import pytest
+
+    # two triangles of a quad with the shared edge duplicated, vertex 3 copies vertex 1
+    vertices = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]]
+    normals = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [-1.0, 0.0, 0.0], [0.0, -1.0, 0.0], [0.0, 0.0, -1.0]]
+    faces = [[0, 1, 2], [3, 4, 5]]
+
+    mesh = meshalyzer.Mesh(vertices, faces, normals)
+    assert mesh.weld_by_map([0, 1, 2, 1, 4, 5]) == 1
+    assert mesh.vertex_count() == 5
+    assert mesh.faces == [[0, 1, 2], [1, 3, 4]]
+    # the merged vertex keeps the normal of its target, the rest move down with their vertices
+    assert mesh.interpolate_at(1, [1.0, 0.0, 0.0])[1] == [0.0, 1.0, 0.0]
+    assert mesh.interpolate_at(1, [0.0, 1.0, 0.0])[1] == [0.0, -1.0, 0.0]
+
+    mesh.weld_by_map([0, 1, 2, 3, 2])
+    assert mesh.faces == [[0, 1, 2], [1, 3, 2]]
+    assert mesh.weld_by_map(list(range(4))) == 0
+
+    with pytest.raises(ValueError, match="remap entries"):
+        mesh.weld_by_map([0, 1, 2])
+    # chains have to be resolved by the caller
+    with pytest.raises(ValueError, match="itself"):
+        mesh.weld_by_map([0, 0, 1, 3])
+    with pytest.raises(IndexError):
+        mesh.weld_by_map([0, 1, 2, 4])
+    assert mesh.vertex_count() == 4
+
+    # faces that collapse stay until remove_degenerate_faces drops them
+    assert mesh.weld_by_map([0, 1, 1, 3]) == 1
+    assert mesh.faces == [[0, 1, 1], [1, 2, 1]]
+    assert mesh.remove_degenerate_faces() == 2
+
+
+def test_gauss_bonnet_on_sphere_torus_and_disk():
+    import math
+    from meshes import torus, uv_sphere
+
+    for sphere in (uv_sphere(), uv_sphere(rings=7, segments=9, radius=3.0)):
+        assert abs(sphere.total_gaussian_curvature() - 4.0 * math.pi) < 1e-4
+
+    ring = torus()
+    assert meshalyzer.PyTopologyAnalyzer(ring).is_watertight()
+    assert abs(ring.total_gaussian_curvature()) < 1e-4
+    curvature = ring.gaussian_curvature()
+    # the outer equator is convex, the inner one saddle shaped
+    assert curvature[0] > 0.0
+    assert curvature[8] < 0.0
+
+    # a flat fan is a disk: no interior curvature, 2 pi along the boundary
+    square = meshalyzer.Mesh([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0], [0.5, 0.5, 0.0], [7.0, 7.0, 7.0]],
+                             [[0, 1, 4], [1, 2, 4], [2, 3, 4], [3, 0, 4]])
+    curvature = square.gaussian_curvature()
+    assert abs(curvature[4]) < 1e-6
+    assert curvature[5] == 0.0
+    assert abs(square.total_gaussian_curvature() - 2.0 * math.pi) < 1e-5
+
+
+def test_mean_curvature_normals_of_sphere_point_inward():
+    from meshes import uv_sphere
+
+    sphere = uv_sphere(rings=24, segments=48, radius=2.0)
+    normals = sphere.mean_curvature_normals()
+
+    for face in (100, 500, 1000):
+        for corner, v in enumerate(sphere.faces[face]):
+            bary = [0.0, 0.0, 0.0]
+            bary[corner] = 1.0
+            position, _ = sphere.interpolate_at(face, bary)
+
+            n = normals[v]
+            assert abs(sum(c * c for c in n) ** 0.5 - 0.5) < 0.02
+            assert sum(a * b for a, b in zip(n, position)) < 0.0
+
+    open_sphere = sphere.submesh(list(range(96, sphere.face_count())))
+    assert open_sphere.mean_curvature_normals()[0] == [0.0, 0.0, 0.0]
+
+
+def test_closest_point_barycentric_coordinates():
+    mesh = meshalyzer.Mesh([[0.0, 0.0, 0.0], [3.0, 0.0, 0.0], [0.0, 3.0, 0.0]], [[0, 1, 2]])
+
+    face, point, bary, distance = mesh.closest_point([1.0, 1.0, 2.0])
+    assert face == 0
+    assert all(abs(a - b) < 1e-6 for a, b in zip(point, [1.0, 1.0, 0.0]))
+    assert all(abs(w - 1 / 3) < 1e-6 for w in bary)
+    assert abs(distance - 2.0) < 1e-6
+
+    _, point, bary, _ = mesh.closest_point([5.0, -1.0, 0.0])
+    assert point == [3.0, 0.0, 0.0]
+    assert bary == [0.0, 1.0, 0.0]
+
+    _, point, bary, distance = mesh.closest_point([2.0, 2.0, 0.0])
+    assert abs(sum(bary) - 1.0) < 1e-6 and min(bary) >= 0.0
+    assert mesh.interpolate_at(0, bary)[0] == point
+    assert abs(distance - 2 ** 0.5 / 2) < 1e-6
+
+
+def test_closest_points_matches_single_queries_in_order():
+    import pytest
+    from meshes import uv_sphere
+
+    sphere = uv_sphere()
+    points = [[0.1 * i - 2.0, 0.05 * i, 1.5 - 0.07 * i] for i in range(40)]
+    assert sphere.closest_points(points) == [sphere.closest_point(p) for p in points]
+    assert sphere.closest_points([]) == []
+
+    with pytest.raises(meshalyzer.MeshGeometryError):
+        meshalyzer.Mesh([], []).closest_points(points)
+
+
+def test_offset_surface_of_sphere():
+    from meshes import uv_sphere
+
+    sphere = uv_sphere()
+    for distance in (0.1, -0.1):
+        offset = sphere.offset_surface(distance)
+        assert offset.faces == sphere.faces
+
+        radii = [r for _, r in offset.k_nearest_vertices([0.0, 0.0, 0.0], offset.vertex_count())]
+        assert abs(radii[0] - (1.0 + distance)) < 1e-3
+        assert abs(radii[-1] - (1.0 + distance)) < 1e-3
+
+
+def test_sample_surface_indexed_is_area_weighted_and_reproducible():
+    from meshes import CUBE_VERTICES
+
+    # the first triangle has three times the area of the second
+    vertices = [[0.0, 0.0, 0.0], [3.0, 0.0, 0.0], [0.0, 2.0, 0.0], [10.0, 0.0, 0.0], [11.0, 0.0, 0.0], [10.0, 2.0, 0.0]]
+    mesh = meshalyzer.Mesh(vertices, [[0, 1, 2], [3, 4, 5]])
+
+    positions, faces, barycentrics = mesh.sample_surface_indexed(4000, seed=7)
+    assert len(positions) == len(faces) == len(barycentrics) == 4000
+    assert (positions, faces, barycentrics) == mesh.sample_surface_indexed(4000, seed=7)
+    assert faces != mesh.sample_surface_indexed(4000, seed=8)[1]
+    assert abs(faces.count(0) / 4000 - 0.75) < 0.03
+
+    for position, face, bary in list(zip(positions, faces, barycentrics))[:50]:
+        assert abs(sum(bary) - 1.0) < 1e-5
+        assert all(c >= 0.0 for c in bary)
+        interpolated, _ = mesh.interpolate_at(face, bary)
+        assert all(abs(a - b) < 1e-5 for a, b in zip(position, interpolated))
+
+    flat = meshalyzer.Mesh(CUBE_VERTICES, [[0, 1, 1]])
+    assert flat.sample_surface_indexed(10) == ([], [], [])
+
+
+def test_min_wall_thickness_of_hollow_box():
+    from meshes import unit_cube
+
+    outer = unit_cube().scaled(10.0)
+    inner = unit_cube().scaled(6.0).translated(2.0, 2.0, 2.0)
+    inner.flip_normals()
+    hollow = meshalyzer.Mesh.merge(outer, inner)
+
+    thickness, point = hollow.min_wall_thickness(500)
+    assert abs(thickness - 2.0) < 1e-3
+    assert all(0.0 <= c <= 10.0 for c in point)
+    assert hollow.min_wall_thickness(500) == (thickness, point)
+
+
+def test_vertex_thickness_of_a_slab():
+    import pytest
+    from meshes import subdivided_cube
+
+    slab = subdivided_cube(10)
+    slab.transform([[10.0, 0.0, 0.0, 0.0], [0.0, 10.0, 0.0, 0.0], [0.0, 0.0, 0.5, 0.0], [0.0, 0.0, 0.0, 1.0]])
+    thickness = slab.vertex_thickness(16)
+
+    positions = {}
+    for f, face in enumerate(slab.faces):
+        for corner in range(3):
+            bary = [1.0 if i == corner else 0.0 for i in range(3)]
+            positions[face[corner]] = slab.interpolate_at(f, bary)[0]
+
+    # away from the rim every ray crosses straight through the slab
+    interior = [v for v, (x, y, z) in positions.items() if 1.0 <= x <= 9.0 and 1.0 <= y <= 9.0]
+    assert len(interior) == 2 * 81
+    assert all(abs(thickness[v] - 0.5) < 1e-4 for v in interior)
+    assert all(0.0 < t < 10.0 + 1e-4 for t in thickness)
+
+    plane = meshalyzer.Mesh([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]], [[0, 1, 2]])
+    assert plane.vertex_thickness(4) == [float("inf")] * 3
+    with pytest.raises(ValueError):
+        plane.vertex_thickness(0)
+
+
+def test_shortest_path_on_grid():
+    import pytest
+
+    vertices = [[float(x), float(y), 0.0] for y in range(3) for x in range(3)] + [[9.0, 9.0, 9.0]] * 3
+    faces = []
+    for j in range(2):
+        for i in range(2):
+            a, b, c, d = j * 3 + i, j * 3 + i + 1, (j + 1) * 3 + i, (j + 1) * 3 + i + 1
+            faces += [[a, b, d], [a, d, c]]
+    faces.append([9, 10, 11])
+    mesh = meshalyzer.Mesh(vertices, faces)
+
+    assert mesh.shortest_path(0, 8) == [0, 4, 8]
+    assert abs(mesh.path_length(0, 8) - 2 * 2 ** 0.5) < 1e-6
+    path = mesh.shortest_path(2, 6)
+    assert len(path) == 5 and path[0] == 2 and path[-1] == 6
+    assert abs(mesh.path_length(2, 6) - 4.0) < 1e-6
+    assert mesh.shortest_path(3, 3) == [3]
+
+    assert mesh.shortest_path(0, 9) == []
+    assert mesh.path_length(0, 9) is None
+    assert mesh.geodesic_distances(0)[9] == float("inf")
+
+    with pytest.raises(IndexError):
+        mesh.shortest_path(0, 12)
+
+
+def test_farthest_point_sampling_spreads_over_a_sphere():
+    import pytest
+    from meshes import uv_sphere
+
+    sphere = uv_sphere(rings=24, segments=48)
+    samples = sphere.farthest_point_sampling(20, 0)
+    assert len(set(samples)) == 20
+    # from the north pole the farthest vertex is the south pole, the last one
+    assert samples[:2] == [0, sphere.vertex_count() - 1]
+
+    # every sample is about as far from its nearest neighbor as the others
+    nearest = [min(sphere.geodesic_distances(a)[b] for b in samples if b != a) for a in samples]
+    mean = sum(nearest) / len(nearest)
+    deviation = (sum((d - mean) ** 2 for d in nearest) / len(nearest)) ** 0.5
+    assert deviation < 0.15 * mean
+    assert min(nearest) > 0.75 * mean
+
+    assert sphere.farthest_point_sampling(0, 5) == []
+    assert len(sphere.farthest_point_sampling(sphere.vertex_count(), 5)) == sphere.vertex_count()
+    with pytest.raises(ValueError):
+        sphere.farthest_point_sampling(sphere.vertex_count() + 1, 0)
+    with pytest.raises(IndexError):
+        sphere.farthest_point_sampling(3, sphere.vertex_count())
+
+
+def test_farthest_point_sampling_matches_a_full_search_per_sample():
+    from meshes import torus
+
+    # a few thousand vertices over two components, the second one far away and sheared so
+    # distances rarely tie
+    first = torus(rings=48, segments=64)
+    second = torus(rings=16, segments=24)
+    shear = [[1.0, 0.3, 0.0, 20.0], [0.0, 1.0, 0.0, 0.0], [0.0, 0.0, 1.0, 0.0], [0.0, 0.0, 0.0, 1.0]]
+    second.transform(shear)
+    mesh = meshalyzer.Mesh.merge(first, second)
+    count = 60
+    samples = mesh.farthest_point_sampling(count, 7)
+
+    # the same greedy choice with a complete Dijkstra from every sample
+    n = mesh.vertex_count()
+    nearest = [float("inf")] * n
+    expected = [7]
+    while len(expected) < count:
+        distances = mesh.geodesic_distances(expected[-1])
+        nearest = [min(a, b) for a, b in zip(nearest, distances)]
+        taken = set(expected)
+        expected.append(max((v for v in range(n) if v not in taken), key=lambda v: (nearest[v], -v)))
+
+    assert samples == expected
+    # the unreachable component is sampled right after the start
+    assert samples[1] >= first.vertex_count()
+
+
+def test_edge_flips_remove_slivers():
+    import math
+
+    vertices = [[-1.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.2, 0.0], [0.0, -0.2, 0.0]]
+    mesh = meshalyzer.Mesh(vertices, [[0, 1, 2], [1, 0, 3]])
+
+    def slivers():
+        count = 0
+        for face in mesh.faces:
+            p = [vertices[i] for i in face]
+            for i in range(3):
+                u = [p[(i + 1) % 3][k] - p[i][k] for k in range(3)]
+                w = [p[(i + 2) % 3][k] - p[i][k] for k in range(3)]
+                cos = sum(x * y for x, y in zip(u, w)) / math.hypot(*u) / math.hypot(*w)
+                if math.degrees(math.acos(cos)) < 15.0:
+                    count += 1
+                    break
+        return count
+
+    assert slivers() == 2
+    assert mesh.improve_by_edge_flips(10) == 1
+    assert slivers() == 0
+    assert sorted(sorted(face) for face in mesh.faces) == [[0, 2, 3], [1, 2, 3]]
+    assert mesh.compute_face_normals() == [[0.0, 0.0, 1.0], [0.0, 0.0, 1.0]]
+    assert mesh.improve_by_edge_flips(10) == 0
+
+
+def test_isotropic_remesh_evens_out_edge_lengths():
+    import statistics
+    from meshes import uv_sphere
+
+    sphere = uv_sphere()
+    before = meshalyzer.PyTopologyAnalyzer(sphere).edge_lengths()
+
+    remeshed = sphere.isotropic_remesh(0.15, 5)
+    analyzer = meshalyzer.PyTopologyAnalyzer(remeshed)
+    after = analyzer.edge_lengths()
+
+    assert abs(statistics.mean(after) - 0.15) < 0.015
+    assert statistics.pstdev(after) / statistics.mean(after) < 0.5 * statistics.pstdev(before) / statistics.mean(before)
+    assert max(after) < 1.5 * 0.15
+    assert analyzer.is_watertight()
+    assert abs(remeshed.compute_volume() - sphere.compute_volume()) < 0.02 * sphere.compute_volume()
+    assert remeshed.faces == sphere.isotropic_remesh(0.15, 5).faces
+
+
+def test_isotropic_remesh_keeps_open_boundaries():
+    import pytest
+    from meshes import subdivided_cube
+
+    cube = subdivided_cube(3)
+    box = cube.submesh([f for f in range(cube.face_count()) if cube.interpolate_at(f, [1 / 3, 1 / 3, 1 / 3])[0][2] < 1.0])
+    remeshed = box.isotropic_remesh(0.1, 3)
+
+    assert remeshed.face_count() > 10 * box.face_count()
+    assert remeshed.compute_bounds() == box.compute_bounds()
+    [(rim, perimeter)] = meshalyzer.PyTopologyAnalyzer(remeshed).classify_holes()
+    assert abs(perimeter - 4.0) < 1e-5
+    assert len(rim) == 48
+
+    with pytest.raises(ValueError):
+        box.isotropic_remesh(0.0, 3)
+
+
+def test_connected_components_of_separate_parts():
+    from meshes import unit_cube
+
+    cubes = meshalyzer.Mesh.merge(unit_cube(), unit_cube().translated(3.0, 0.0, 0.0))
+    triangle = meshalyzer.Mesh([[0.0, 5.0, 0.0], [1.0, 5.0, 0.0], [0.0, 6.0, 0.0], [9.0, 9.0, 9.0]], [[0, 1, 2]])
+    mesh = meshalyzer.Mesh.merge(cubes, triangle)
+
+    assert mesh.connected_components() == [list(range(12)), list(range(12, 24)), [24]]
+    assert mesh.component_count() == 3
+    assert meshalyzer.Mesh([], []).component_count() == 0
+
+
+def test_sanitize_drops_non_finite_vertices():
+    inf, nan = float("inf"), float("nan")
+    vertices = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [inf, 0.0, 0.0], [0.0, nan, 0.0]]
+    mesh = meshalyzer.Mesh(vertices, [[0, 1, 2], [0, 1, 3], [2, 4, 1]])
+
+    assert mesh.has_non_finite()
+    assert mesh.sanitize() == 2
+    assert not mesh.has_non_finite()
+    assert mesh.faces == [[0, 1, 2]]
+    assert mesh.vertex_count() == 3
+    assert mesh.compute_bounds() == ([0.0, 0.0, 0.0], [1.0, 1.0, 0.0])
+    assert mesh.sanitize() == 0
+
+
+def test_convex_hull_and_point_containment():
+    import pytest
+    from meshes import l_prism, uv_sphere
+
+    prism = l_prism()
+    hull = prism.convex_hull()
+    assert abs(hull.compute_volume() - 3.5) < 1e-5
+    assert hull.vertex_count() == 10
+    assert hull.is_convex()
+    assert not prism.is_convex()
+    assert uv_sphere().convex_hull().is_convex()
+
+    assert prism.contains_point([0.5, 0.5, 0.5])
+    assert prism.contains_point([1.5, 0.5, 0.5])
+    assert not prism.contains_point([1.5, 1.5, 0.5])
+    assert not prism.contains_point([0.5, 0.5, 1.5])
+
+    with pytest.raises(ValueError):
+        meshalyzer.Mesh([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [1.0, 1.0, 0.0]], [[0, 1, 2]]).convex_hull()
+
+
+def test_winding_number_tolerates_small_holes():
+    from meshes import l_prism, uv_sphere
+
+    sphere = uv_sphere()
+    assert abs(sphere.winding_number([0.0, 0.0, 0.0]) - 1.0) < 1e-4
+    assert abs(sphere.winding_number([0.0, 0.0, 2.0])) < 1e-4
+
+    # without the two faces of one quad ray parity depends on the ray, the winding number
+    # barely moves
+    holed = sphere.submesh(list(range(100)) + list(range(102, sphere.face_count())))
+    assert not holed.is_watertight()
+    assert abs(holed.winding_number([0.0, 0.0, 0.0]) - 1.0) < 0.02
+    assert abs(holed.winding_number([0.0, 0.3, -0.2]) - 1.0) < 0.02
+    assert holed.contains_point_robust([0.2, 0.1, 0.0])
+    assert not holed.contains_point_robust([1.5, 0.0, 0.0])
+    assert not holed.contains_point_robust([0.0, 0.0, 0.0], threshold=1.5)
+
+    prism = l_prism()
+    assert prism.contains_point_robust([1.5, 0.5, 0.5])
+    assert not prism.contains_point_robust([1.5, 1.5, 0.5])
+
+
+def test_signed_distance_is_negative_inside():
+    import pytest
+    from meshes import l_prism, uv_sphere
+
+    # the faceted sphere is slightly smaller than the unit sphere
+    center, outside = uv_sphere().signed_distance([[0.0, 0.0, 0.0], [0.0, 0.0, 3.0]])
+    assert -1.0 <= center < -0.95
+    assert abs(outside - 2.0) < 1e-5
+
+    # right around the reflex edge of the notch the sign comes from the edge pseudonormal
+    distances = l_prism().signed_distance([[0.999, 0.999, 0.5], [1.001, 1.001, 0.5], [1.5, 1.5, 0.5], [0.5, 0.5, 0.5]])
+    assert distances[0] == pytest.approx(-0.001 * 2**0.5, abs=1e-5)
+    assert distances[1] == pytest.approx(0.001, abs=1e-5)
+    assert distances[2] == pytest.approx(0.5, abs=1e-5)
+    assert distances[3] == pytest.approx(-0.5, abs=1e-5)
+
+    with pytest.raises(ValueError):
+        meshalyzer.Mesh([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]], [[0, 1, 2]]).signed_distance([[0.0, 0.0, 1.0]])
+    with pytest.raises(ValueError):
+        meshalyzer.Mesh([], []).signed_distance([[0.0, 0.0, 0.0]])
+
+
+def test_nested_shells_of_concentric_spheres():
+    from meshes import uv_sphere
+
+    patch = meshalyzer.Mesh([[0.1, 0.0, 0.0], [0.2, 0.0, 0.0], [0.1, 0.1, 0.0]], [[0, 1, 2]])
+    mesh = uv_sphere(radius=3.0) + uv_sphere(radius=2.0) + uv_sphere(radius=0.5) + patch
+    mesh += uv_sphere().translated(10.0, 0.0, 0.0)
+
+    # inner shells are reported inside every shell around them, the open patch contains nothing
+    assert mesh.nested_shells() == [(0, [1, 2, 3]), (1, [2, 3]), (2, [3]), (3, []), (4, [])]
+    assert uv_sphere().nested_shells() == [(0, [])]
+
+
+def test_convex_decomposition_splits_at_the_notch():
+    import pytest
+    from meshes import l_prism, torus, uv_sphere
+
+    pieces = l_prism().convex_decomposition(8, 0.01)
+    assert len(pieces) == 2
+    assert all(piece.is_convex() for piece in pieces)
+    assert sorted(round(piece.compute_volume(), 5) for piece in pieces) == [1.0, 2.0]
+
+    assert len(uv_sphere().convex_decomposition(8, 0.01)) == 1
+
+    pieces = torus().convex_decomposition(6, 0.01)
+    assert len(pieces) == 6
+    assert all(piece.is_convex() for piece in pieces)
+
+    with pytest.raises(ValueError):
+        l_prism().convex_decomposition(0, 0.01)
+    with pytest.raises(ValueError):
+        l_prism().convex_decomposition(4, -1.0)
+
+
+def test_ray_intersect_all_reports_every_crossing_once():
+    import pytest
+    from meshes import l_prism, unit_cube
+
+    cube = unit_cube()
+    hits = cube.ray_intersect_all([-1.0, -1.0, -1.0], [1.0, 1.0, 1.0])
+    assert len(hits) == 2
+    assert abs(hits[0][0] - 3 ** 0.5) < 1e-5
+    assert abs(hits[1][0] - 2 * 3 ** 0.5) < 1e-5
+
+    # enters through the diagonal shared by the two triangles of a side
+    hits = l_prism().ray_intersect_all([0.5, -1.0, 0.25], [0.0, 1.0, 0.0])
+    assert [round(t, 5) for t, _ in hits] == [1.0, 3.0]
+    # leaves through the notch of the L
+    hits = l_prism().ray_intersect_all([-1.0, 1.5, 0.5], [1.0, 0.0, 0.0])
+    assert [round(t, 5) for t, _ in hits] == [1.0, 2.0]
+
+    assert cube.ray_intersect_all([2.0, 2.0, 2.0], [1.0, 0.0, 0.0]) == []
+    with pytest.raises(ValueError):
+        cube.ray_intersect_all([0.0, 0.0, 0.0], [0.0, 0.0, 0.0])
+
+
+def test_plus_operators_merge_meshes():
+    from meshes import unit_cube, uv_sphere
+
+    a = unit_cube()
+    b = uv_sphere().translated(3.0, 0.0, 0.0)
+    b.compute_normals()
+
+    combined = a + b
+    merged = meshalyzer.Mesh.merge(a, b)
+    assert combined.faces == merged.faces
+    assert combined.compute_bounds() == merged.compute_bounds()
+    assert repr(combined) == repr(merged)
+
+    scene = meshalyzer.Mesh([], [])
+    for offset in range(3):
+        scene += a.translated(2.0 * offset, 0.0, 0.0)
+    assert scene.face_count() == 36
+    assert scene.compute_bounds() == ([0.0, 0.0, 0.0], [5.0, 1.0, 1.0])
+
+    # normals survive only when both sides have them
+    c = uv_sphere()
+    c.compute_normals()
+    c += b
+    assert repr(c).endswith("has_normals=true)")
+    c += c
+    assert c.face_count() == 4 * uv_sphere().face_count()
+    assert c.compute_bounds() == (uv_sphere() + b).compute_bounds()
+    c += a
+    assert repr(c).endswith("has_normals=false)")
+
+
+def test_ambient_occlusion_of_plane_sphere_and_crevice():
+    import pytest
+    from meshes import uv_sphere
+
+    plane = meshalyzer.Mesh([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]], [[0, 1, 2], [0, 2, 3]])
+    assert plane.ambient_occlusion(64) == [1.0] * 4
+
+    occlusion = uv_sphere().ambient_occlusion(32, seed=3)
+    assert sum(occlusion) / len(occlusion) > 0.99
+    assert occlusion == uv_sphere().ambient_occlusion(32, seed=3)
+
+    # narrow shaft, open at the top and wound to face inward, with a vertex in the middle of its floor
+    w, h = 0.1, 2.0
+    vertices = [[-w, -w, 0.0], [w, -w, 0.0], [w, w, 0.0], [-w, w, 0.0],
+                [-w, -w, h], [w, -w, h], [w, w, h], [-w, w, h], [0.0, 0.0, 0.0]]
+    faces = [[0, 1, 8], [1, 2, 8], [2, 3, 8], [3, 0, 8]]
+    for i in range(4):
+        j = (i + 1) % 4
+        faces += [[i, i + 4, j], [j, i + 4, j + 4]]
+    shaft = meshalyzer.Mesh(vertices, faces)
+    assert shaft.vertex_normals()[8] == [0.0, 0.0, 1.0]
+    assert shaft.ambient_occlusion(256)[8] < 0.05
+
+    with pytest.raises(ValueError):
+        plane.ambient_occlusion(0)
+
+
+def test_split_large_faces_keeps_the_mesh_conforming():
+    import pytest
+    from meshes import unit_cube
+
+    # one big triangle next to a small one sharing its long edge
+    vertices = [[0.0, 0.0, 0.0], [4.0, 0.0, 0.0], [0.0, 4.0, 0.0], [0.0, 0.0, -0.1]]
+    mesh = meshalyzer.Mesh(vertices, [[0, 1, 2], [0, 3, 1]])
+    mesh.compute_normals()
+
+    added = mesh.split_large_faces(1.0)
+    assert added == mesh.face_count() - 2
+    assert max(mesh.face_areas()) <= 1.0
+    assert abs(mesh.compute_surface_area() - 8.2) < 1e-4
+    assert repr(mesh).endswith("has_normals=true)")
+    # the shared edge was split on both sides, so it is still interior
+    [(_, perimeter)] = meshalyzer.PyTopologyAnalyzer(mesh).classify_holes()
+    assert perimeter == pytest.approx(4.0 + 32.0 ** 0.5 + 0.1 + 16.01 ** 0.5)
+
+    cube = unit_cube()
+    assert cube.split_large_faces(0.1) > 0
+    assert meshalyzer.PyTopologyAnalyzer(cube).is_watertight()
+    assert abs(cube.compute_volume() - 1.0) < 1e-5
+    assert cube.split_large_faces(0.1) == 0
+
+    with pytest.raises(ValueError):
+        cube.split_large_faces(0.0)
+
+
+def test_split_large_faces_keeps_groups_colors_uvs_and_normals(tmp_path):
+    # a flat square of two triangles with a material each
+    path = tmp_path / "square.obj"
+    path.write_text(
+        "v 0 0 0\nv 2 0 0\nv 2 2 0\nv 0 2 0\n"
+        "usemtl red\nf 1 2 3\n"
+        "usemtl blue\nf 1 3 4\n"
+    )
+    mesh = meshalyzer.Mesh.from_obj(str(path))
+    mesh.compute_normals()
+    mesh.colorize_from_scalars([0.0, 1.0, 2.0, 1.0], "grayscale")
+    mesh.generate_uvs("planar")
+    before = mesh.colors
+
+    assert mesh.split_large_faces(0.1) > 0
+    n = mesh.vertex_count()
+    assert len(mesh.face_groups) == mesh.face_count()
+    assert mesh.group_names == ["red", "blue"]
+    for group in [0, 1]:
+        area = sum(mesh.face_areas()[f] for f in mesh.faces_in_group(group))
+        assert abs(area - 2.0) < 1e-5
+        assert all(mesh.compute_face_normals()[f][2] > 0.99 for f in mesh.faces_in_group(group))
+
+    # the gray level is (x + y) / 4 at the corners, and averaging along edges keeps it that
+    assert mesh.colors[:4] == before and len(mesh.colors) == n
+    positions, _ = mesh.to_buffers()
+    for v in range(n):
+        gray = (positions[3 * v] + positions[3 * v + 1]) / 4.0
+        assert all(abs(c - gray) < 1e-5 for c in mesh.colors[v])
+
+    # the planar projection is affine, so interpolated uvs match projecting again
+    expected = meshalyzer.Mesh.from_buffers(positions, [i for face in mesh.faces for i in face])
+    expected.generate_uvs("planar")
+    assert all(
+        abs(a - b) < 1e-5 for uv, other in zip(mesh.uvs, expected.uvs) for a, b in zip(uv, other)
+    )
+    for f in range(mesh.face_count()):
+        _, normal = mesh.interpolate_at(f, [1.0 / 3.0] * 3)
+        assert abs(normal[2] - 1.0) < 1e-5
+
+
+def test_subdivide_loop_smooths_towards_the_limit_surface():
+    import math
+    from meshes import unit_cube
+
+    # an icosahedron's vertices all move inwards together, so it stays round
+    sphere = meshalyzer.Mesh.sphere(1.0, 0)
+    sphere.compute_normals()
+    sphere.subdivide_loop(3)
+    assert sphere.face_count() == 20 * 4 ** 3
+    assert sphere.vertex_count() == 10 * 4 ** 3 + 2
+    assert sphere.stats()["watertight"] and sphere.stats()["has_normals"]
+    positions, _ = sphere.to_buffers()
+    radii = [math.sqrt(sum(c * c for c in positions[i:i + 3])) for i in range(0, len(positions), 3)]
+    assert max(radii) - min(radii) < 0.03
+
+    cube = unit_cube()
+    cube.subdivide_loop(2)
+    assert cube.stats()["watertight"]
+    assert 0.3 < cube.compute_volume() < 0.6
+    # the surface is pulled in from every side of the box, by the same amount
+    low, high = cube.compute_bounds()
+    assert all(abs(c - 0.05) < 1e-5 for c in low) and all(abs(c - 0.95) < 1e-5 for c in high)
+
+    # the straight rim of a flat patch stays where it is, groups and colors carry over
+    plane = meshalyzer.Mesh.plane(2.0, 2.0, 2)
+    plane.colorize_from_scalars([float(i) for i in range(plane.vertex_count())], "grayscale")
+    before = plane.colors
+    plane.subdivide_loop(1)
+    assert plane.face_count() == 32
+    assert plane.compute_bounds() == ([-1.0, -1.0, 0.0], [1.0, 1.0, 0.0])
+    assert plane.colors[:len(before)] == before
+    assert len(plane.colors) == plane.vertex_count()
+
+
+def test_subdivide_loop_round_trips_through_half_edges():
+    import pytest
+    from meshes import uv_sphere
+
+    # no steps is just the conversion to half-edges and back, which keeps every face as it was
+    for mesh in [uv_sphere(), meshalyzer.Mesh.plane(2.0, 1.0, 3)]:
+        positions, indices = mesh.to_buffers()
+        faces = mesh.faces
+        mesh.subdivide_loop(0)
+        assert mesh.to_buffers() == (positions, indices)
+        assert mesh.faces == faces
+
+    # the second face runs along edge (0, 1) in the same direction as the first one
+    vertices = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, -1.0, 0.0]]
+    mesh = meshalyzer.Mesh(vertices, [[0, 1, 2], [0, 1, 3]])
+    with pytest.raises(meshalyzer.MeshTopologyError):
+        mesh.subdivide_loop(1)
+    with pytest.raises(meshalyzer.MeshTopologyError):
+        mesh.laplacian_smooth(1)
+    assert mesh.faces == [[0, 1, 2], [0, 1, 3]]
+
+    mesh.repair(False, 0.0)
+    mesh.subdivide_loop(1)
+    assert mesh.face_count() == 8
+
+
+def test_transfer_attributes_after_remeshing():
+    import pytest
+    from meshes import uv_sphere
+
+    source = uv_sphere()
+    source.compute_normals()
+    remeshed = uv_sphere()
+    remeshed.split_large_faces(0.01)
+    assert remeshed.transfer_attributes(uv_sphere()) == []
+    assert "has_normals=false" in repr(remeshed)
+
+    assert remeshed.transfer_attributes(source) == ["normals"]
+    for f in range(remeshed.face_count()):
+        position, normal = remeshed.interpolate_at(f, [1.0, 0.0, 0.0])
+        length = sum(c * c for c in position) ** 0.5
+        assert sum(p / length * n for p, n in zip(position, normal)) > 0.99
+
+    with pytest.raises(ValueError):
+        remeshed.transfer_attributes(meshalyzer.Mesh([], []))
+
+
+def test_transfer_attributes_interpolates_uvs():
+    source = meshalyzer.Mesh.plane(2.0, 2.0, 1)
+    source.generate_uvs("planar")
+    target = meshalyzer.Mesh.plane(2.0, 2.0, 4)
+    assert target.transfer_attributes(source) == ["uvs"]
+
+    # the planar projection is affine, so interpolating it across the source faces reproduces it
+    expected = meshalyzer.Mesh.plane(2.0, 2.0, 4)
+    expected.generate_uvs("planar")
+    assert len(target.uvs) == target.vertex_count()
+    for uv, want in zip(target.uvs, expected.uvs):
+        assert abs(uv[0] - want[0]) < 1e-5 and abs(uv[1] - want[1]) < 1e-5
+    # inner grid vertices land inside the source faces, not just on their corners
+    assert any(0.0 < uv[0] < 1.0 and uv not in source.uvs for uv in target.uvs)
+
+
+def test_faces_in_box_matches_a_full_scan():
+    import pytest
+    from meshes import uv_sphere
+
+    sphere = uv_sphere()
+    query_min, query_max = [0.2, -0.3, 0.1], [1.5, 0.4, 0.6]
+
+    expected = []
+    for f in range(sphere.face_count()):
+        corners = [sphere.interpolate_at(f, bary)[0] for bary in ([1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0])]
+        if all(min(c[axis] for c in corners) <= query_max[axis] and query_min[axis] <= max(c[axis] for c in corners)
+               for axis in range(3)):
+            expected.append(f)
+
+    faces = sphere.faces_in_box(query_min, query_max)
+    assert faces == expected
+    assert 0 < len(faces) < sphere.face_count()
+
+    region = sphere.submesh(faces)
+    assert region.face_count() == len(faces)
+
+    assert sphere.faces_in_box([5.0, 5.0, 5.0], [6.0, 6.0, 6.0]) == []
+    assert sphere.faces_in_box([-2.0, -2.0, -2.0], [2.0, 2.0, 2.0]) == list(range(sphere.face_count()))
+    with pytest.raises(ValueError):
+        sphere.faces_in_box([1.0, 0.0, 0.0], [0.0, 1.0, 1.0])
+
+
+def test_primitives_are_closed_and_outward():
+    import math
+
+    cube = meshalyzer.Mesh.cube(1.0)
+    assert abs(cube.compute_volume() - 1.0) < 1e-5
+    assert cube.is_watertight()
+
+    sphere = meshalyzer.Mesh.sphere(2.0, 3)
+    assert sphere.face_count() == 20 * 4 ** 3
+    assert sphere.is_watertight()
+    assert abs(sphere.compute_volume() - 4.0 / 3.0 * math.pi * 8.0) < 0.5
+    assert "has_normals=true" in repr(meshalyzer.Mesh.sphere(normals=True))
+
+    cylinder = meshalyzer.Mesh.cylinder(1.0, 2.0, 64)
+    assert cylinder.is_watertight()
+    assert abs(cylinder.compute_volume() - 2.0 * math.pi) < 0.05
+
+    plane = meshalyzer.Mesh.plane(2.0, 3.0, 4)
+    assert plane.face_count() == 32
+    assert plane.has_holes()
+    assert abs(plane.compute_surface_area() - 6.0) < 1e-5
+    assert all(abs(n[2] - 1.0) < 1e-6 for n in plane.vertex_normals())
+
+
+def test_cotangent_smoothing_does_not_slide_vertices():
+    import math
+    import pytest
+    from meshes import uv_sphere
+
+    def positions(mesh):
+        corners = {}
+        for f, face in enumerate(mesh.faces):
+            for k, v in enumerate(face):
+                bary = [0.0, 0.0, 0.0]
+                bary[k] = 1.0
+                corners[v] = (f, bary)
+        return [mesh.interpolate_at(*corners[v])[0] for v in sorted(corners)]
+
+    # the rings near the poles are much smaller than those around the equator, so the plain
+    # ring average pulls vertices towards the poles while the cotangent one only shrinks
+    def tangential_drift(weighting):
+        mesh = uv_sphere(12, 24)
+        before = positions(mesh)
+        mesh.laplacian_smooth(1, 0.5, weighting)
+        drift = 0.0
+        for p, q in zip(before, positions(mesh)):
+            radial = sum(p[i] * q[i] for i in range(3))
+            tangent = [q[i] - radial * p[i] for i in range(3)]
+            drift = max(drift, math.sqrt(sum(t * t for t in tangent)))
+        return drift
+
+    uniform = tangential_drift("uniform")
+    assert uniform > 1e-2
+    assert tangential_drift("cotangent") < uniform * 0.1
+
+    with pytest.raises(ValueError, match="weighting"):
+        uv_sphere().laplacian_smooth(1, 0.5, "harmonic")
+
+
+def test_curve_skeleton_of_a_tube_follows_its_axis():
+    import math
+    import pytest
+    from meshes import capped_tube
+
+    tube = capped_tube(rings=21, segments=16, radius=0.3, length=4.0)
+    assert tube.is_watertight()
+    nodes, edges = tube.curve_skeleton(20)
+
+    # the rings contract onto the axis and become one node each, joined in a chain
+    assert 10 <= len(nodes) <= 23
+    assert all(math.hypot(x, y) < 1e-3 for x, y, _ in nodes)
+    heights = [z for _, _, z in nodes]
+    assert min(heights) < 0.5 and max(heights) > 3.5
+    assert len(edges) == len(nodes) - 1
+    degrees = [0] * len(nodes)
+    for a, b in edges:
+        assert a < b
+        degrees[a] += 1
+        degrees[b] += 1
+    assert sorted(degrees) == [1, 1] + [2] * (len(nodes) - 2)
+
+    # without contraction every vertex stays its own node
+    nodes, edges = tube.curve_skeleton(0)
+    assert len(nodes) == 21 * 16 + 2
+    assert len(edges) == len(tube.faces) * 3 // 2
+
+    with pytest.raises(ValueError, match="nan"):
+        tube.curve_skeleton(5, float("nan"))
+
+
+def test_compute_tangents_follow_uv_directions():
+    import pytest
+
+    mesh = meshalyzer.Mesh.plane(2.0, 2.0, 2)
+    with pytest.raises(meshalyzer.MeshError, match="compute_normals"):
+        mesh.compute_tangents([[0.0, 0.0]] * 9)
+    mesh.compute_normals()
+
+    # u runs along x and v against y, so the bitangent is flipped to -y
+    uvs = [[i * 0.5, 1.0 - j * 0.5] for j in range(3) for i in range(3)]
+    tangents, bitangents = mesh.compute_tangents(uvs)
+    for t, b in zip(tangents, bitangents):
+        assert abs(t[0] - 1.0) < 1e-5 and abs(t[1]) < 1e-5 and abs(t[2]) < 1e-5
+        assert abs(b[1] + 1.0) < 1e-5 and abs(b[0]) < 1e-5 and abs(b[2]) < 1e-5
+
+    # all uvs equal: no gradient, but still a unit frame orthogonal to the normal
+    tangents, bitangents = mesh.compute_tangents([[0.5, 0.5]] * 9)
+    for t, b in zip(tangents, bitangents):
+        assert abs(sum(x * x for x in t) - 1.0) < 1e-5
+        assert abs(t[2]) < 1e-5 and abs(b[2]) < 1e-5
+
+    with pytest.raises(ValueError, match="uvs"):
+        mesh.compute_tangents(uvs[:-1])
+
+
+def test_generate_uvs_projections_and_seams():
+    import pytest
+    from meshes import uv_sphere
+
+    sphere = uv_sphere()
+    sphere.compute_normals()
+    added = sphere.generate_uvs("spherical")
+    assert added > 0
+    assert sphere.vertex_count() == uv_sphere().vertex_count() + added
+    assert sphere.stats()["has_uvs"]
+
+    uvs = sphere.uvs
+    assert len(uvs) == sphere.vertex_count()
+    for i in range(2):
+        assert abs(min(uv[i] for uv in uvs)) < 1e-6
+        assert abs(max(uv[i] for uv in uvs) - 1.0) < 1e-6
+    # no face interpolates across the seam
+    for face in sphere.faces:
+        us = [uvs[v][0] for v in face]
+        assert max(us) - min(us) <= 0.5
+    tangents, _ = sphere.compute_tangents()
+    assert len(tangents) == sphere.vertex_count()
+
+    cylinder = meshalyzer.Mesh.cylinder(1.0, 2.0, 16)
+    cylinder.generate_uvs("cylindrical")
+    assert min(uv[1] for uv in cylinder.uvs) == 0.0 and max(uv[1] for uv in cylinder.uvs) == 1.0
+
+    plane = meshalyzer.Mesh.plane(2.0, 1.0, 2)
+    assert plane.generate_uvs("planar") == 0
+    for i in range(2):
+        assert min(uv[i] for uv in plane.uvs) == 0.0 and max(uv[i] for uv in plane.uvs) == 1.0
+
+    with pytest.raises(ValueError, match="projection"):
+        plane.generate_uvs("cubic")
+
+
+def test_simplify_and_remesh_carry_normals():
+    sphere = meshalyzer.Mesh.sphere(1.0, 3, normals=True)
+
+    simplified = sphere.simplify(200)
+    assert "has_normals=true" in repr(simplified)
+    assert "has_normals=false" in repr(sphere.simplify(200, preserve_normals=False))
+    assert "has_normals=false" in repr(meshalyzer.Mesh.sphere(1.0, 3).simplify(200))
+
+    # transferred from the exact sphere normals, so they stay close to radial
+    for f in range(simplified.face_count()):
+        position, normal = simplified.interpolate_at(f, [1.0, 0.0, 0.0])
+        length = sum(p * p for p in position) ** 0.5
+        assert sum(position[i] / length * normal[i] for i in range(3)) > 0.99
+
+    assert "has_normals=true" in repr(sphere.isotropic_remesh(0.3, 2))
+    assert "has_normals=false" in repr(sphere.isotropic_remesh(0.3, 2, preserve_normals=False))
+
+
+def test_relative_tolerances_are_scale_invariant():
+    from meshes import CUBE_FACES, CUBE_VERTICES
+
+    # two copies of the unit cube, the second one nudged by 1e-5 of its diagonal
+    nudge = 3 ** 0.5 * 1e-5
+    vertices = CUBE_VERTICES + [[x + nudge, y, z] for x, y, z in CUBE_VERTICES]
+    faces = CUBE_FACES + [[a + 8, b + 8, c + 8] for a, b, c in CUBE_FACES]
+
+    merged = []
+    for scale in [1e-3, 1.0, 1e3]:
+        mesh = meshalyzer.Mesh([[c * scale for c in v] for v in vertices], faces)
+        assert abs(mesh.characteristic_length() - scale * (3 ** 0.5 + nudge)) < 1e-4 * scale
+        merged.append((mesh.remove_duplicate_vertices(-1e-4), mesh.faces))
+
+    assert merged[0] == merged[1] == merged[2]
+    assert merged[0][0] == 8
+    assert meshalyzer.Mesh(vertices, faces).remove_duplicate_vertices(-1e-6) == 0
+    assert meshalyzer.Mesh([], []).characteristic_length() == 0.0
+
+
+def test_zero_tolerance_welds_only_identical_positions():
+    # a grid of distinct vertices with every row repeated; -0.0 is the same position as 0.0
+    n = 200
+    vertices = [[float(i), float(j), 0.0] for j in range(2) for i in range(n)]
+    vertices += [[float(i) if i else -0.0, float(j), 0.0] for j in range(2) for i in range(n)]
+    vertices += [[1e30, 0.0, 0.0], [1e30, 0.0, 1e-3]]
+    faces = [[i, i + 1, i + n] for i in range(n - 1)]
+    faces += [[2 * n + i, 2 * n + i + 1, 3 * n + i] for i in range(n - 1)]
+    faces += [[4 * n, 4 * n + 1, 0]]
+
+    mesh = meshalyzer.Mesh(vertices, faces)
+    assert mesh.remove_duplicate_vertices(0.0) == 2 * n
+    assert mesh.vertex_count() == 2 * n + 2
+
+    # a tolerance far below the coordinates' spacing still welds only what it should
+    mesh = meshalyzer.Mesh(vertices, faces)
+    assert mesh.remove_duplicate_vertices(1e-30) == 2 * n
+    # and one large enough to reach across the pair far from the origin welds that too
+    mesh = meshalyzer.Mesh(vertices, faces)
+    assert mesh.remove_duplicate_vertices(0.5) == 2 * n + 1
+
+
+def test_colorize_from_scalars():
+    import pytest
+
+    plane = meshalyzer.Mesh.plane(1.0, 1.0, 4)
+    n = plane.vertex_count()
+    assert plane.colors is None
+
+    plane.colorize_from_scalars([3.0] * n, "viridis")
+    assert len(plane.colors) == n
+    assert all(color == plane.colors[0] for color in plane.colors)
+
+    # a ramp spans the map: jet runs from dark blue to dark red, grayscale from black to white
+    ramp = [float(i) for i in range(n)]
+    plane.colorize_from_scalars(ramp, "jet")
+    assert all(abs(a - b) < 1e-6 for a, b in zip(plane.colors[0], [0.0, 0.0, 0.5]))
+    assert all(abs(a - b) < 1e-6 for a, b in zip(plane.colors[-1], [0.5, 0.0, 0.0]))
+    plane.colorize_from_scalars(ramp, "grayscale")
+    assert plane.colors[0] == [0.0, 0.0, 0.0] and plane.colors[-1] == [1.0, 1.0, 1.0]
+    plane.colorize_from_scalars(ramp, "viridis")
+    assert plane.colors[0][2] > plane.colors[0][1] and plane.colors[-1][1] > plane.colors[-1][2]
+
+    with pytest.raises(ValueError, match="colormap"):
+        plane.colorize_from_scalars(ramp, "rainbow")
+    with pytest.raises(ValueError, match="values"):
+        plane.colorize_from_scalars(ramp[:-1], "jet")
+
+    assert plane.submesh([0]).colors == [plane.colors[i] for i in plane.faces[0]]
+    assert meshalyzer.Mesh.merge(plane, plane).colors == plane.colors + plane.colors
+    assert meshalyzer.Mesh.merge(plane, meshalyzer.Mesh.plane()).colors is None
+
+
+def test_colors_are_saved_and_transferred(tmp_path):
+    plane = meshalyzer.Mesh.plane(1.0, 1.0, 1)
+    plane.colorize_from_scalars([0.0, 1.0, 2.0, 3.0], "grayscale")
+
+    plane.save_ply(str(tmp_path / "plane.ply"), binary=False)
+    lines = (tmp_path / "plane.ply").read_text().splitlines()
+    assert "property uchar red" in lines
+    body = lines[lines.index("end_header") + 1:]
+    assert [line.split()[-3:] for line in body[:4]] == [["0"] * 3, ["85"] * 3, ["170"] * 3, ["255"] * 3]
+
+    finer = meshalyzer.Mesh.plane(1.0, 1.0, 2)
+    assert finer.transfer_attributes(plane) == ["colors"]
+    # the center lies on the diagonal between the darkest and brightest corner
+    assert all(abs(c - 0.5) < 1e-5 for c in finer.colors[4])
+
+
+def test_degenerate_edges_and_skipping_them():
+    import math
+
+    # the last vertex sits on top of vertex 0 and splits the first face of the sphere
+    rings, segments = 6, 8
+    vertices = [[0.0, 0.0, 1.0]]
+    for i in range(1, rings):
+        theta = math.pi * i / rings
+        for j in range(segments):
+            phi = 2.0 * math.pi * j / segments
+            vertices.append([math.sin(theta) * math.cos(phi), math.sin(theta) * math.sin(phi), math.cos(theta)])
+    vertices.append([0.0, 0.0, -1.0])
+    faces = [[0, 1 + j, 1 + (j + 1) % segments] for j in range(segments)]
+    for i in range(1, rings - 1):
+        for j in range(segments):
+            a, b = 1 + (i - 1) * segments + j, 1 + (i - 1) * segments + (j + 1) % segments
+            c, d = a + segments, b + segments
+            faces += [[a, c, d], [a, d, b]]
+    bottom = len(vertices) - 1
+    faces += [[bottom, 1 + (rings - 2) * segments + (j + 1) % segments, 1 + (rings - 2) * segments + j]
+              for j in range(segments)]
+
+    extra = len(vertices)
+    vertices.append([0.0, 0.0, 1.0])
+    a, b, c = faces[0]
+    faces[0:1] = [[a, b, extra], [b, c, extra], [c, a, extra]]
+    mesh = meshalyzer.Mesh(vertices, faces)
+
+    assert mesh.degenerate_edges(1e-6) == [(0, extra)]
+    assert mesh.degenerate_edges(0.0) == [(0, extra)]
+    assert meshalyzer.Mesh(vertices, faces[3:]).degenerate_edges(1e-6) == []
+
+    kept = [f for f in faces if not (0 in f and extra in f)]
+    reference = meshalyzer.Mesh(vertices, kept)
+    assert mesh.gaussian_curvature(skip_degenerate=1e-6) == reference.gaussian_curvature()
+    for n, m in zip(mesh.mean_curvature_normals(skip_degenerate=1e-6), reference.mean_curvature_normals()):
+        assert all(abs(x - y) < 1e-5 for x, y in zip(n, m))
+    assert mesh.total_gaussian_curvature(1e-6) == reference.total_gaussian_curvature()
+    assert len(mesh.gaussian_curvature()) == len(vertices)
+
+
+def test_constructor_keeps_given_normals():
+    import pytest
+
+    vertices = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]
+    # authored normals that compute_normals would replace with (0, 0, 1)
+    normals = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, -1.0]]
+    mesh = meshalyzer.Mesh(vertices, [[0, 1, 2]], normals=normals)
+
+    assert mesh.stats()["has_normals"]
+    for corner, normal in enumerate(normals):
+        bary = [0.0, 0.0, 0.0]
+        bary[corner] = 1.0
+        assert mesh.interpolate_at(0, bary)[1] == normal
+
+    assert not meshalyzer.Mesh(vertices, [[0, 1, 2]]).stats()["has_normals"]
+    with pytest.raises(ValueError, match="normals"):
+        meshalyzer.Mesh(vertices, [[0, 1, 2]], normals[:2])
+
+
+def test_boolean_results_are_closed_and_can_be_chained():
+    import pytest
+
+    big = meshalyzer.Mesh.cube(2.0)
+    corner = meshalyzer.Mesh.cube(1.0).translated(1.0, 0.3, 0.2)
+    side = meshalyzer.Mesh.cube(1.0).translated(-1.0, -0.25, 0.35)
+
+    # a cube cut out of the middle of a face leaves a pocket
+    pocket = meshalyzer.Mesh.difference(big, meshalyzer.Mesh.cube(1.0).translated(0.0, 0.0, 1.0))
+    assert pocket.stats()["watertight"]
+    assert pocket.compute_volume() == pytest.approx(7.5, abs=1e-4)
+
+    union = meshalyzer.Mesh.union(big, corner)
+    assert union.stats()["watertight"]
+    assert union.compute_volume() == pytest.approx(8.5, abs=1e-4)
+
+    # half of side overlaps the union, only that half is removed
+    chained = meshalyzer.Mesh.difference(union, side)
+    assert chained.stats()["watertight"]
+    assert chained.compute_volume() == pytest.approx(8.0, abs=1e-4)
+
+    # a ball away from both cuts comes out whole
+    both = meshalyzer.Mesh.intersection(chained, meshalyzer.Mesh.sphere(0.4, 2))
+    assert both.stats()["watertight"]
+    assert both.compute_volume() == pytest.approx(meshalyzer.Mesh.sphere(0.4, 2).compute_volume(), abs=1e-4)
@@ -0,0 +1,304 @@
+import meshalyzer
+
+
+def test_edge_length_statistics():
+    mesh = meshalyzer.Mesh([[0.0, 0.0, 0.0], [3.0, 0.0, 0.0], [0.0, 4.0, 0.0]], [[0, 1, 2]])
+    analyzer = meshalyzer.PyTopologyAnalyzer(mesh)
+
+    assert sorted(analyzer.edge_lengths()) == [3.0, 4.0, 5.0]
+    assert analyzer.min_edge_length() == 3.0
+    assert analyzer.max_edge_length() == 5.0
+    assert analyzer.mean_edge_length() == 4.0
+
+    empty = meshalyzer.PyTopologyAnalyzer(meshalyzer.Mesh([], []))
+    assert empty.edge_lengths() == []
+    assert empty.mean_edge_length() is None
+
+
+def test_cube_segments_into_its_sides():
+    from meshes import unit_cube
+
+    analyzer = meshalyzer.PyTopologyAnalyzer(unit_cube())
+
+    assert len(analyzer.sharp_edges(30.0)) == 12
+    segments = analyzer.segment_by_sharp_edges(30.0)
+    assert sorted(len(segment) for segment in segments) == [2] * 6
+    assert analyzer.segment_by_sharp_edges(120.0) == [list(range(12))]
+
+
+def test_classify_and_fill_small_holes():
+    vertices = [[float(x), float(y), 0.0] for y in range(4) for x in range(4)]
+    faces = []
+    for j in range(3):
+        for i in range(3):
+            if (i, j) == (1, 1):
+                continue
+            a, b, c, d = j * 4 + i, j * 4 + i + 1, (j + 1) * 4 + i, (j + 1) * 4 + i + 1
+            faces += [[a, b, d], [a, d, c]]
+    mesh = meshalyzer.Mesh(vertices, faces)
+
+    holes = meshalyzer.PyTopologyAnalyzer(mesh).classify_holes()
+    assert sorted((len(hole), perimeter) for hole, perimeter in holes) == [(4, 4.0), (12, 12.0)]
+
+    assert mesh.fill_holes_below(5.0) == 1
+    holes = meshalyzer.PyTopologyAnalyzer(mesh).classify_holes()
+    assert [perimeter for _, perimeter in holes] == [12.0]
+
+
+def test_face_adjacency_includes_non_manifold_neighbours():
+    vertices = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, -1.0, 0.0], [0.0, 0.0, 1.0], [5.0, 5.0, 5.0]]
+    faces = [[0, 1, 2], [1, 0, 3], [0, 1, 4], [4, 1, 5]]
+    analyzer = meshalyzer.PyTopologyAnalyzer(meshalyzer.Mesh(vertices, faces))
+
+    assert analyzer.face_adjacency() == [[1, 2], [0, 2], [0, 1, 3], [2]]
+    # (0, 1) has three faces, (1, 4) two and the rest one
+    assert analyzer.edge_classification() == (1, 7, 1)
+
+
+def test_edge_classification_of_closed_and_open_meshes():
+    from meshes import torus, unit_cube
+
+    assert meshalyzer.PyTopologyAnalyzer(unit_cube()).edge_classification() == (18, 0, 0)
+    assert meshalyzer.PyTopologyAnalyzer(torus()).edge_classification()[1:] == (0, 0)
+    plane = meshalyzer.Mesh.plane(1.0, 1.0, 2)
+    assert meshalyzer.PyTopologyAnalyzer(plane).edge_classification() == (8, 8, 0)
+
+
+def test_dual_graph_of_cube():
+    import math
+    from meshes import unit_cube
+
+    analyzer = meshalyzer.PyTopologyAnalyzer(unit_cube())
+    src, dst = analyzer.dual_graph()
+
+    # 18 shared edges, each linking its two faces in both directions
+    assert len(src) == len(dst) == 36
+    pairs = list(zip(src, dst))
+    assert pairs == sorted(pairs)
+    assert {(g, f) for f, g in pairs} == set(pairs)
+    assert [g for f, g in pairs if f == 0] == analyzer.face_adjacency()[0]
+
+    src, dst, weights = analyzer.dual_graph(edge_weights=True)
+    assert len(weights) == 36
+    # faces 0 and 1 share the diagonal of the bottom side
+    assert abs(weights[pairs.index((0, 1))] - math.sqrt(2.0)) < 1e-6
+    assert sorted(set(round(w, 4) for w in weights)) == [1.0, round(math.sqrt(2.0), 4)]
+
+
+def test_watertight_components_of_sphere_and_plane():
+    from meshes import uv_sphere
+
+    sphere = uv_sphere()
+    plane = meshalyzer.Mesh([[3.0, 0.0, 0.0], [4.0, 0.0, 0.0], [4.0, 1.0, 0.0], [3.0, 1.0, 0.0]], [[0, 1, 2], [0, 2, 3]])
+    analyzer = meshalyzer.PyTopologyAnalyzer(meshalyzer.Mesh.merge(sphere, plane))
+
+    assert not analyzer.is_watertight()
+    assert analyzer.watertight_components() == [True, False]
+    assert analyzer.open_components() == [1]
+
+    analyzer = meshalyzer.PyTopologyAnalyzer(meshalyzer.Mesh.merge(plane, sphere))
+    assert analyzer.watertight_components() == [False, True]
+    assert analyzer.open_components() == [0]
+
+
+def test_vertex_and_edge_face_lookups():
+    import pytest
+    from meshes import unit_cube
+
+    analyzer = meshalyzer.PyTopologyAnalyzer(unit_cube())
+
+    assert analyzer.vertex_faces(0) == [0, 4, 8]
+    assert analyzer.edge_faces(2, 1) == [0, 1]
+    assert analyzer.edge_faces(1, 2) == [0, 1]
+    assert analyzer.edge_faces(0, 7) == []
+
+    with pytest.raises(IndexError):
+        analyzer.vertex_faces(8)
+    with pytest.raises(IndexError):
+        analyzer.edge_faces(0, 8)
+
+
+def test_with_tolerance_sees_through_unwelded_seams():
+    from meshes import CUBE_FACES, CUBE_VERTICES
+
+    vertices = [list(v) for v in CUBE_VERTICES] + [[1.0 + 1e-6, 1.0, 1.0 - 1e-6]]
+    faces = [[8 if v == 7 else v for v in face] if i in (3, 7) else list(face) for i, face in enumerate(CUBE_FACES)]
+    mesh = meshalyzer.Mesh(vertices, faces)
+
+    assert not meshalyzer.PyTopologyAnalyzer(mesh).is_watertight()
+
+    analyzer = meshalyzer.PyTopologyAnalyzer.with_tolerance(mesh, 1e-4)
+    assert analyzer.is_watertight()
+    assert analyzer.find_holes() == []
+    assert analyzer.isolated_vertices() == [8]
+    assert mesh.vertex_count() == 9
+
+
+def test_coplanar_regions_of_cube_and_sphere():
+    from meshes import unit_cube, uv_sphere
+
+    regions = meshalyzer.PyTopologyAnalyzer(unit_cube()).coplanar_regions(1.0)
+    assert len(regions) == 6
+    assert all(len(region) == 2 for region in regions)
+
+    sphere = meshalyzer.PyTopologyAnalyzer(uv_sphere())
+    assert len(sphere.coplanar_regions(1.0)) > 100
+    assert len(sphere.coplanar_regions(180.0)) == 1
+
+
+def test_duplicate_faces_in_any_rotation_or_winding():
+    from meshes import CUBE_FACES, CUBE_VERTICES
+
+    a, b, c = CUBE_FACES[0]
+    faces = CUBE_FACES + [[b, c, a], [a, c, b], [c, b, a]]
+    mesh = meshalyzer.Mesh(CUBE_VERTICES, faces)
+
+    assert meshalyzer.PyTopologyAnalyzer(mesh).duplicate_faces() == [(0, 12), (0, 13), (0, 14)]
+    assert not meshalyzer.PyTopologyAnalyzer(mesh).is_watertight()
+
+    kept = meshalyzer.Mesh(CUBE_VERTICES, faces)
+    assert kept.remove_duplicate_faces() == 3
+    assert kept.faces == CUBE_FACES
+    assert meshalyzer.PyTopologyAnalyzer(kept).is_watertight()
+    assert kept.remove_duplicate_faces() == 0
+
+    # two copies of each winding cancel out completely
+    cancelled = meshalyzer.Mesh(CUBE_VERTICES, faces)
+    assert cancelled.remove_duplicate_faces(drop_opposite=True) == 4
+    assert cancelled.faces == CUBE_FACES[1:]
+
+    # a lone copy against the original winding takes only one original with it
+    single = meshalyzer.Mesh(CUBE_VERTICES, CUBE_FACES + [[a, c, b]])
+    assert single.remove_duplicate_faces(drop_opposite=True) == 2
+    assert single.faces == CUBE_FACES[1:]
+
+
+def test_mesh_watertight_queries_follow_edits():
+    from meshes import CUBE_FACES, CUBE_VERTICES
+
+    mesh = meshalyzer.Mesh(CUBE_VERTICES, CUBE_FACES[:-1])
+    assert not mesh.is_watertight()
+    assert mesh.has_holes()
+    assert not meshalyzer.PyTopologyAnalyzer(mesh).is_watertight()
+
+    # the cached topology is rebuilt after the faces change
+    assert mesh.fill_holes() == 1
+    assert mesh.is_watertight()
+    assert not mesh.has_holes()
+    assert meshalyzer.PyTopologyAnalyzer(mesh).is_watertight()
+
+    # and isn't carried over into the welded view of an unwelded copy
+    vertices = [list(v) for v in CUBE_VERTICES] + [[1.0 + 1e-6, 1.0, 1.0 - 1e-6]]
+    faces = [[8 if v == 7 else v for v in face] if i in (3, 7) else list(face) for i, face in enumerate(CUBE_FACES)]
+    seamed = meshalyzer.Mesh(vertices, faces)
+    assert not seamed.is_watertight()
+    assert meshalyzer.PyTopologyAnalyzer.with_tolerance(seamed, 1e-4).is_watertight()
+    assert not seamed.is_watertight()
+
+
+def test_has_holes_agrees_with_the_analyzer():
+    from meshes import CUBE_FACES, CUBE_VERTICES, unit_cube
+
+    assert not unit_cube().has_holes()
+    assert meshalyzer.Mesh(CUBE_VERTICES, CUBE_FACES[1:]).has_holes()
+    assert not meshalyzer.Mesh([], []).has_holes()
+
+    # a fin on a closed cube leaves one edge with three faces
+    fin = meshalyzer.Mesh(CUBE_VERTICES + [[0.5, 0.5, 3.0]], CUBE_FACES + [[0, 1, 8]])
+    assert fin.has_holes()
+    for mesh in (unit_cube(), fin):
+        assert mesh.has_holes() == (not meshalyzer.PyTopologyAnalyzer(mesh).is_watertight())
+
+
+def test_sphere_like_ignores_stray_vertices_and_rejects_pairs():
+    from meshes import torus, uv_sphere
+
+    tetra_vertices = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]
+    tetra_faces = [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]]
+
+    def analyzer(vertices, faces):
+        return meshalyzer.PyTopologyAnalyzer(meshalyzer.Mesh(vertices, faces))
+
+    tetra = analyzer(tetra_vertices, tetra_faces)
+    assert tetra.euler_characteristic() == 2
+    assert tetra.is_sphere_like()
+
+    stray = analyzer(tetra_vertices + [[5.0, 5.0, 5.0]], tetra_faces)
+    assert stray.euler_characteristic() == 2
+    assert stray.is_sphere_like()
+
+    # two separate tetrahedra add up to 4
+    shifted = [[x + 3.0, y, z] for x, y, z in tetra_vertices]
+    pair = analyzer(tetra_vertices + shifted, tetra_faces + [[v + 4 for v in face] for face in tetra_faces])
+    assert pair.euler_characteristic() == 4
+    assert pair.is_watertight()
+    assert not pair.is_sphere_like()
+
+    # two tetrahedra touching at a corner are connected and closed but pinched
+    touching = [[-x, -y, -z] for x, y, z in tetra_vertices[1:]]
+    pinched = analyzer(tetra_vertices + touching,
+                       tetra_faces + [[0 if v == 0 else v + 3 for v in face] for face in tetra_faces])
+    assert pinched.is_watertight()
+    assert not pinched.has_manifold_vertices()
+    assert not pinched.is_sphere_like()
+
+    assert meshalyzer.PyTopologyAnalyzer(uv_sphere()).is_sphere_like()
+    assert meshalyzer.PyTopologyAnalyzer(torus()).euler_characteristic() == 0
+    assert not meshalyzer.PyTopologyAnalyzer(torus()).is_sphere_like()
+
+
+def test_incremental_watertightness_matches_rebuild():
+    import random
+    from meshes import uv_sphere
+
+    mesh = uv_sphere(6, 8)
+    assert mesh.is_watertight()
+    # watertightness only depends on the faces
+    points = [[0.0, 0.0, 0.0]] * mesh.vertex_count()
+
+    rng = random.Random(7)
+    removed = []
+    for step in range(200):
+        if removed and (rng.random() < 0.5 or mesh.face_count() == 0):
+            face = removed.pop(rng.randrange(len(removed)))
+            assert mesh.add_face(face) == mesh.face_count() - 1
+        else:
+            removed.append(mesh.remove_face(rng.randrange(mesh.face_count())))
+        rebuilt = meshalyzer.Mesh(points, mesh.faces)
+        assert mesh.is_watertight() == rebuilt.is_watertight()
+
+    for face in removed:
+        mesh.add_face(face)
+    assert mesh.is_watertight()
+    mesh.add_face(mesh.faces[0])
+    assert not mesh.is_watertight()
+
+
+def test_fix_t_junctions_closes_a_split_seam():
+    import pytest
+
+    # Mesh.cube's layout, with the top side split at a new vertex 8 in the middle of edge
+    # (4, 5) that the -y side doesn't know about
+    vertices = [[x - 0.5, y - 0.5, z - 0.5] for z in (0, 1) for y in (0, 1) for x in (0, 1)]
+    vertices.append([0.0, -0.5, 0.5])
+    faces = [
+        [0, 2, 3], [0, 3, 1],
+        [4, 8, 6], [8, 5, 7], [8, 7, 6],
+        [0, 1, 5], [0, 5, 4],
+        [2, 6, 7], [2, 7, 3],
+        [0, 4, 6], [0, 6, 2],
+        [1, 3, 7], [1, 7, 5],
+    ]
+    mesh = meshalyzer.Mesh(vertices, faces)
+    assert not mesh.is_watertight()
+    assert mesh.find_t_junctions(1e-6) == [(8, (4, 5))]
+
+    assert mesh.fix_t_junctions(1e-6) == 1
+    assert mesh.is_watertight()
+    assert mesh.face_count() == 14
+    assert abs(mesh.compute_volume() - 1.0) < 1e-6
+    assert mesh.find_t_junctions(1e-6) == []
+    assert mesh.fix_t_junctions(1e-6) == 0
+
+    with pytest.raises(ValueError):
+        mesh.find_t_junctions(float("nan"))